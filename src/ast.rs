@@ -1,8 +1,10 @@
 use crate::stack::Stack;
+use crate::QuarterError;
 
 #[derive(Debug, Clone)]
 pub enum AstNode {
     PushNumber(i64),
+    PushFloat(f64),  // float literal - pushed onto the float stack
     CallWord(String),
     Sequence(Vec<AstNode>),
     IfThenElse {
@@ -21,6 +23,17 @@ pub enum AstNode {
         increment: i64,  // 1 for LOOP, variable for +LOOP
         conditional: bool,  // true for ?DO (skip if start >= limit), false for DO
     },
+    Case {
+        clauses: Vec<(Vec<AstNode>, Vec<AstNode>)>,  // (test before OF, body up to ENDOF)
+        default: Vec<AstNode>,  // code between the last ENDOF and ENDCASE
+    },
+    Locals {
+        count: usize,  // number of locals declared by {: ... :}
+        args: usize,  // the first `args` locals are initialised from the stack
+        body: Vec<AstNode>,  // code after :} (the scope of the locals)
+    },
+    LocalFetch(usize),  // push the nth local of the current frame
+    LocalStore(usize),  // TO <local> - pop into the nth local of the current frame
     PrintString(String),
     StackString(String),  // S" - push address and length
     CString(String),  // C" - push address of null-terminated string
@@ -30,19 +43,34 @@ pub enum AstNode {
     Unloop,  // Discard loop parameters (used before EXIT when exiting from within a loop)
     Execute,  // EXECUTE - takes xt from stack and executes the word
     InlineInstruction(String),  // INLINE directive - maps to LLVM instruction (e.g., "LLVM-ADD")
-    TickLiteral(String),  // ['] - compile-only, stores word name, pushes xt at runtime
     Find,  // FIND - searches dictionary for word name (c-addr -- c-addr 0 | xt 1 | xt -1)
+    Postpone(String),  // POSTPONE - compile-only, appends word to the definition being compiled
+    CompileLiteral,  // LITERAL - appends number from stack to the definition being compiled
+    CompileComma,  // COMPILE, - appends word given by xt to the definition being compiled
+    DeferFetch,  // DEFER@ - ( xt1 -- xt2 ) xt2 is the word deferred word xt1 executes
+    DeferStore,  // DEFER! - ( xt2 xt1 -- ) make deferred word xt1 execute xt2
+    WordList,  // WORDLIST - ( -- wid ) create a new empty wordlist
+    GetOrder,  // GET-ORDER - ( -- widn ... wid1 n ) wid1 is searched first
+    SetOrder,  // SET-ORDER - ( widn ... wid1 n -- ) n = -1 selects the minimum search order
+    GetCurrent,  // GET-CURRENT - ( -- wid ) wordlist new definitions go into
+    SetCurrent,  // SET-CURRENT - ( wid -- )
+    SearchWordlist,  // SEARCH-WORDLIST - ( c-addr u wid -- 0 | xt 1 | xt -1 )
+    ToName,  // >NAME - ( xt -- nt ) name token of the word xt executes, 0 if it names none
+    NameToString,  // NAME>STRING - ( nt -- c-addr u ) name of the word, kept in the name area
+    Catch,  // CATCH - ( i*x xt -- j*x 0 | i*x n ) execute xt, returning the code it throws
+    Throw,  // THROW - ( k*x n -- k*x | i*x n ) unwind to the innermost CATCH if n is non-zero
 }
 
 impl AstNode {
     /// Validate that all words referenced in this AST exist in the dictionary
-    pub fn validate(&self, dict: &crate::dictionary::Dictionary) -> Result<(), String> {
+    pub fn validate(&self, dict: &crate::dictionary::Dictionary) -> Result<(), QuarterError> {
         self.validate_with_name(dict, None)
     }
 
-    pub fn validate_with_name(&self, dict: &crate::dictionary::Dictionary, defining_word: Option<&str>) -> Result<(), String> {
+    pub fn validate_with_name(&self, dict: &crate::dictionary::Dictionary, defining_word: Option<&str>) -> Result<(), QuarterError> {
         match self {
             AstNode::PushNumber(_) => Ok(()),
+            AstNode::PushFloat(_) => Ok(()),
             AstNode::PrintString(_) => Ok(()),
             AstNode::StackString(_) => Ok(()),
             AstNode::CString(_) => Ok(()),
@@ -53,24 +81,33 @@ impl AstNode {
             AstNode::Execute => Ok(()),  // Execute resolves word at runtime, no compile-time validation
             AstNode::InlineInstruction(_) => Ok(()),  // Inline instructions are validated at JIT time
             AstNode::Find => Ok(()),  // Find searches dictionary at runtime, no compile-time validation
-            AstNode::TickLiteral(name) => {
-                // ['] validates word exists at compile time
-                if dict.has_word(name) {
-                    Ok(())
-                } else {
-                    Err(format!("Undefined word in [']:  {}", name))
-                }
-            }
+            AstNode::Postpone(_) => Ok(()),  // POSTPONE validates its word when parsed
+            AstNode::CompileLiteral => Ok(()),
+            AstNode::CompileComma => Ok(()),  // COMPILE, resolves word at runtime
+            AstNode::DeferFetch => Ok(()),  // DEFER@ and DEFER! resolve their xt at runtime
+            AstNode::DeferStore => Ok(()),
+            AstNode::WordList => Ok(()),  // Search-order words only touch the dictionary at runtime
+            AstNode::GetOrder => Ok(()),
+            AstNode::SetOrder => Ok(()),
+            AstNode::GetCurrent => Ok(()),
+            AstNode::SetCurrent => Ok(()),
+            AstNode::SearchWordlist => Ok(()),
+            AstNode::ToName => Ok(()),  // Name tokens are looked up at runtime
+            AstNode::NameToString => Ok(()),
+            AstNode::Catch => Ok(()),  // CATCH resolves its xt at runtime
+            AstNode::Throw => Ok(()),
+            AstNode::LocalFetch(_) => Ok(()),  // Local indices are resolved when parsed
+            AstNode::LocalStore(_) => Ok(()),
             AstNode::CallWord(name) => {
                 // Allow forward reference if this is the word being defined (for recursion)
                 if let Some(def_name) = defining_word
-                    && name.to_uppercase() == def_name.to_uppercase() {
+                    && *name == dict.definition_key(&def_name.to_uppercase()) {
                         return Ok(());
                     }
                 if dict.has_word(name) {
                     Ok(())
                 } else {
-                    Err(format!("Undefined word: {}", name))
+                    Err(QuarterError::UndefinedWord(name.clone()))
                 }
             }
             AstNode::Sequence(nodes) => {
@@ -114,9 +151,27 @@ impl AstNode {
                 }
                 Ok(())
             }
+            AstNode::Case { clauses, default } => {
+                for (test, body) in clauses {
+                    for node in test.iter().chain(body) {
+                        node.validate_with_name(dict, defining_word)?;
+                    }
+                }
+                for node in default {
+                    node.validate_with_name(dict, defining_word)?;
+                }
+                Ok(())
+            }
+            AstNode::Locals { body, .. } => {
+                for node in body {
+                    node.validate_with_name(dict, defining_word)?;
+                }
+                Ok(())
+            }
         }
     }
 
+    /// Execute the node as the body of a word: EXIT ends it early
     pub fn execute(
         &self,
         stack: &mut Stack,
@@ -124,183 +179,189 @@ impl AstNode {
         loop_stack: &mut crate::LoopStack,
         return_stack: &mut crate::ReturnStack,
         memory: &mut crate::Memory,
-    ) -> Result<(), String> {
+    ) -> Result<(), QuarterError> {
+        let flow = self.run(stack, dict, loop_stack, return_stack, memory)?;
+        word_finished(flow)
+    }
+
+    /// Execute the node, reporting an EXIT or LEAVE for the enclosing word or loop to act on
+    pub fn run(
+        &self,
+        stack: &mut Stack,
+        dict: &crate::dictionary::Dictionary,
+        loop_stack: &mut crate::LoopStack,
+        return_stack: &mut crate::ReturnStack,
+        memory: &mut crate::Memory,
+    ) -> Result<Flow, QuarterError> {
         match self {
             AstNode::PushNumber(n) => {
                 stack.push(*n, memory);
-                Ok(())
+                Ok(Flow::Next)
             }
-            AstNode::CallWord(name) => dict.execute_word(name, stack, loop_stack, return_stack, memory),
-            AstNode::Sequence(nodes) => {
-                for node in nodes {
-                    match node.execute(stack, dict, loop_stack, return_stack, memory) {
-                        Err(msg) if msg == "EXIT" => {
-                            // EXIT was called, stop processing and return success
-                            return Ok(());
-                        }
-                        Err(e) => return Err(e),
-                        Ok(()) => {}
-                    }
-                }
-                Ok(())
+            AstNode::PushFloat(r) => {
+                memory.fpush(*r)?;
+                Ok(Flow::Next)
+            }
+            AstNode::CallWord(name) => {
+                dict.execute_word(name, stack, loop_stack, return_stack, memory)?;
+                Ok(Flow::Next)
             }
+            AstNode::Sequence(nodes) => run_all(nodes, stack, dict, loop_stack, return_stack, memory),
             AstNode::IfThenElse {
                 then_branch,
                 else_branch,
             } => {
                 // Pop the condition from the stack
-                if let Some(condition) = stack.pop(memory) {
-                    if condition != 0 {
-                        // Non-zero is true in Forth
-                        for node in then_branch {
-                            match node.execute(stack, dict, loop_stack, return_stack, memory) {
-                                Err(msg) if msg == "EXIT" => return Err(msg),
-                                Err(e) => return Err(e),
-                                Ok(()) => {}
-                            }
-                        }
-                    } else if let Some(else_nodes) = else_branch {
-                        for node in else_nodes {
-                            match node.execute(stack, dict, loop_stack, return_stack, memory) {
-                                Err(msg) if msg == "EXIT" => return Err(msg),
-                                Err(e) => return Err(e),
-                                Ok(()) => {}
-                            }
-                        }
-                    }
-                    Ok(())
+                let condition = stack.pop(memory).ok_or_else(|| QuarterError::underflow("IF"))?;
+                if condition != 0 {
+                    // Non-zero is true in Forth
+                    run_all(then_branch, stack, dict, loop_stack, return_stack, memory)
+                } else if let Some(else_nodes) = else_branch {
+                    run_all(else_nodes, stack, dict, loop_stack, return_stack, memory)
                 } else {
-                    Err("Stack underflow in IF".to_string())
+                    Ok(Flow::Next)
                 }
             }
             AstNode::BeginUntil { body } => {
                 loop {
                     // Execute body
-                    for node in body {
-                        match node.execute(stack, dict, loop_stack, return_stack, memory) {
-                            Err(msg) if msg == "EXIT" => return Err(msg),
-                            Err(e) => return Err(e),
-                            Ok(()) => {}
-                        }
+                    let flow = run_all(body, stack, dict, loop_stack, return_stack, memory)?;
+                    if flow != Flow::Next {
+                        return Ok(flow);
                     }
                     // Check condition (top of stack)
-                    if let Some(condition) = stack.pop(memory) {
-                        if condition != 0 {
-                            break;  // Exit if true (-1)
-                        }
-                    } else {
-                        return Err("Stack underflow in UNTIL".to_string());
+                    let condition = stack.pop(memory).ok_or_else(|| QuarterError::underflow("UNTIL"))?;
+                    if condition != 0 {
+                        break;  // Exit if true (-1)
                     }
                 }
-                Ok(())
+                Ok(Flow::Next)
             }
             AstNode::BeginWhileRepeat { condition, body } => {
                 loop {
                     // Evaluate condition
-                    for node in condition {
-                        match node.execute(stack, dict, loop_stack, return_stack, memory) {
-                            Err(msg) if msg == "EXIT" => return Err(msg),
-                            Err(e) => return Err(e),
-                            Ok(()) => {}
-                        }
+                    let flow = run_all(condition, stack, dict, loop_stack, return_stack, memory)?;
+                    if flow != Flow::Next {
+                        return Ok(flow);
                     }
                     // Check if we should continue
-                    if let Some(cond) = stack.pop(memory) {
-                        if cond == 0 {
-                            break;  // Exit if false (0)
-                        }
-                    } else {
-                        return Err("Stack underflow in WHILE".to_string());
+                    let cond = stack.pop(memory).ok_or_else(|| QuarterError::underflow("WHILE"))?;
+                    if cond == 0 {
+                        break;  // Exit if false (0)
                     }
                     // Execute body
-                    for node in body {
-                        match node.execute(stack, dict, loop_stack, return_stack, memory) {
-                            Err(msg) if msg == "EXIT" => return Err(msg),
-                            Err(e) => return Err(e),
-                            Ok(()) => {}
-                        }
+                    let flow = run_all(body, stack, dict, loop_stack, return_stack, memory)?;
+                    if flow != Flow::Next {
+                        return Ok(flow);
                     }
                 }
-                Ok(())
+                Ok(Flow::Next)
             }
             AstNode::DoLoop { body, increment, conditional: _ } => {
                 // Pop limit and start from stack ( limit start -- )
-                if let (Some(start), Some(limit)) = (stack.pop(memory), stack.pop(memory)) {
-                    // Both DO and ?DO skip if start >= limit
-                    // (?DO explicitly documents this behavior, DO matches for safety)
-                    if start >= limit {
-                        // Don't execute - loop would run 0 times
-                        return Ok(());
-                    }
+                let (Some(start), Some(limit)) = (stack.pop(memory), stack.pop(memory)) else {
+                    return Err(QuarterError::underflow("DO"));
+                };
+                // Both DO and ?DO skip if start >= limit
+                // (?DO explicitly documents this behavior, DO matches for safety)
+                if start >= limit {
+                    // Don't execute - loop would run 0 times
+                    return Ok(Flow::Next);
+                }
 
-                    loop_stack.push_loop(start, limit);
-
-                    let result = loop {
-                        // Execute body
-                        let mut should_leave = false;
-                        for node in body {
-                            match node.execute(stack, dict, loop_stack, return_stack, memory) {
-                                Err(msg) if msg == "LEAVE" => {
-                                    // LEAVE was called, exit loop early
-                                    should_leave = true;
-                                    break;
-                                }
-                                Err(msg) if msg == "EXIT" => {
-                                    // EXIT was called, exit loop AND word
-                                    loop_stack.pop_loop();
-                                    return Err(msg);
-                                }
-                                Err(e) => {
-                                    loop_stack.pop_loop();
-                                    return Err(e);
-                                }
-                                Ok(()) => {}
-                            }
-                        }
+                loop_stack.push_loop(start, limit);
 
-                        if should_leave {
-                            break Ok(());
-                        }
+                let result = loop {
+                    // Execute body
+                    match run_all(body, stack, dict, loop_stack, return_stack, memory) {
+                        // LEAVE was called, exit loop early
+                        Ok(Flow::Leave) => break Ok(Flow::Next),
+                        Ok(Flow::Next) => {}
+                        // EXIT or an error ends the loop AND the word
+                        other => break other,
+                    }
 
-                        // Get increment value
-                        let inc = if *increment == 0 {
-                            // +LOOP: pop increment from stack
-                            if let Some(n) = stack.pop(memory) {
-                                n
-                            } else {
-                                loop_stack.pop_loop();
-                                return Err("Stack underflow in +LOOP".to_string());
-                            }
-                        } else {
-                            // LOOP: use fixed increment
-                            *increment
-                        };
-
-                        // Increment and check if done
-                        if !loop_stack.increment(inc) {
-                            break Ok(());
+                    // Get increment value
+                    let inc = if *increment == 0 {
+                        // +LOOP: pop increment from stack
+                        match stack.pop(memory) {
+                            Some(n) => n,
+                            None => break Err(QuarterError::underflow("+LOOP")),
                         }
+                    } else {
+                        // LOOP: use fixed increment
+                        *increment
                     };
 
-                    loop_stack.pop_loop();
-                    result
-                } else {
-                    Err("Stack underflow in DO".to_string())
+                    // Increment and check if done
+                    if !loop_stack.increment(inc) {
+                        break Ok(Flow::Next);
+                    }
+                };
+
+                loop_stack.pop_loop();
+                result
+            }
+            AstNode::Case { clauses, default } => {
+                // CASE ( x -- ): each clause pushes a test value, OF compares it with x
+                for (test, body) in clauses {
+                    let flow = run_all(test, stack, dict, loop_stack, return_stack, memory)?;
+                    if flow != Flow::Next {
+                        return Ok(flow);
+                    }
+                    let (Some(value), Some(selector)) = (stack.pop(memory), stack.pop(memory)) else {
+                        return Err(QuarterError::underflow("OF"));
+                    };
+                    if selector == value {
+                        // Match: OF consumed the selector, run the clause and leave the CASE
+                        return run_all(body, stack, dict, loop_stack, return_stack, memory);
+                    }
+                    // No match: keep the selector for the next clause
+                    stack.push(selector, memory);
+                }
+                // Default code sees the selector on top of the stack; ENDCASE drops it
+                let flow = run_all(default, stack, dict, loop_stack, return_stack, memory)?;
+                if flow != Flow::Next {
+                    return Ok(flow);
                 }
+                stack.pop(memory).ok_or_else(|| QuarterError::underflow("ENDCASE"))?;
+                Ok(Flow::Next)
+            }
+            AstNode::Locals { count, args, body } => {
+                // {: args | others -- :} takes `args` values from the stack, topmost last
+                let mut frame = vec![0; *count];
+                for slot in frame[..*args].iter_mut().rev() {
+                    *slot = stack.pop(memory).ok_or_else(|| QuarterError::underflow("{:"))?;
+                }
+
+                // Each call gets its own frame, dropped however the body finishes (including EXIT)
+                dict.push_local_frame(frame);
+                let result = run_all(body, stack, dict, loop_stack, return_stack, memory);
+                dict.pop_local_frame();
+                result
+            }
+            AstNode::LocalFetch(index) => {
+                stack.push(dict.get_local(*index)?, memory);
+                Ok(Flow::Next)
+            }
+            AstNode::LocalStore(index) => {
+                let value = stack.pop(memory).ok_or_else(|| QuarterError::underflow("TO"))?;
+                dict.set_local(*index, value)?;
+                Ok(Flow::Next)
             }
             AstNode::PrintString(s) => {
-                print!("{}", s);
-                Ok(())
+                memory.session().write_output(s.as_bytes());
+                Ok(Flow::Next)
             }
             AstNode::AbortQuote(s) => {
                 // ABORT" - ( flag -- )
                 // If flag is true, print message and abort
-                let flag = stack.pop(memory).ok_or("Stack underflow for ABORT\"")?;
+                let flag = stack.pop(memory).ok_or_else(|| QuarterError::underflow("ABORT\""))?;
                 if flag != 0 {
-                    eprintln!("{}", s);
-                    std::process::exit(-2);
+                    clear_stacks_unless_caught(stack, dict, loop_stack, return_stack, memory);
+                    return Err(QuarterError::AbortQuote(s.clone()));
                 }
-                Ok(())
+                Ok(Flow::Next)
             }
             AstNode::StackString(s) => {
                 // S" - Store string in memory and push address and length
@@ -319,7 +380,7 @@ impl AstNode {
                 // Push address and length onto stack
                 stack.push(addr, memory);
                 stack.push(len, memory);
-                Ok(())
+                Ok(Flow::Next)
             }
             AstNode::CString(s) => {
                 // C" - Store null-terminated string in memory and push address only
@@ -340,72 +401,33 @@ impl AstNode {
 
                 // Push address only (not length)
                 stack.push(addr, memory);
-                Ok(())
-            }
-            AstNode::Leave => {
-                // Signal to exit the loop
-                Err("LEAVE".to_string())
-            }
-            AstNode::Exit => {
-                // Signal to exit the current word
-                Err("EXIT".to_string())
+                Ok(Flow::Next)
             }
+            AstNode::Leave => Ok(Flow::Leave),
+            AstNode::Exit => Ok(Flow::Exit),
             AstNode::Unloop => {
                 // Pop loop control parameters from loop stack
                 // Used when exiting from within a loop (before EXIT)
                 loop_stack.pop_loop();
-                Ok(())
+                Ok(Flow::Next)
             }
             AstNode::Execute => {
                 // EXECUTE ( xt -- )
                 // Execute word from execution token
-                // xt is the address of a counted string (length byte + characters)
-                let xt = stack.pop(memory).ok_or("Stack underflow for EXECUTE")?;
-                let addr = xt as usize;
-
-                // Read the length byte
-                let len = memory.fetch_byte(addr)? as usize;
-
-                // Read the word name from memory
-                let mut word_name = String::with_capacity(len);
-                for i in 0..len {
-                    let byte = memory.fetch_byte(addr + 1 + i)? as u8;
-                    word_name.push(byte as char);
-                }
-
-                // Execute the word
-                dict.execute_word(&word_name, stack, loop_stack, return_stack, memory)?;
-                Ok(())
+                // 0 is never a valid xt; a DEFER word holds it until set with IS
+                let xt = stack.pop(memory).ok_or_else(|| QuarterError::underflow("EXECUTE"))?;
+                let key = xt_key(xt, dict)?;
+                dict.execute_word(&key, stack, loop_stack, return_stack, memory)?;
+                Ok(Flow::Next)
             }
             AstNode::InlineInstruction(instruction) => {
                 // Inline instructions can only be executed in JIT-compiled code
-                Err(format!("Inline instruction {} can only be used in JIT-compiled words", instruction))
-            }
-            AstNode::TickLiteral(word_name) => {
-                // ['] ( -- xt )
-                // Store word name as counted string at HERE, push xt (address)
-                let xt_addr = memory.here();
-                let name_bytes = word_name.as_bytes();
-
-                // Store length byte
-                memory.store_byte(xt_addr as usize, name_bytes.len() as i64)?;
-
-                // Store character bytes
-                for (offset, &byte) in name_bytes.iter().enumerate() {
-                    memory.store_byte(xt_addr as usize + 1 + offset, byte as i64)?;
-                }
-
-                // Advance HERE
-                memory.allot((1 + name_bytes.len()) as i64)?;
-
-                // Push xt (address of counted string) onto stack
-                stack.push(xt_addr, memory);
-                Ok(())
+                Err(QuarterError::Compile(format!("Inline instruction {} can only be used in JIT-compiled words", instruction)))
             }
             AstNode::Find => {
                 // FIND ( c-addr -- c-addr 0 | xt 1 | xt -1 )
                 // Search dictionary for word name given as counted string
-                let c_addr = stack.pop(memory).ok_or("Stack underflow for FIND")?;
+                let c_addr = stack.pop(memory).ok_or_else(|| QuarterError::underflow("FIND"))?;
 
                 // Read counted string
                 let len = memory.fetch_byte(c_addr as usize)? as usize;
@@ -418,23 +440,12 @@ impl AstNode {
                 let word_name_upper = word_name.to_uppercase();
 
                 // Search for the word
-                if dict.has_word(&word_name_upper) {
-                    // Word found - create xt and push result
-                    let xt_addr = memory.here();
-                    let name_bytes = word_name_upper.as_bytes();
-
-                    // Store counted string at HERE
-                    memory.store_byte(xt_addr as usize, name_bytes.len() as i64)?;
-                    for (offset, &byte) in name_bytes.iter().enumerate() {
-                        memory.store_byte(xt_addr as usize + 1 + offset, byte as i64)?;
-                    }
-                    memory.allot((1 + name_bytes.len()) as i64)?;
-
-                    // Push xt
-                    stack.push(xt_addr, memory);
+                if let Some(key) = dict.find(&word_name_upper) {
+                    // Word found - push its xt
+                    stack.push(dict.xt(&key).unwrap_or(0), memory);
 
                     // Push 1 if immediate, -1 if not
-                    if dict.is_immediate(&word_name_upper) {
+                    if dict.is_immediate(&key) {
                         stack.push(1, memory);
                     } else {
                         stack.push(-1, memory);
@@ -444,14 +455,197 @@ impl AstNode {
                     stack.push(c_addr, memory);
                     stack.push(0, memory);
                 }
-                Ok(())
+                Ok(Flow::Next)
+            }
+            AstNode::Postpone(word_name) => {
+                // Compile the postponed word into the definition being compiled
+                dict.compile_token(word_name.clone());
+                Ok(Flow::Next)
+            }
+            AstNode::CompileLiteral => {
+                // LITERAL ( x -- )
+                // Compile x so the definition pushes it at runtime
+                let value = stack.pop(memory).ok_or_else(|| QuarterError::underflow("LITERAL"))?;
+                dict.compile_token(value.to_string());
+                Ok(Flow::Next)
+            }
+            AstNode::CompileComma => {
+                // COMPILE, ( xt -- )
+                // Compile a call to the word the xt names
+                let xt = stack.pop(memory).ok_or_else(|| QuarterError::underflow("COMPILE,"))?;
+                dict.compile_token(xt_key(xt, dict)?);
+                Ok(Flow::Next)
+            }
+            AstNode::DeferFetch => {
+                // DEFER@ ( xt1 -- xt2 )
+                let xt = stack.pop(memory).ok_or_else(|| QuarterError::underflow("DEFER@"))?;
+                let addr = deferred_cell(xt, dict)?;
+                stack.push(memory.fetch(addr)?, memory);
+                Ok(Flow::Next)
+            }
+            AstNode::DeferStore => {
+                // DEFER! ( xt2 xt1 -- )
+                let xt = stack.pop(memory).ok_or_else(|| QuarterError::underflow("DEFER!"))?;
+                let target = stack.pop(memory).ok_or_else(|| QuarterError::underflow("DEFER!"))?;
+                let addr = deferred_cell(xt, dict)?;
+                memory.store(addr, target)?;
+                Ok(Flow::Next)
+            }
+            AstNode::WordList => {
+                stack.push(dict.new_wordlist(), memory);
+                Ok(Flow::Next)
+            }
+            AstNode::GetOrder => {
+                // GET-ORDER ( -- widn ... wid1 n )
+                let order = dict.get_order();
+                for &wid in order.iter().rev() {
+                    stack.push(wid, memory);
+                }
+                stack.push(order.len() as i64, memory);
+                Ok(Flow::Next)
+            }
+            AstNode::SetOrder => {
+                // SET-ORDER ( widn ... wid1 n -- )
+                let n = stack.pop(memory).ok_or_else(|| QuarterError::underflow("SET-ORDER"))?;
+                if n == -1 {
+                    dict.set_order(vec![crate::dictionary::FORTH_WORDLIST]);
+                    return Ok(Flow::Next);
+                }
+                let mut order = Vec::new();
+                for _ in 0..n {
+                    order.push(stack.pop(memory).ok_or_else(|| QuarterError::underflow("SET-ORDER"))?);
+                }
+                dict.set_order(order);
+                Ok(Flow::Next)
+            }
+            AstNode::GetCurrent => {
+                stack.push(dict.get_current(), memory);
+                Ok(Flow::Next)
+            }
+            AstNode::SetCurrent => {
+                let wid = stack.pop(memory).ok_or_else(|| QuarterError::underflow("SET-CURRENT"))?;
+                dict.set_current(wid);
+                Ok(Flow::Next)
+            }
+            AstNode::SearchWordlist => {
+                // SEARCH-WORDLIST ( c-addr u wid -- 0 | xt 1 | xt -1 )
+                let wid = stack.pop(memory).ok_or_else(|| QuarterError::underflow("SEARCH-WORDLIST"))?;
+                let len = stack.pop(memory).ok_or_else(|| QuarterError::underflow("SEARCH-WORDLIST"))?;
+                let addr = stack.pop(memory).ok_or_else(|| QuarterError::underflow("SEARCH-WORDLIST"))?;
+
+                let mut word_name = String::with_capacity(len as usize);
+                for offset in 0..len {
+                    word_name.push(memory.fetch_byte((addr + offset) as usize)? as u8 as char);
+                }
+
+                match dict.search_wordlist(&word_name.to_uppercase(), wid) {
+                    Some(key) => {
+                        stack.push(dict.xt(&key).unwrap_or(0), memory);
+                        stack.push(if dict.is_immediate(&key) { 1 } else { -1 }, memory);
+                    }
+                    None => stack.push(0, memory),
+                }
+                Ok(Flow::Next)
+            }
+            AstNode::ToName => {
+                // >NAME ( xt -- nt )
+                // Every word has one name, so its xt doubles as its name token
+                let xt = stack.pop(memory).ok_or_else(|| QuarterError::underflow(">NAME"))?;
+                stack.push(if dict.xt_key(xt).is_some() { xt } else { 0 }, memory);
+                Ok(Flow::Next)
+            }
+            AstNode::NameToString => {
+                // NAME>STRING ( nt -- c-addr u )
+                let nt = stack.pop(memory).ok_or_else(|| QuarterError::underflow("NAME>STRING"))?;
+                let (addr, len) = dict.name_string(nt, memory)?;
+                stack.push(addr, memory);
+                stack.push(len, memory);
+                Ok(Flow::Next)
+            }
+            AstNode::Catch => {
+                // CATCH ( i*x xt -- j*x 0 | i*x n )
+                if stack.is_empty() {
+                    return Err(QuarterError::underflow("CATCH"));
+                }
+                dict.push_catch_frame(crate::CatchFrame {
+                    sp: stack.get_sp() - 8,
+                    rp: return_stack.get_rp(),
+                    loop_depth: loop_stack.depth(),
+                    capture_depth: memory.session().capture_depth(),
+                });
+                let result = AstNode::Execute.execute(stack, dict, loop_stack, return_stack, memory);
+                let frame = dict.pop_catch_frame().ok_or_else(|| QuarterError::Other("CATCH frame lost".to_string()))?;
+                match result {
+                    Ok(()) => stack.push(0, memory),
+                    Err(e) => {
+                        // Unwind to the stack depths CATCH started with, then push the code
+                        memory.take_throw();
+                        stack.set_sp(frame.sp);
+                        return_stack.set_rp(frame.rp);
+                        loop_stack.truncate(frame.loop_depth);
+                        memory.session().truncate_captures(frame.capture_depth);
+                        stack.push(e.code(), memory);
+                    }
+                }
+                Ok(Flow::Next)
+            }
+            AstNode::Throw => {
+                // THROW ( k*x n -- k*x | i*x n )
+                let code = stack.pop(memory).ok_or_else(|| QuarterError::underflow("THROW"))?;
+                if code == 0 {
+                    return Ok(Flow::Next);
+                }
+                if code == -1 || code == -2 {
+                    clear_stacks_unless_caught(stack, dict, loop_stack, return_stack, memory);
+                }
+                Err(QuarterError::from_code(code))
             }
         }
     }
 
+    /// Check whether this AST appends to the definition being compiled
+    /// (POSTPONE, LITERAL, COMPILE,), uses the search order or maps xts to names.
+    /// Such words only work under the outer interpreter, so they are never handed
+    /// to the JIT/AOT compiler
+    pub fn compiles_code(&self) -> bool {
+        self.any_node(&|node| match node {
+            AstNode::Postpone(_) | AstNode::CompileLiteral | AstNode::CompileComma
+            | AstNode::WordList | AstNode::GetOrder | AstNode::SetOrder
+            | AstNode::GetCurrent | AstNode::SetCurrent | AstNode::SearchWordlist => true,
+            // Which word an xt names is only known to the dictionary, and an AOT
+            // executable has none, so >NAME and NAME>STRING stay interpreted
+            AstNode::ToName | AstNode::NameToString => true,
+            _ => false,
+        })
+    }
+
+    /// Check whether `pred` holds for this node or any node nested in it
+    pub fn any_node(&self, pred: &dyn Fn(&AstNode) -> bool) -> bool {
+        if pred(self) {
+            return true;
+        }
+        match self {
+            AstNode::Sequence(nodes)
+            | AstNode::BeginUntil { body: nodes }
+            | AstNode::DoLoop { body: nodes, .. } => nodes.iter().any(|n| n.any_node(pred)),
+            AstNode::IfThenElse { then_branch, else_branch } => {
+                then_branch.iter().any(|n| n.any_node(pred))
+                    || else_branch.as_ref().is_some_and(|b| b.iter().any(|n| n.any_node(pred)))
+            }
+            AstNode::BeginWhileRepeat { condition, body } => {
+                condition.iter().any(|n| n.any_node(pred)) || body.iter().any(|n| n.any_node(pred))
+            }
+            AstNode::Case { clauses, default } => {
+                clauses.iter().any(|(test, body)| test.iter().chain(body).any(|n| n.any_node(pred)))
+                    || default.iter().any(|n| n.any_node(pred))
+            }
+            AstNode::Locals { body, .. } => body.iter().any(|n| n.any_node(pred)),
+            _ => false,
+        }
+    }
+
     /// Execute with tail call optimization check
-    /// Returns Ok(true) if a tail call was detected (should loop back)
-    /// Returns Ok(false) if execution completed normally
+    /// Returns Flow::TailCall if a tail call was detected (should loop back)
     pub fn execute_with_tco_check(
         &self,
         stack: &mut Stack,
@@ -460,7 +654,7 @@ impl AstNode {
         return_stack: &mut crate::ReturnStack,
         memory: &mut crate::Memory,
         word_name: &str,
-    ) -> Result<bool, String> {
+    ) -> Result<Flow, QuarterError> {
         // Execute all nodes except the last one normally
         match self {
             AstNode::Sequence(nodes) => {
@@ -472,7 +666,7 @@ impl AstNode {
                         if let AstNode::CallWord(name) = node
                             && name.to_uppercase() == word_name.to_uppercase() {
                                 // Tail call detected! Signal to loop back WITHOUT executing
-                                return Ok(true);
+                                return Ok(Flow::TailCall);
                             }
                         // Last node might be IfThenElse with tail calls
                         if let AstNode::IfThenElse { then_branch, else_branch } = node {
@@ -490,72 +684,120 @@ impl AstNode {
                     }
 
                     // Execute node normally (skipped if we returned above)
-                    match node.execute(stack, dict, loop_stack, return_stack, memory) {
-                        Err(msg) if msg == "EXIT" => {
-                            return Err(msg);
-                        }
-                        Err(e) => return Err(e),
-                        Ok(()) => {}
+                    let flow = node.run(stack, dict, loop_stack, return_stack, memory)?;
+                    if flow != Flow::Next {
+                        return Ok(flow);
                     }
                 }
-                Ok(false)  // Normal completion
+                Ok(Flow::Next)  // Normal completion
             }
             AstNode::IfThenElse { then_branch, else_branch } => {
                 // Pop the condition from the stack
-                if let Some(condition) = stack.pop(memory) {
-                    if condition != 0 {
-                        // Execute then branch
-                        for (i, node) in then_branch.iter().enumerate() {
-                            let is_last = i == then_branch.len() - 1;
-                            if is_last
-                                && let AstNode::CallWord(name) = node
-                                && name.to_uppercase() == word_name.to_uppercase() {
-                                    return Ok(true);  // Tail call
-                                }
-
-                            match node.execute(stack, dict, loop_stack, return_stack, memory) {
-                                Err(msg) if msg == "EXIT" => return Err(msg),
-                                Err(e) => return Err(e),
-                                Ok(()) => {}
-                            }
-                        }
-                    } else if let Some(else_nodes) = else_branch {
-                        // Execute else branch
-                        for (i, node) in else_nodes.iter().enumerate() {
-                            let is_last = i == else_nodes.len() - 1;
-                            if is_last
-                                && let AstNode::CallWord(name) = node
-                                && name.to_uppercase() == word_name.to_uppercase() {
-                                    return Ok(true);  // Tail call
-                                }
-
-                            match node.execute(stack, dict, loop_stack, return_stack, memory) {
-                                Err(msg) if msg == "EXIT" => return Err(msg),
-                                Err(e) => return Err(e),
-                                Ok(()) => {}
-                            }
+                let condition = stack.pop(memory).ok_or_else(|| QuarterError::underflow("IF"))?;
+                let branch = if condition != 0 {
+                    then_branch
+                } else if let Some(else_nodes) = else_branch {
+                    else_nodes
+                } else {
+                    return Ok(Flow::Next);
+                };
+                for (i, node) in branch.iter().enumerate() {
+                    let is_last = i == branch.len() - 1;
+                    if is_last
+                        && let AstNode::CallWord(name) = node
+                        && name.to_uppercase() == word_name.to_uppercase() {
+                            return Ok(Flow::TailCall);
                         }
+
+                    let flow = node.run(stack, dict, loop_stack, return_stack, memory)?;
+                    if flow != Flow::Next {
+                        return Ok(flow);
                     }
-                    Ok(false)
-                } else {
-                    Err("Stack underflow in IF".to_string())
                 }
+                Ok(Flow::Next)
             }
             AstNode::CallWord(name) => {
                 // Direct call - check if it's a tail call
                 if name.to_uppercase() == word_name.to_uppercase() {
-                    Ok(true)  // Tail call
+                    Ok(Flow::TailCall)
                 } else {
                     // Regular call, execute normally
                     dict.execute_word(name, stack, loop_stack, return_stack, memory)?;
-                    Ok(false)
+                    Ok(Flow::Next)
                 }
             }
             _ => {
                 // For other node types, just execute normally
-                self.execute(stack, dict, loop_stack, return_stack, memory)?;
-                Ok(false)
+                self.run(stack, dict, loop_stack, return_stack, memory)
             }
         }
     }
 }
+
+/// How a node finished when it did not fail
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Flow {
+    /// Go on with the next node
+    Next,
+    /// LEAVE: end the innermost DO loop
+    Leave,
+    /// EXIT: return from the word being run
+    Exit,
+    /// A call back into the word being run, in tail position (only from execute_with_tco_check)
+    TailCall,
+}
+
+/// Run nodes in order until one of them EXITs or LEAVEs
+fn run_all(
+    nodes: &[AstNode],
+    stack: &mut Stack,
+    dict: &crate::dictionary::Dictionary,
+    loop_stack: &mut crate::LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) -> Result<Flow, QuarterError> {
+    for node in nodes {
+        let flow = node.run(stack, dict, loop_stack, return_stack, memory)?;
+        if flow != Flow::Next {
+            return Ok(flow);
+        }
+    }
+    Ok(Flow::Next)
+}
+
+/// End of a word's body: EXIT returns normally, a LEAVE that no loop of the word took is an error
+pub fn word_finished(flow: Flow) -> Result<(), QuarterError> {
+    match flow {
+        Flow::Leave => Err(QuarterError::Other("LEAVE outside a DO loop".to_string())),
+        _ => Ok(()),
+    }
+}
+
+/// Key of the word `xt` names
+fn xt_key(xt: i64, dict: &crate::dictionary::Dictionary) -> Result<String, QuarterError> {
+    dict.xt_key(xt)
+        .map(str::to_string)
+        .ok_or(QuarterError::InvalidXt(xt))
+}
+
+/// Find the cell holding the target of the deferred word named by `xt`
+fn deferred_cell(xt: i64, dict: &crate::dictionary::Dictionary) -> Result<usize, QuarterError> {
+    let key = xt_key(xt, dict)?;
+    dict.get_deferred_addr(&key)
+        .ok_or_else(|| QuarterError::Other(format!("{} is not a deferred word", key)))
+}
+
+/// ABORT and ABORT" empty the stacks unless a CATCH will restore them
+pub(crate) fn clear_stacks_unless_caught(
+    stack: &mut Stack,
+    dict: &crate::dictionary::Dictionary,
+    loop_stack: &mut crate::LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    if !dict.in_catch() {
+        stack.set_sp(0);
+        while return_stack.pop(memory).is_some() {}
+        loop_stack.truncate(0);
+    }
+}
@@ -2,26 +2,28 @@
 ///
 /// This module exposes AST node inspection to Forth code via integer handles.
 /// Forth code can query AST structure and compile it using LLVM primitives.
+///
+/// Handles index the AST registry of the interpreter's session, so each interpreter has its own.
 use crate::ast::AstNode;
-use std::cell::RefCell;
 use std::collections::HashMap;
 
 /// AST node handle type
 pub type AstHandle = i64;
 
-// Thread-local AST registry
-thread_local! {
-    static AST_REGISTRY: RefCell<AstRegistry> = RefCell::new(AstRegistry::new());
-}
-
 /// Registry for AST nodes
-struct AstRegistry {
+pub struct AstRegistry {
     next_id: i64,
     nodes: HashMap<AstHandle, AstNode>,
 }
 
+impl Default for AstRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AstRegistry {
-    fn new() -> Self {
+    pub fn new() -> Self {
         AstRegistry {
             next_id: 1,
             nodes: HashMap::new(),
@@ -34,12 +36,14 @@ impl AstRegistry {
         self.next_id += 1;
         let _node_desc = match &node {
             AstNode::PushNumber(n) => format!("PushNumber({})", n),
+            AstNode::PushFloat(r) => format!("PushFloat({})", r),
             AstNode::CallWord(w) => format!("CallWord({})", w),
             AstNode::Sequence(v) => format!("Sequence({})", v.len()),
             AstNode::IfThenElse { .. } => "IfThenElse".to_string(),
             AstNode::BeginUntil { .. } => "BeginUntil".to_string(),
             AstNode::BeginWhileRepeat { .. } => "BeginWhileRepeat".to_string(),
             AstNode::DoLoop { .. } => "DoLoop".to_string(),
+            AstNode::Case { clauses, .. } => format!("Case({})", clauses.len()),
             AstNode::PrintString(_) => "PrintString".to_string(),
             AstNode::StackString(_) => "StackString".to_string(),
             AstNode::CString(_) => "CString".to_string(),
@@ -49,8 +53,25 @@ impl AstRegistry {
             AstNode::Unloop => "Unloop".to_string(),
             AstNode::InlineInstruction(_) => "InlineInstruction".to_string(),
             AstNode::Execute => "Execute".to_string(),
-            AstNode::TickLiteral(w) => format!("TickLiteral({})", w),
             AstNode::Find => "Find".to_string(),
+            AstNode::Postpone(w) => format!("Postpone({})", w),
+            AstNode::CompileLiteral => "CompileLiteral".to_string(),
+            AstNode::CompileComma => "CompileComma".to_string(),
+            AstNode::DeferFetch => "DeferFetch".to_string(),
+            AstNode::DeferStore => "DeferStore".to_string(),
+            AstNode::WordList => "WordList".to_string(),
+            AstNode::GetOrder => "GetOrder".to_string(),
+            AstNode::SetOrder => "SetOrder".to_string(),
+            AstNode::GetCurrent => "GetCurrent".to_string(),
+            AstNode::SetCurrent => "SetCurrent".to_string(),
+            AstNode::SearchWordlist => "SearchWordlist".to_string(),
+            AstNode::ToName => "ToName".to_string(),
+            AstNode::NameToString => "NameToString".to_string(),
+            AstNode::Catch => "Catch".to_string(),
+            AstNode::Throw => "Throw".to_string(),
+            AstNode::Locals { count, .. } => format!("Locals({})", count),
+            AstNode::LocalFetch(n) => format!("LocalFetch({})", n),
+            AstNode::LocalStore(n) => format!("LocalStore({})", n),
         };
         self.nodes.insert(handle, node);
         // eprintln!("[AST-REG] Registered handle {} = {} (total: {})", handle, _node_desc, self.nodes.len());
@@ -59,7 +80,9 @@ impl AstRegistry {
 
     /// Get node type as integer
     /// 1=PushNumber, 2=CallWord, 3=Sequence, 4=IfThenElse, 5=BeginUntil,
-    /// 6=BeginWhileRepeat, 7=DoLoop, 8=PrintString, 9=StackString, 10=Leave, 11=Exit, 12=InlineInstruction
+    /// 6=BeginWhileRepeat, 7=DoLoop, 8=PrintString, 9=StackString, 10=Leave, 11=Exit, 12=InlineInstruction,
+    /// 14=Execute, 22=Case, 23=DeferFetch, 24=DeferStore, 25=Locals, 26=LocalFetch, 27=LocalStore,
+    /// 28=PushFloat, 29-34=search-order words, 35-36=exceptions, 37-38=name-token words
     fn get_node_type(&self, handle: AstHandle) -> Result<i64, String> {
         // eprintln!("[AST-LOOKUP] Looking up handle {} (registry has {} nodes, next_id={})", handle, self.nodes.len(), self.next_id);
         let node = self.nodes.get(&handle)
@@ -84,79 +107,80 @@ impl AstRegistry {
             AstNode::Unloop => 13,
             AstNode::InlineInstruction(_) => 12,
             AstNode::Execute => 14,
-            AstNode::TickLiteral(_) => 15,
             AstNode::Find => 16,
             AstNode::AbortQuote(_) => 17,
+            // Compile-time only (never reach the LLVM compiler, see AstNode::compiles_code)
+            AstNode::Postpone(_) => 19,
+            AstNode::CompileLiteral => 20,
+            AstNode::CompileComma => 21,
+            AstNode::Case { .. } => 22,
+            AstNode::DeferFetch => 23,
+            AstNode::DeferStore => 24,
+            AstNode::Locals { .. } => 25,
+            AstNode::LocalFetch(_) => 26,
+            AstNode::LocalStore(_) => 27,
+            AstNode::PushFloat(_) => 28,
+            // Search order, compile-time only like POSTPONE
+            AstNode::WordList => 29,
+            AstNode::GetOrder => 30,
+            AstNode::SetOrder => 31,
+            AstNode::GetCurrent => 32,
+            AstNode::SetCurrent => 33,
+            AstNode::SearchWordlist => 34,
+            // Exceptions
+            AstNode::Catch => 35,
+            AstNode::Throw => 36,
+            // Name tokens need the dictionary, so words using them stay interpreted
+            AstNode::ToName => 37,
+            AstNode::NameToString => 38,
         })
     }
 
-    /// Get number value from PushNumber node
+    /// Get number value from PushNumber node (a PushFloat yields its IEEE-754 bits)
     fn get_number(&self, handle: AstHandle) -> Result<i64, String> {
         let node = self.nodes.get(&handle)
             .ok_or_else(|| format!("Invalid AST handle: {}", handle))?;
 
         match node {
             AstNode::PushNumber(n) => Ok(*n),
+            AstNode::PushFloat(r) => Ok(r.to_bits() as i64),
             _ => Err("AST node is not a PushNumber".to_string()),
         }
     }
 
-    /// Get word name from CallWord node (stores in memory at given address)
-    /// Returns length of string
-    fn get_word_name(&self, handle: AstHandle, memory: &mut crate::Memory, addr: usize) -> Result<i64, String> {
+    /// Get word name from CallWord node
+    fn get_word_name(&self, handle: AstHandle) -> Result<String, String> {
         let node = self.nodes.get(&handle)
             .ok_or_else(|| format!("Invalid AST handle: {}", handle))?;
 
         match node {
-            AstNode::CallWord(name) => {
-                // Store string bytes in memory
-                for (i, byte) in name.as_bytes().iter().enumerate() {
-                    memory.store_byte(addr + i, *byte as i64)
-                        .map_err(|e| format!("Failed to store string: {}", e))?;
-                }
-                Ok(name.len() as i64)
-            }
+            AstNode::CallWord(name) => Ok(name.clone()),
             _ => Err("AST node is not a CallWord".to_string()),
         }
     }
 
-    /// Get instruction name from InlineInstruction node (stores in memory at given address)
-    /// Returns length of string
-    fn get_inline_instruction(&self, handle: AstHandle, memory: &mut crate::Memory, addr: usize) -> Result<i64, String> {
+    /// Get instruction name from InlineInstruction node
+    fn get_inline_instruction(&self, handle: AstHandle) -> Result<String, String> {
         let node = self.nodes.get(&handle)
             .ok_or_else(|| format!("Invalid AST handle: {}", handle))?;
 
         match node {
-            AstNode::InlineInstruction(instruction) => {
-                // Store string bytes in memory
-                for (i, byte) in instruction.as_bytes().iter().enumerate() {
-                    memory.store_byte(addr + i, *byte as i64)
-                        .map_err(|e| format!("Failed to store string: {}", e))?;
-                }
-                Ok(instruction.len() as i64)
-            }
+            AstNode::InlineInstruction(instruction) => Ok(instruction.clone()),
             _ => Err("AST node is not an InlineInstruction".to_string()),
         }
     }
 
     /// Get string value from PrintString, StackString, or AbortQuote node
-    fn get_string(&self, handle: AstHandle, memory: &mut crate::Memory, addr: usize) -> Result<i64, String> {
+    fn get_string(&self, handle: AstHandle) -> Result<String, String> {
         let node = self.nodes.get(&handle)
             .ok_or_else(|| format!("Invalid AST handle: {}", handle))?;
 
-        let string = match node {
-            AstNode::PrintString(s) => s,
-            AstNode::StackString(s) => s,
-            AstNode::AbortQuote(s) => s,
-            _ => return Err("AST node is not a PrintString, StackString, or AbortQuote".to_string()),
-        };
-
-        // Store string bytes in memory
-        for (i, byte) in string.as_bytes().iter().enumerate() {
-            memory.store_byte(addr + i, *byte as i64)
-                .map_err(|e| format!("Failed to store string: {}", e))?;
+        match node {
+            AstNode::PrintString(s) => Ok(s.clone()),
+            AstNode::StackString(s) => Ok(s.clone()),
+            AstNode::AbortQuote(s) => Ok(s.clone()),
+            _ => Err("AST node is not a PrintString, StackString, or AbortQuote".to_string()),
         }
-        Ok(string.len() as i64)
     }
 
     /// Get number of children in a Sequence node
@@ -250,6 +274,49 @@ impl AstRegistry {
         }
     }
 
+    /// Get number of OF clauses in a Case
+    fn get_case_count(&self, handle: AstHandle) -> Result<i64, String> {
+        let node = self.nodes.get(&handle)
+            .ok_or_else(|| format!("Invalid AST handle: {}", handle))?;
+
+        match node {
+            AstNode::Case { clauses, .. } => Ok(clauses.len() as i64),
+            _ => Err("AST node is not a Case".to_string()),
+        }
+    }
+
+    /// Get test code (before OF) or body (OF to ENDOF) of the nth clause (returns Sequence handle)
+    fn get_case_clause(&mut self, handle: AstHandle, index: i64, body: bool) -> Result<AstHandle, String> {
+        let node = self.nodes.get(&handle)
+            .ok_or_else(|| format!("Invalid AST handle: {}", handle))?;
+
+        match node {
+            AstNode::Case { clauses, .. } => {
+                if index < 0 || index >= clauses.len() as i64 {
+                    return Err(format!("Case clause index out of bounds: {}", index));
+                }
+                let (test, clause_body) = &clauses[index as usize];
+                let seq = AstNode::Sequence(if body { clause_body.clone() } else { test.clone() });
+                Ok(self.register_node(seq))
+            }
+            _ => Err("AST node is not a Case".to_string()),
+        }
+    }
+
+    /// Get default code of a Case (returns Sequence handle, possibly empty)
+    fn get_case_default(&mut self, handle: AstHandle) -> Result<AstHandle, String> {
+        let node = self.nodes.get(&handle)
+            .ok_or_else(|| format!("Invalid AST handle: {}", handle))?;
+
+        match node {
+            AstNode::Case { default, .. } => {
+                let seq = AstNode::Sequence(default.clone());
+                Ok(self.register_node(seq))
+            }
+            _ => Err("AST node is not a Case".to_string()),
+        }
+    }
+
     /// Get loop increment (for DoLoop)
     fn get_loop_increment(&self, handle: AstHandle) -> Result<i64, String> {
         let node = self.nodes.get(&handle)
@@ -260,133 +327,181 @@ impl AstRegistry {
             _ => Err("AST node is not a DoLoop".to_string()),
         }
     }
+
+    /// Get number of locals, or how many of them are taken from the stack (for Locals)
+    fn get_locals_count(&self, handle: AstHandle, args_only: bool) -> Result<i64, String> {
+        let node = self.nodes.get(&handle)
+            .ok_or_else(|| format!("Invalid AST handle: {}", handle))?;
+
+        match node {
+            AstNode::Locals { count, args, .. } => Ok(if args_only { *args } else { *count } as i64),
+            _ => Err("AST node is not a Locals".to_string()),
+        }
+    }
+
+    /// Get code in the scope of the locals (returns Sequence handle)
+    fn get_locals_body(&mut self, handle: AstHandle) -> Result<AstHandle, String> {
+        let node = self.nodes.get(&handle)
+            .ok_or_else(|| format!("Invalid AST handle: {}", handle))?;
+
+        match node {
+            AstNode::Locals { body, .. } => {
+                let seq = AstNode::Sequence(body.clone());
+                Ok(self.register_node(seq))
+            }
+            _ => Err("AST node is not a Locals".to_string()),
+        }
+    }
+
+    /// Get frame index of the local read or written (for LocalFetch/LocalStore)
+    fn get_local_index(&self, handle: AstHandle) -> Result<i64, String> {
+        let node = self.nodes.get(&handle)
+            .ok_or_else(|| format!("Invalid AST handle: {}", handle))?;
+
+        match node {
+            AstNode::LocalFetch(index) | AstNode::LocalStore(index) => Ok(*index as i64),
+            _ => Err("AST node is not a LocalFetch or LocalStore".to_string()),
+        }
+    }
 }
 
 // =============================================================================
 // PUBLIC API FUNCTIONS
 // =============================================================================
 
-/// Register an AST node and return its handle
-pub fn ast_register_node(node: AstNode) -> AstHandle {
-    AST_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.register_node(node)
-    })
+/// Store a string's bytes in memory at `addr`, returning its length
+fn store_string(memory: &mut crate::Memory, addr: usize, text: &str) -> Result<i64, String> {
+    for (i, byte) in text.bytes().enumerate() {
+        memory.store_byte(addr + i, byte as i64)
+            .map_err(|e| format!("Failed to store string: {}", e))?;
+    }
+    Ok(text.len() as i64)
 }
 
-/// Clear the AST registry (for testing)
-pub fn ast_clear_registry() {
-    AST_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.nodes.clear();
-        registry.next_id = 1;
-    })
+/// Register an AST node and return its handle
+pub fn ast_register_node(registry: &mut AstRegistry, node: AstNode) -> AstHandle {
+    registry.register_node(node)
 }
 
 /// Get AST node type
 /// Stack: ( ast-handle -- type )
-pub fn ast_get_type(handle: i64) -> Result<i64, String> {
-    AST_REGISTRY.with(|cell| {
-        let registry = cell.borrow();
-        registry.get_node_type(handle)
-    })
+pub fn ast_get_type(registry: &AstRegistry, handle: i64) -> Result<i64, String> {
+    registry.get_node_type(handle)
 }
 
 /// Get number from PushNumber node
 /// Stack: ( ast-handle -- number )
-pub fn ast_get_number(handle: i64) -> Result<i64, String> {
-    AST_REGISTRY.with(|cell| {
-        let registry = cell.borrow();
-        registry.get_number(handle)
-    })
+pub fn ast_get_number(registry: &AstRegistry, handle: i64) -> Result<i64, String> {
+    registry.get_number(handle)
 }
 
 /// Get word name from CallWord node
 /// Stack: ( ast-handle addr -- length )
 pub fn ast_get_word_name(handle: i64, memory: &mut crate::Memory, addr: usize) -> Result<i64, String> {
-    AST_REGISTRY.with(|cell| {
-        let registry = cell.borrow();
-        registry.get_word_name(handle, memory, addr)
-    })
+    let text = memory.session().ast.get_word_name(handle)?;
+    store_string(memory, addr, &text)
 }
 
 /// Get instruction name from InlineInstruction
 /// Stack: ( ast-handle addr -- length )
 pub fn ast_get_inline_instruction(handle: i64, memory: &mut crate::Memory, addr: usize) -> Result<i64, String> {
-    AST_REGISTRY.with(|cell| {
-        let registry = cell.borrow();
-        registry.get_inline_instruction(handle, memory, addr)
-    })
+    let text = memory.session().ast.get_inline_instruction(handle)?;
+    store_string(memory, addr, &text)
 }
 
 /// Get string from PrintString or StackString
 /// Stack: ( ast-handle addr -- length )
 pub fn ast_get_string(handle: i64, memory: &mut crate::Memory, addr: usize) -> Result<i64, String> {
-    AST_REGISTRY.with(|cell| {
-        let registry = cell.borrow();
-        registry.get_string(handle, memory, addr)
-    })
+    let text = memory.session().ast.get_string(handle)?;
+    store_string(memory, addr, &text)
 }
 
 /// Get sequence length
 /// Stack: ( ast-handle -- length )
-pub fn ast_get_sequence_length(handle: i64) -> Result<i64, String> {
-    AST_REGISTRY.with(|cell| {
-        let registry = cell.borrow();
-        registry.get_sequence_length(handle)
-    })
+pub fn ast_get_sequence_length(registry: &AstRegistry, handle: i64) -> Result<i64, String> {
+    registry.get_sequence_length(handle)
 }
 
 /// Get sequence child
 /// Stack: ( ast-handle index -- child-handle )
-pub fn ast_get_sequence_child(handle: i64, index: i64) -> Result<i64, String> {
-    AST_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.get_sequence_child(handle, index)
-    })
+pub fn ast_get_sequence_child(registry: &mut AstRegistry, handle: i64, index: i64) -> Result<i64, String> {
+    registry.get_sequence_child(handle, index)
 }
 
 /// Get IF then branch
 /// Stack: ( ast-handle -- then-handle )
-pub fn ast_get_if_then(handle: i64) -> Result<i64, String> {
-    AST_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.get_if_then_branch(handle)
-    })
+pub fn ast_get_if_then(registry: &mut AstRegistry, handle: i64) -> Result<i64, String> {
+    registry.get_if_then_branch(handle)
 }
 
 /// Get IF else branch
 /// Stack: ( ast-handle -- else-handle-or-0 )
-pub fn ast_get_if_else(handle: i64) -> Result<i64, String> {
-    AST_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.get_if_else_branch(handle)
-    })
+pub fn ast_get_if_else(registry: &mut AstRegistry, handle: i64) -> Result<i64, String> {
+    registry.get_if_else_branch(handle)
 }
 
 /// Get loop body
 /// Stack: ( ast-handle -- body-handle )
-pub fn ast_get_loop_body(handle: i64) -> Result<i64, String> {
-    AST_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.get_loop_body(handle)
-    })
+pub fn ast_get_loop_body(registry: &mut AstRegistry, handle: i64) -> Result<i64, String> {
+    registry.get_loop_body(handle)
 }
 
 /// Get loop condition (BeginWhileRepeat only)
 /// Stack: ( ast-handle -- condition-handle )
-pub fn ast_get_loop_condition(handle: i64) -> Result<i64, String> {
-    AST_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.get_loop_condition(handle)
-    })
+pub fn ast_get_loop_condition(registry: &mut AstRegistry, handle: i64) -> Result<i64, String> {
+    registry.get_loop_condition(handle)
 }
 
 /// Get loop increment (DoLoop only)
 /// Stack: ( ast-handle -- increment )
-pub fn ast_get_loop_increment(handle: i64) -> Result<i64, String> {
-    AST_REGISTRY.with(|cell| {
-        let registry = cell.borrow();
-        registry.get_loop_increment(handle)
-    })
+pub fn ast_get_loop_increment(registry: &AstRegistry, handle: i64) -> Result<i64, String> {
+    registry.get_loop_increment(handle)
+}
+
+/// Get number of OF clauses (Case only)
+/// Stack: ( ast-handle -- count )
+pub fn ast_get_case_count(registry: &AstRegistry, handle: i64) -> Result<i64, String> {
+    registry.get_case_count(handle)
+}
+
+/// Get test code of the nth clause (Case only)
+/// Stack: ( ast-handle index -- test-handle )
+pub fn ast_get_case_test(registry: &mut AstRegistry, handle: i64, index: i64) -> Result<i64, String> {
+    registry.get_case_clause(handle, index, false)
+}
+
+/// Get body of the nth clause (Case only)
+/// Stack: ( ast-handle index -- body-handle )
+pub fn ast_get_case_body(registry: &mut AstRegistry, handle: i64, index: i64) -> Result<i64, String> {
+    registry.get_case_clause(handle, index, true)
+}
+
+/// Get default code (Case only)
+/// Stack: ( ast-handle -- default-handle )
+pub fn ast_get_case_default(registry: &mut AstRegistry, handle: i64) -> Result<i64, String> {
+    registry.get_case_default(handle)
+}
+
+/// Get number of locals (Locals only)
+/// Stack: ( ast-handle -- count )
+pub fn ast_get_locals_count(registry: &AstRegistry, handle: i64) -> Result<i64, String> {
+    registry.get_locals_count(handle, false)
+}
+
+/// Get number of locals initialised from the stack (Locals only)
+/// Stack: ( ast-handle -- args )
+pub fn ast_get_locals_args(registry: &AstRegistry, handle: i64) -> Result<i64, String> {
+    registry.get_locals_count(handle, true)
+}
+
+/// Get code in the scope of the locals (Locals only)
+/// Stack: ( ast-handle -- body-handle )
+pub fn ast_get_locals_body(registry: &mut AstRegistry, handle: i64) -> Result<i64, String> {
+    registry.get_locals_body(handle)
+}
+
+/// Get frame index of a local (LocalFetch/LocalStore only)
+/// Stack: ( ast-handle -- index )
+pub fn ast_get_local_index(registry: &AstRegistry, handle: i64) -> Result<i64, String> {
+    registry.get_local_index(handle)
 }
@@ -1,5 +1,7 @@
+use crate::native::{NativeContext, NativeFn};
 use crate::words;
-use crate::{ast::AstNode, stack::Stack};
+use crate::{ast::{AstNode, Flow}, stack::Stack, QuarterError};
+use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, HashSet};
 
 // Type alias for JIT-compiled Forth functions
@@ -19,10 +21,46 @@ macro_rules! register_primitives {
     };
 }
 
+/// Wordlist identifier of FORTH-WORDLIST, which holds the system and user words
+pub const FORTH_WORDLIST: i64 = 1;
+
+/// Wordlist identifier of the private wordlist stdlib/compiler.fth defines its words in
+pub const COMPILER_WORDLIST: i64 = 2;
+
 pub enum Word {
     Primitive(fn(&mut Stack, &crate::LoopStack, &mut crate::ReturnStack, &mut crate::Memory)),
+    Native(RefCell<NativeFn>),
     Compiled(AstNode),
     JITCompiled(JITFunction),
+    Marker(Marker),
+}
+
+/// State saved by `MARKER <name>`; running <name> restores it, forgetting <name>
+/// and everything defined after it
+#[derive(Debug, Clone)]
+pub struct Marker {
+    pub definitions: usize,  // Length of the definition history
+    pub here: i64,
+    pub search_order: Vec<i64>,
+    pub current_wordlist: i64,
+}
+
+/// What a definition replaced, so MARKER and FORGET can undo it
+struct Definition {
+    key: String,
+    replacement: bool,  // New body for an existing word (DOES>, JIT), not a new definition
+    here: Option<i64>,  // HERE before the definition, if the interpreter noted it
+    word: Option<Word>,
+    value_addr: Option<usize>,
+    deferred_addr: Option<usize>,
+    immediate: bool,
+    frozen: bool,
+    changes_order: bool,
+    defines: bool,
+    pair_constant: bool,
+    last_defined_word: Option<String>,
+    new_xt: bool,  // The definition gave `key` its execution token
+    retired: Option<String>,  // Key the definition it hid was moved to, with that one's xt
 }
 
 pub struct Dictionary {
@@ -32,6 +70,25 @@ pub struct Dictionary {
     last_defined_word: Option<String>,
     has_redefinitions: bool,
     current_file_words: HashSet<String>,  // Words defined in current file
+    compile_buffer: RefCell<Vec<String>>,  // Tokens appended by POSTPONE, LITERAL, COMPILE,
+    value_addrs: HashMap<String, usize>,  // Data cell of each VALUE (for TO and +TO)
+    deferred_addrs: HashMap<String, usize>,  // Cell holding the xt of each DEFER word
+    local_frames: RefCell<Vec<Vec<i64>>>,  // Locals of each active {: ... :} call, innermost last
+    catch_frames: RefCell<Vec<crate::CatchFrame>>,  // Each active CATCH, innermost last
+    search_order: RefCell<Vec<i64>>,  // Wordlists searched for a name, first searched first
+    current_wordlist: Cell<i64>,  // Wordlist new definitions are added to
+    last_wordlist: Cell<i64>,  // Highest wordlist identifier handed out so far
+    order_words: HashSet<String>,  // Words that change the search order when run
+    defining_words: HashSet<String>,  // Words that CREATE a word when run
+    pair_constants: HashSet<String>,  // Words made by 2CONSTANT, which callers inline
+    last_created: Option<(String, i64)>,  // Name and data-field address of the word CREATE made last
+    history: Vec<Definition>,  // Definitions since startup, oldest first
+    definition_here: Option<i64>,  // HERE before the construct being interpreted
+    xt_keys: Vec<String>,  // Key each execution token names, xt 1 first
+    xts: HashMap<String, i64>,  // Execution token of each key
+    name_addrs: Vec<Option<usize>>,  // Where each xt's name lies in the name area, None if it didn't fit
+    names_written: Cell<usize>,  // Names already laid down in memory, xt 1 first
+    native_xts: RefCell<Vec<(i64, usize)>>,  // xt table entries not yet written to memory
 }
 
 impl Default for Dictionary {
@@ -49,6 +106,25 @@ impl Dictionary {
             last_defined_word: None,
             has_redefinitions: false,
             current_file_words: HashSet::new(),
+            compile_buffer: RefCell::new(Vec::new()),
+            value_addrs: HashMap::new(),
+            deferred_addrs: HashMap::new(),
+            local_frames: RefCell::new(Vec::new()),
+            catch_frames: RefCell::new(Vec::new()),
+            search_order: RefCell::new(vec![FORTH_WORDLIST]),
+            current_wordlist: Cell::new(FORTH_WORDLIST),
+            last_wordlist: Cell::new(COMPILER_WORDLIST),
+            order_words: HashSet::new(),
+            defining_words: HashSet::new(),
+            pair_constants: HashSet::new(),
+            last_created: None,
+            history: Vec::new(),
+            definition_here: None,
+            xt_keys: Vec::new(),
+            xts: HashMap::new(),
+            name_addrs: Vec::new(),
+            names_written: Cell::new(0),
+            native_xts: RefCell::new(Vec::new()),
         };
 
         // Register all built-in primitive words using macro
@@ -63,10 +139,59 @@ impl Dictionary {
             "EMIT" => words::emit,
             "SPACE" => words::space,
             "TYPE" => words::type_word,
+            ">STRING" => words::to_string_word,
+            "STRING>" => words::string_from_word,
             "COMPARE" => words::compare,
             "-TRAILING" => words::minus_trailing,
             "SEARCH" => words::search,
             "KEY" => words::key,
+            "KEY?" => words::key_question,
+            "EKEY" => words::ekey,
+            "ACCEPT" => words::accept,
+
+            // File access
+            "OPEN-FILE" => words::open_file,
+            "CREATE-FILE" => words::create_file,
+            "CLOSE-FILE" => words::close_file,
+            "DELETE-FILE" => words::delete_file,
+            "READ-FILE" => words::read_file,
+            "READ-LINE" => words::read_line,
+            "WRITE-FILE" => words::write_file,
+            "WRITE-LINE" => words::write_line,
+            "FILE-POSITION" => words::file_position,
+            "FILE-SIZE" => words::file_size,
+            "REPOSITION-FILE" => words::reposition_file,
+
+            // Memory allocation
+            "ALLOCATE" => words::allocate,
+            "FREE" => words::free,
+            "RESIZE" => words::resize,
+
+            // Input source
+            "SOURCE" => words::source,
+            ">IN" => words::to_in,
+            "SOURCE-ID" => words::source_id,
+            "PARSE" => words::parse,
+            "PARSE-NAME" => words::parse_name,
+            "WORD" => words::word,
+            "REFILL" => words::refill,
+            "SAVE-INPUT" => words::save_input,
+            "RESTORE-INPUT" => words::restore_input,
+
+            // Conditional compilation and environment queries
+            "[IF]" => words::bracket_if,
+            "[ELSE]" => words::bracket_else,
+            "[THEN]" => words::bracket_then,
+            "ENVIRONMENT?" => words::environment_query,
+
+            // Pictured numeric output
+            "<#" => words::less_number_sign,
+            "#" => words::number_sign,
+            "#S" => words::number_sign_s,
+            "HOLD" => words::hold,
+            "HOLDS" => words::holds,
+            "SIGN" => words::sign,
+            "#>" => words::number_sign_greater,
 
             // Arithmetic operations
             "+" => words::add,
@@ -85,6 +210,43 @@ impl Dictionary {
             "2*" => words::two_star,
             "2/" => words::two_slash,
 
+            // Double-cell arithmetic
+            "UM*" => words::um_star,
+            "M*" => words::m_star,
+            "UM/MOD" => words::um_slash_mod,
+            "SM/REM" => words::sm_slash_rem,
+            "FM/MOD" => words::fm_slash_mod,
+            "D+" => words::d_plus,
+            "D-" => words::d_minus,
+            "DNEGATE" => words::dnegate,
+            "D." => words::d_dot,
+
+            // Floating point
+            "F+" => words::f_plus,
+            "F-" => words::f_minus,
+            "F*" => words::f_star,
+            "F/" => words::f_slash,
+            "FNEGATE" => words::fnegate,
+            "FABS" => words::fabs,
+            "FSQRT" => words::fsqrt,
+            "FSIN" => words::fsin,
+            "FCOS" => words::fcos,
+            "FLOOR" => words::floor,
+            "FROUND" => words::fround,
+            "FDUP" => words::fdup,
+            "FDROP" => words::fdrop,
+            "FSWAP" => words::fswap,
+            "FOVER" => words::fover,
+            "FDEPTH" => words::fdepth,
+            "F@" => words::f_fetch,
+            "F!" => words::f_store,
+            "S>F" => words::s_to_f,
+            "F>S" => words::f_to_s,
+            "F<" => words::f_less,
+            "F0<" => words::f_zero_less,
+            "F0=" => words::f_zero_equal,
+            "F." => words::f_dot,
+
             // Comparison operations
             "<" => words::less_than,
             ">" => words::greater_than,
@@ -125,6 +287,8 @@ impl Dictionary {
             "@" => words::fetch,
             "C!" => words::c_store,
             "C@" => words::c_fetch,
+            "2!" => words::two_store,
+            "2@" => words::two_fetch,
 
             // Stack pointer operations
             "SP@" => words::sp_fetch,
@@ -137,8 +301,13 @@ impl Dictionary {
             "ALLOT" => words::allot,
             "," => words::comma,
             "BASE" => words::base,
+            "STATE" => words::state,
             ">NUMBER" => words::to_number,
 
+            // Compilation state
+            "[" => words::left_bracket,
+            "]" => words::right_bracket,
+
             // Loop operations
             "I" => words::loop_i,
             "J" => words::loop_j,
@@ -163,11 +332,13 @@ impl Dictionary {
             "LLVM-BUILD-LOAD" => words::llvm_build_load_word,
             "LLVM-BUILD-STORE" => words::llvm_build_store_word,
             "LLVM-BUILD-GEP" => words::llvm_build_gep_word,
+            "LLVM-BUILD-ALLOCA" => words::llvm_build_alloca_word,
             "LLVM-BUILD-ADD" => words::llvm_build_add_word,
             "LLVM-BUILD-SUB" => words::llvm_build_sub_word,
             "LLVM-BUILD-MUL" => words::llvm_build_mul_word,
             "LLVM-BUILD-SDIV" => words::llvm_build_sdiv_word,
             "LLVM-BUILD-SREM" => words::llvm_build_srem_word,
+            "LLVM-BUILD-FLOAT-OP" => words::llvm_build_float_op_word,
             "LLVM-BUILD-AND" => words::llvm_build_and_word,
             "LLVM-BUILD-OR" => words::llvm_build_or_word,
             "LLVM-BUILD-XOR" => words::llvm_build_xor_word,
@@ -180,6 +351,8 @@ impl Dictionary {
             "LLVM-BUILD-SELECT" => words::llvm_build_select_word,
             "LLVM-BUILD-TRUNC" => words::llvm_build_trunc_word,
             "LLVM-BUILD-CALL" => words::llvm_build_call_word,
+            "LLVM-BUILD-INDIRECT-CALL" => words::llvm_build_indirect_call_word,
+            "LLVM-FUNCTION-ADDRESS" => words::llvm_function_address_word,
             "LLVM-GET-PARAM" => words::llvm_get_param_word,
             "LLVM-BUILD-PHI" => words::llvm_build_phi_word,
             "LLVM-PHI-ADD-INCOMING" => words::llvm_phi_add_incoming_word,
@@ -193,6 +366,7 @@ impl Dictionary {
             "AST-TYPE" => words::ast_get_type_word,
             "AST-GET-NUMBER" => words::ast_get_number_word,
             "AST-GET-WORD" => words::ast_get_word_word,
+            "NATIVE-XT" => words::native_xt_word,
             "AST-GET-STRING" => words::ast_get_string_word,
             "AST-SEQ-LENGTH" => words::ast_seq_length_word,
             "AST-SEQ-CHILD" => words::ast_seq_child_word,
@@ -201,6 +375,14 @@ impl Dictionary {
             "AST-LOOP-BODY" => words::ast_loop_body_word,
             "AST-LOOP-CONDITION" => words::ast_loop_condition_word,
             "AST-LOOP-INCREMENT" => words::ast_loop_increment_word,
+            "AST-CASE-COUNT" => words::ast_case_count_word,
+            "AST-CASE-TEST" => words::ast_case_test_word,
+            "AST-CASE-BODY" => words::ast_case_body_word,
+            "AST-CASE-DEFAULT" => words::ast_case_default_word,
+            "AST-LOCALS-COUNT" => words::ast_locals_count_word,
+            "AST-LOCALS-ARGS" => words::ast_locals_args_word,
+            "AST-LOCALS-BODY" => words::ast_locals_body_word,
+            "AST-LOCAL-INDEX" => words::ast_local_index_word,
 
             // Test and JIT operations
             "TEST-AST-CREATE" => words::test_ast_create_word,
@@ -214,9 +396,6 @@ impl Dictionary {
             "EVALUATE" => words::evaluate_word,
             "CMOVE" => words::cmove_word,
             "BYE" => words::bye_word,
-            "ABORT" => words::abort_word,
-            "THROW" => words::throw_word,
-            "CATCH" => words::catch_word,
         );
 
         // Add EXECUTE as a compiled word that takes xt from stack and executes it
@@ -225,6 +404,47 @@ impl Dictionary {
         // Add FIND as a compiled word that searches dictionary
         dict.add_compiled("FIND".to_string(), AstNode::Find);
 
+        // Compiler extension words append to the definition being compiled
+        dict.add_compiled("LITERAL".to_string(), AstNode::CompileLiteral);
+        dict.add_compiled("COMPILE,".to_string(), AstNode::CompileComma);
+        dict.immediate_words.insert("[".to_string());
+        dict.immediate_words.insert("LITERAL".to_string());
+        dict.immediate_words.insert("[IF]".to_string());
+        dict.immediate_words.insert("[ELSE]".to_string());
+        dict.immediate_words.insert("[THEN]".to_string());
+
+        // Exceptions: THROW unwinds to the innermost CATCH, ABORT is -1 THROW
+        dict.add_compiled("CATCH".to_string(), AstNode::Catch);
+        dict.add_compiled("THROW".to_string(), AstNode::Throw);
+        dict.add_compiled("ABORT".to_string(), AstNode::Sequence(vec![AstNode::PushNumber(-1), AstNode::Throw]));
+
+        // CREATE and the (DOES>) a DOES> compiles to are native words, so compiled code
+        // reaches them through the trampoline. They leave the words they make to the
+        // outer interpreter, which defines them once the word running them returns
+        dict.add_native("CREATE", words::create_word);
+        dict.add_native("(DOES>)", words::does_word);
+        dict.defining_words.insert("CREATE".to_string());
+
+        // Deferred words: read and set the xt a DEFER word executes
+        dict.add_compiled("DEFER@".to_string(), AstNode::DeferFetch);
+        dict.add_compiled("DEFER!".to_string(), AstNode::DeferStore);
+
+        // Search order: wordlists are numbered, FORTH-WORDLIST holds everything above
+        dict.add_compiled("FORTH-WORDLIST".to_string(), AstNode::PushNumber(FORTH_WORDLIST));
+        dict.add_compiled("COMPILER-WORDLIST".to_string(), AstNode::PushNumber(COMPILER_WORDLIST));
+        dict.add_compiled("WORDLIST".to_string(), AstNode::WordList);
+        dict.add_compiled("GET-ORDER".to_string(), AstNode::GetOrder);
+        dict.add_compiled("SET-ORDER".to_string(), AstNode::SetOrder);
+        dict.add_compiled("GET-CURRENT".to_string(), AstNode::GetCurrent);
+        dict.add_compiled("SET-CURRENT".to_string(), AstNode::SetCurrent);
+        dict.add_compiled("SEARCH-WORDLIST".to_string(), AstNode::SearchWordlist);
+
+        // Name tokens: a word's xt is also its name token
+        dict.add_compiled(">NAME".to_string(), AstNode::ToName);
+        dict.add_compiled("NAME>STRING".to_string(), AstNode::NameToString);
+
+        // Built-in words can't be forgotten
+        dict.history.clear();
         dict
     }
 
@@ -233,26 +453,455 @@ impl Dictionary {
         name: &str,
         func: fn(&mut Stack, &crate::LoopStack, &mut crate::ReturnStack, &mut crate::Memory),
     ) {
-        self.words.insert(name.to_string(), Word::Primitive(func));
+        self.define(self.definition_key(name), Word::Primitive(func), false);
+    }
+
+    /// Define `name` as a native word: a Rust closure that may keep state of its own
+    /// and fails by returning an error, which THROW unwinds with
+    pub fn add_native<F>(&mut self, name: &str, func: F)
+    where
+        F: FnMut(&mut NativeContext) -> Result<(), QuarterError> + 'static,
+    {
+        let key = self.definition_key(&name.to_uppercase());
+        let func: NativeFn = Box::new(func);
+        self.define(key.clone(), Word::Native(RefCell::new(func)), false);
+        self.value_addrs.remove(&key);
+        self.deferred_addrs.remove(&key);
+        self.last_defined_word = Some(key);
     }
 
     pub fn add_compiled(&mut self, name: String, ast: AstNode) {
-        self.last_defined_word = Some(name.clone());
-        self.words.insert(name, Word::Compiled(ast));
+        self.define_compiled(name, ast, false);
+    }
+
+    /// Give the word just created a new body (the DOES> part of a defining word's child)
+    pub fn replace_compiled(&mut self, name: String, ast: AstNode) {
+        self.define_compiled(name, ast, true);
+    }
+
+    fn define_compiled(&mut self, name: String, ast: AstNode, replacement: bool) {
+        let key = self.definition_key(&name);
+        // Calls are bound to keys, so a word changes the search order if it
+        // sets it directly or calls a word that does, by name or by a literal xt
+        // as a word DOES> changed does. Likewise it is a defining word if it calls one
+        let calls = |words: &HashSet<String>| ast.any_node(&|node| match node {
+            AstNode::CallWord(called) => words.contains(called),
+            AstNode::Sequence(nodes) => nodes.windows(2).any(|pair| matches!(pair,
+                [AstNode::PushNumber(xt), AstNode::Execute] if self.xt_key(*xt).is_some_and(|key| words.contains(key)))),
+            _ => false,
+        });
+        let changes_order = ast.any_node(&|node| matches!(node, AstNode::SetOrder)) || calls(&self.order_words);
+        let defines = calls(&self.defining_words);
+        self.define(key.clone(), Word::Compiled(ast), replacement);
+        self.value_addrs.remove(&key);
+        self.deferred_addrs.remove(&key);
+        if changes_order {
+            self.order_words.insert(key.clone());
+        } else {
+            self.order_words.remove(&key);
+        }
+        if defines {
+            self.defining_words.insert(key.clone());
+        } else {
+            self.defining_words.remove(&key);
+        }
+        self.pair_constants.remove(&key);
+        self.last_defined_word = Some(key);
+    }
+
+    /// Define `<x1> <x2> 2CONSTANT <name>`
+    pub fn add_2constant(&mut self, name: String, x1: i64, x2: i64) {
+        let key = self.definition_key(&name);
+        self.add_compiled(name, AstNode::Sequence(vec![AstNode::PushNumber(x1), AstNode::PushNumber(x2)]));
+        self.pair_constants.insert(key);
+    }
+
+    /// Whether `name` was made by 2CONSTANT, so its pair can be inlined
+    pub fn is_2constant(&self, name: &str) -> bool {
+        self.find(name).is_some_and(|key| self.pair_constants.contains(&key))
+    }
+
+    /// Define a VALUE whose data lives in the cell at `addr`
+    /// The word fetches the cell at call time so TO and +TO are seen by compiled callers
+    pub fn add_value(&mut self, name: String, addr: usize) {
+        let ast = AstNode::Sequence(vec![
+            AstNode::PushNumber(addr as i64),
+            AstNode::CallWord("@".to_string()),
+        ]);
+        let key = self.definition_key(&name);
+        self.add_compiled(name, ast);
+        self.value_addrs.insert(key, addr);
+    }
+
+    /// Get the data cell of a VALUE, if `name` is one
+    pub fn get_value_addr(&self, name: &str) -> Option<usize> {
+        self.value_addrs.get(&self.find(name)?).copied()
+    }
+
+    /// Define a DEFER word whose xt is kept in the cell at `addr`
+    /// The word fetches and executes the xt at call time, so IS rebinds compiled callers too
+    pub fn add_deferred(&mut self, name: String, addr: usize) {
+        let ast = AstNode::Sequence(vec![
+            AstNode::PushNumber(addr as i64),
+            AstNode::CallWord("@".to_string()),
+            AstNode::CallWord("EXECUTE".to_string()),
+        ]);
+        let key = self.definition_key(&name);
+        self.add_compiled(name, ast);
+        self.deferred_addrs.insert(key, addr);
+    }
+
+    /// Get the xt cell of a DEFER word, if `name` is one
+    pub fn get_deferred_addr(&self, name: &str) -> Option<usize> {
+        self.deferred_addrs.get(&self.find(name)?).copied()
     }
 
     pub fn add_jit_compiled(&mut self, name: String, func: JITFunction) {
-        self.last_defined_word = Some(name.clone());
-        self.words.insert(name, Word::JITCompiled(func));
+        let key = self.definition_key(&name);
+        // Batch compilation replaces words that are already defined
+        let replacement = self.words.contains_key(&key);
+        self.define(key.clone(), Word::JITCompiled(func), replacement);
+        self.last_defined_word = Some(key);
+    }
+
+    /// Define a word made by CREATE, which pushes the address of its data field at `addr`
+    /// until DOES> gives it more to do
+    pub fn add_created(&mut self, name: String, addr: i64) {
+        self.add_compiled(name.clone(), AstNode::PushNumber(addr));
+        self.last_created = Some((name, addr));
+    }
+
+    /// Name and data-field address of the word CREATE made last, if nothing has been
+    /// defined since, as DOES> changes it
+    pub fn last_created(&self) -> Option<(String, i64)> {
+        self.last_created.clone().filter(|(name, _)| self.last_defined_word.as_ref() == Some(&self.definition_key(name)))
+    }
+
+    /// Define `MARKER <name>`: running <name> puts the dictionary, the search order
+    /// and HERE back to how they are now
+    pub fn add_marker(&mut self, name: String, here: i64) {
+        let marker = Marker {
+            definitions: self.history.len(),
+            here,
+            search_order: self.get_order(),
+            current_wordlist: self.get_current(),
+        };
+        let key = self.definition_key(&name);
+        self.define(key.clone(), Word::Marker(marker), false);
+        self.value_addrs.remove(&key);
+        self.deferred_addrs.remove(&key);
+        self.last_defined_word = Some(key);
+    }
+
+    /// Get the state a MARKER word restores, if `name` is one
+    pub fn get_marker(&self, name: &str) -> Option<&Marker> {
+        match self.get_word(name) {
+            Some(Word::Marker(marker)) => Some(marker),
+            _ => None,
+        }
+    }
+
+    /// Undo every definition made after `marker` and restore its search order
+    pub fn restore_marker(&mut self, marker: &Marker) {
+        self.undo_definitions(marker.definitions);
+        self.set_order(marker.search_order.clone());
+        self.set_current(marker.current_wordlist);
+    }
+
+    /// FORGET <name>: undo the latest definition of `name` and everything after it
+    /// Returns HERE before that definition, if it is known
+    pub fn forget(&mut self, name: &str) -> Result<Option<i64>, QuarterError> {
+        let key = self.find(name).ok_or_else(|| QuarterError::UndefinedWord(name.to_string()))?;
+        let index = self.history.iter()
+            .rposition(|definition| definition.key == key && !definition.replacement)
+            .ok_or_else(|| QuarterError::Other(format!("FORGET cannot forget built-in word: {}", name)))?;
+        let here = self.history[index].here;
+        self.undo_definitions(index);
+        Ok(here)
+    }
+
+    /// Note HERE before the outer interpreter handles its next construct, so FORGET
+    /// can release the data space of the words defined by it
+    pub fn note_here(&mut self, here: i64) {
+        self.definition_here = Some(here);
+    }
+
+    /// Store `word` under `key`, recording what it replaces
+    fn define(&mut self, key: String, word: Word, replacement: bool) {
+        // A new definition of a name gets an xt of its own; the one it hides keeps its
+        // xt under a key no name finds, so xts taken earlier still run it
+        let retired = (!replacement && self.words.contains_key(&key)).then(|| {
+            let retired = format!("{} #{}", key, self.xts[&key]);
+            self.rename(&key, &retired);
+            retired
+        });
+        let new_xt = !self.xts.contains_key(&key);
+        if new_xt {
+            self.name_addrs.push(self.next_name_addr(&key));
+            self.xt_keys.push(key.clone());
+            self.xts.insert(key.clone(), self.xt_keys.len() as i64);
+        }
+        // A fresh xt's table entry is still empty, so only native code needs writing
+        let native = Self::native_entry(&word);
+        if native != 0 || !new_xt {
+            self.native_xts.borrow_mut().push((self.xts[&key], native));
+        }
+        let definition = Definition {
+            replacement,
+            here: self.definition_here,
+            word: self.words.insert(key.clone(), word),
+            value_addr: self.value_addrs.get(&key).copied(),
+            deferred_addr: self.deferred_addrs.get(&key).copied(),
+            immediate: self.immediate_words.contains(&key),
+            frozen: self.frozen_words.contains(&key),
+            changes_order: self.order_words.contains(&key),
+            defines: self.defining_words.contains(&key),
+            pair_constant: self.pair_constants.contains(&key),
+            last_defined_word: self.last_defined_word.clone(),
+            new_xt,
+            retired,
+            key,
+        };
+        self.history.push(definition);
     }
 
+    /// Move the word under `from` to `to`, with its xt and what is known about it
+    fn rename(&mut self, from: &str, to: &str) {
+        if let Some(word) = self.words.remove(from) {
+            self.words.insert(to.to_string(), word);
+        }
+        if let Some(xt) = self.xts.remove(from) {
+            self.xt_keys[xt as usize - 1] = to.to_string();
+            self.xts.insert(to.to_string(), xt);
+        }
+        for addrs in [&mut self.value_addrs, &mut self.deferred_addrs] {
+            if let Some(addr) = addrs.remove(from) {
+                addrs.insert(to.to_string(), addr);
+            }
+        }
+        for set in [
+            &mut self.immediate_words,
+            &mut self.frozen_words,
+            &mut self.order_words,
+            &mut self.defining_words,
+            &mut self.pair_constants,
+        ] {
+            if set.remove(from) {
+                set.insert(to.to_string());
+            }
+        }
+    }
+
+    /// Undo definitions, newest first, until only the first `len` remain
+    fn undo_definitions(&mut self, len: usize) {
+        while self.history.len() > len {
+            let Some(definition) = self.history.pop() else {
+                break;
+            };
+            let key = definition.key;
+            let xt = self.xts[&key];
+            self.native_xts.borrow_mut().push((xt, definition.word.as_ref().map_or(0, Self::native_entry)));
+            if definition.new_xt {
+                self.xt_keys.pop();
+                self.xts.remove(&key);
+                self.name_addrs.pop();
+                self.names_written.set(self.names_written.get().min(self.name_addrs.len()));
+            }
+            match definition.word {
+                Some(word) => self.words.insert(key.clone(), word),
+                None => self.words.remove(&key),
+            };
+            match definition.value_addr {
+                Some(addr) => self.value_addrs.insert(key.clone(), addr),
+                None => self.value_addrs.remove(&key),
+            };
+            match definition.deferred_addr {
+                Some(addr) => self.deferred_addrs.insert(key.clone(), addr),
+                None => self.deferred_addrs.remove(&key),
+            };
+            for (set, member) in [
+                (&mut self.immediate_words, definition.immediate),
+                (&mut self.frozen_words, definition.frozen),
+                (&mut self.order_words, definition.changes_order),
+                (&mut self.defining_words, definition.defines),
+                (&mut self.pair_constants, definition.pair_constant),
+            ] {
+                if member {
+                    set.insert(key.clone());
+                } else {
+                    set.remove(&key);
+                }
+            }
+            self.last_defined_word = definition.last_defined_word;
+            if let Some(retired) = definition.retired {
+                self.rename(&retired, &key);
+            }
+        }
+    }
+
+    /// Execution token of the word stored under `key`
+    pub fn xt(&self, key: &str) -> Option<i64> {
+        self.xts.get(key).copied()
+    }
+
+    /// Key of the word execution token `xt` names, if it names one
+    pub fn xt_key(&self, xt: i64) -> Option<&str> {
+        let index = usize::try_from(xt).ok()?.checked_sub(1)?;
+        self.xt_keys.get(index).map(String::as_str)
+    }
+
+    /// Address in the name area for the name of a new xt for `key`, after the name of the last xt
+    fn next_name_addr(&self, key: &str) -> Option<usize> {
+        let addr = match self.name_addrs.last() {
+            None => crate::NAME_AREA_ADDR,
+            Some(last) => (*last)? + Self::key_name(&self.xt_keys[self.name_addrs.len() - 1]).len(),
+        };
+        (addr + Self::key_name(key).len() <= crate::NAME_AREA_ADDR + crate::NAME_AREA_SIZE).then_some(addr)
+    }
+
+    /// Name of the word stored under `key`: words outside FORTH-WORDLIST are keyed
+    /// "NAME wid" and retired definitions "NAME #xt"
+    fn key_name(key: &str) -> &str {
+        key.split(' ').next().unwrap_or_default()
+    }
+
+    /// NAME>STRING: address and length of the name of the word `nt` names. Each name is
+    /// laid down in the name area once, the first time names are looked up after its xt is created
+    pub fn name_string(&self, nt: i64, memory: &mut crate::Memory) -> Result<(i64, i64), QuarterError> {
+        let name = Self::key_name(self.xt_key(nt).ok_or(QuarterError::InvalidXt(nt))?);
+        for xt in self.names_written.get()..self.name_addrs.len() {
+            if let Some(addr) = self.name_addrs[xt] {
+                memory.store_bytes(addr, Self::key_name(&self.xt_keys[xt]).as_bytes())?;
+            }
+        }
+        self.names_written.set(self.name_addrs.len());
+        let addr = self.name_addrs[nt as usize - 1].ok_or(QuarterError::DictionaryOverflow)?;
+        Ok((addr as i64, name.len() as i64))
+    }
+
+    /// Execution token of the word stored under `key` if it is a native word,
+    /// which compiled code can only reach through quarter_call_native
+    pub fn native_xt(&self, key: &str) -> Option<i64> {
+        match self.words.get(key) {
+            Some(Word::Native(_)) => self.xt(key),
+            _ => None,
+        }
+    }
+
+    /// Address compiled code calls to run `word` directly, or 0 if it has none
+    fn native_entry(word: &Word) -> usize {
+        match word {
+            Word::JITCompiled(func) => *func as usize,
+            _ => 0,
+        }
+    }
+
+    /// Bring the xt table compiled EXECUTE calls through up to date with the dictionary
+    fn sync_xt_table(&self, memory: &mut crate::Memory) -> Result<(), QuarterError> {
+        for (xt, native) in self.native_xts.borrow_mut().drain(..) {
+            if xt < crate::XT_TABLE_CELLS {
+                memory.store(crate::XT_TABLE_ADDR + xt as usize * 8, native as i64)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Key a word is stored under: FORTH-WORDLIST words keep their plain name,
+    /// other wordlists append the identifier after a space, which no token contains
+    fn wordlist_key(wid: i64, name: &str) -> String {
+        if wid == FORTH_WORDLIST {
+            name.to_string()
+        } else {
+            format!("{} {}", name, wid)
+        }
+    }
+
+    /// Key under which `name` is found in wordlist `wid`, if it is defined there
+    pub fn search_wordlist(&self, name: &str, wid: i64) -> Option<String> {
+        let key = Self::wordlist_key(wid, name);
+        self.words.contains_key(&key).then_some(key)
+    }
+
+    /// Key of the word `name` refers to under the current search order
+    /// FORTH-WORDLIST is searched last even if the order leaves it out, so the
+    /// system words stay reachable. A key handed out earlier (e.g. in an xt) is accepted as is
+    pub fn find(&self, name: &str) -> Option<String> {
+        if name.contains(' ') {
+            return self.words.contains_key(name).then(|| name.to_string());
+        }
+        self.search_order.borrow().iter()
+            .chain(std::iter::once(&FORTH_WORDLIST))
+            .find_map(|&wid| self.search_wordlist(name, wid))
+    }
+
+    /// Key a new definition of `name` gets in the compilation wordlist
+    pub fn definition_key(&self, name: &str) -> String {
+        if name.contains(' ') {
+            name.to_string()
+        } else {
+            Self::wordlist_key(self.current_wordlist.get(), name)
+        }
+    }
+
+    /// Bind a name to the key of the word it refers to now, so the code keeps calling
+    /// that word whatever the search order is when it runs. A definition refers to
+    /// itself (`current_word`); names that are not defined are left as they are
+    pub fn bind(&self, name: &str, current_word: Option<&str>) -> String {
+        match current_word {
+            Some(word) if word.to_uppercase() == name => self.definition_key(name),
+            _ => self.find(name).unwrap_or_else(|| name.to_string()),
+        }
+    }
+
+    /// Key of a word stdlib/compiler.fth defines, for calling it from Rust
+    pub fn compiler_word(name: &str) -> String {
+        Self::wordlist_key(COMPILER_WORDLIST, name)
+    }
+
+    /// Create a new, empty wordlist and return its identifier
+    pub fn new_wordlist(&self) -> i64 {
+        let wid = self.last_wordlist.get() + 1;
+        self.last_wordlist.set(wid);
+        wid
+    }
+
+    /// Get the search order, first searched first
+    pub fn get_order(&self) -> Vec<i64> {
+        self.search_order.borrow().clone()
+    }
+
+    /// Replace the search order, first searched first
+    pub fn set_order(&self, order: Vec<i64>) {
+        *self.search_order.borrow_mut() = order;
+    }
+
+    /// Get the wordlist new definitions are added to
+    pub fn get_current(&self) -> i64 {
+        self.current_wordlist.get()
+    }
+
+    /// Add new definitions to wordlist `wid`
+    pub fn set_current(&self, wid: i64) {
+        self.current_wordlist.set(wid);
+    }
+
+    /// Check whether running `name` may change the search order, in which case
+    /// the outer interpreter must look up the names after it afresh
+    pub fn changes_search_order(&self, name: &str) -> bool {
+        self.find(name).is_some_and(|key| self.order_words.contains(&key))
+    }
+
+    /// Check whether running `name` may CREATE a word, whose name it parses from the input
+    pub fn is_defining_word(&self, name: &str) -> bool {
+        self.find(name).is_some_and(|key| self.defining_words.contains(&key))
+    }
 
     pub fn has_word(&self, word: &str) -> bool {
-        self.words.contains_key(word)
+        self.find(word).is_some()
     }
 
     pub fn get_word(&self, word: &str) -> Option<&Word> {
-        self.words.get(word)
+        self.words.get(&self.find(word)?)
     }
 
     /// Get all words with their names (for batch compilation)
@@ -262,12 +911,12 @@ impl Dictionary {
 
     /// Freeze a word to prevent re-definition (used after JIT compilation)
     pub fn freeze_word(&mut self, name: &str) {
-        self.frozen_words.insert(name.to_uppercase());
+        self.frozen_words.insert(self.definition_key(&name.to_uppercase()));
     }
 
     /// Check if a word is frozen (cannot be re-defined)
     pub fn is_frozen(&self, name: &str) -> bool {
-        self.frozen_words.contains(&name.to_uppercase())
+        self.frozen_words.contains(&self.definition_key(&name.to_uppercase()))
     }
 
     /// Start tracking words for a new file (call at start of file load in define_only mode)
@@ -310,7 +959,60 @@ impl Dictionary {
 
     /// Check if a word is immediate (executes during compilation)
     pub fn is_immediate(&self, name: &str) -> bool {
-        self.immediate_words.contains(&name.to_uppercase())
+        self.find(&name.to_uppercase())
+            .is_some_and(|key| self.immediate_words.contains(&key))
+    }
+
+    /// Append a token to the definition currently being compiled
+    pub fn compile_token(&self, token: String) {
+        self.compile_buffer.borrow_mut().push(token);
+    }
+
+    /// Take the tokens appended since the last call
+    pub fn take_compiled_tokens(&self) -> Vec<String> {
+        std::mem::take(&mut *self.compile_buffer.borrow_mut())
+    }
+
+    /// Enter a word with locals
+    pub fn push_local_frame(&self, frame: Vec<i64>) {
+        self.local_frames.borrow_mut().push(frame);
+    }
+
+    /// Leave the innermost word with locals
+    pub fn pop_local_frame(&self) {
+        self.local_frames.borrow_mut().pop();
+    }
+
+    /// Read the nth local of the innermost frame
+    pub fn get_local(&self, index: usize) -> Result<i64, QuarterError> {
+        self.local_frames.borrow().last()
+            .and_then(|frame| frame.get(index).copied())
+            .ok_or_else(|| QuarterError::Other(format!("Invalid local: {}", index)))
+    }
+
+    /// Write the nth local of the innermost frame
+    pub fn set_local(&self, index: usize, value: i64) -> Result<(), QuarterError> {
+        let mut frames = self.local_frames.borrow_mut();
+        let slot = frames.last_mut()
+            .and_then(|frame| frame.get_mut(index))
+            .ok_or_else(|| QuarterError::Other(format!("Invalid local: {}", index)))?;
+        *slot = value;
+        Ok(())
+    }
+
+    /// Enter a CATCH
+    pub fn push_catch_frame(&self, frame: crate::CatchFrame) {
+        self.catch_frames.borrow_mut().push(frame);
+    }
+
+    /// Leave the innermost CATCH
+    pub fn pop_catch_frame(&self) -> Option<crate::CatchFrame> {
+        self.catch_frames.borrow_mut().pop()
+    }
+
+    /// Check whether a THROW would be caught
+    pub fn in_catch(&self) -> bool {
+        !self.catch_frames.borrow().is_empty()
     }
 
     /// Get the last defined word name
@@ -352,6 +1054,27 @@ impl Dictionary {
         }
     }
 
+    /// Turn a throw code raised by a primitive or native code into the error THROW unwinds with
+    fn pending_throw(
+        &self,
+        stack: &mut Stack,
+        loop_stack: &mut crate::LoopStack,
+        return_stack: &mut crate::ReturnStack,
+        memory: &mut crate::Memory,
+    ) -> Result<(), QuarterError> {
+        match memory.take_throw() {
+            Some(code) => {
+                if code == -1 || code == -2 {
+                    crate::ast::clear_stacks_unless_caught(stack, self, loop_stack, return_stack, memory);
+                }
+                Err(QuarterError::from_code(code))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Execute the word stored under `word`, a key as returned by `find` or `bind`
+    /// (FORTH-WORDLIST words are keyed by their plain name)
     pub fn execute_word(
         &self,
         word: &str,
@@ -359,46 +1082,45 @@ impl Dictionary {
         loop_stack: &mut crate::LoopStack,
         return_stack: &mut crate::ReturnStack,
         memory: &mut crate::Memory,
-    ) -> Result<(), String> {
+    ) -> Result<(), QuarterError> {
         if let Some(w) = self.words.get(word) {
             match w {
                 Word::Primitive(func) => {
+                    // Primitives can't return errors, so they raise throw codes in memory
+                    memory.take_throw();
                     func(stack, loop_stack, return_stack, memory);
-                    Ok(())
+                    self.pending_throw(stack, loop_stack, return_stack, memory)
+                }
+                Word::Native(func) => {
+                    // A native word that calls back into itself can't borrow its closure twice
+                    let mut func = func
+                        .try_borrow_mut()
+                        .map_err(|_| QuarterError::Other(format!("{} can't call itself", word)))?;
+                    memory.take_throw();
+                    func(&mut NativeContext { stack, loop_stack, return_stack, memory })?;
+                    // Words popped without checking leave an underflow pending
+                    self.pending_throw(stack, loop_stack, return_stack, memory)
                 }
                 Word::Compiled(ast) => {
                     // Check if this is a tail-recursive function
                     if Self::is_tail_recursive_call(ast, word) {
                         // Tail call optimization: execute in a loop instead of recursion
                         loop {
-                            match ast.execute_with_tco_check(stack, self, loop_stack, return_stack, memory, word) {
-                                Ok(true) => {
-                                    // Tail call detected, continue loop
-                                    continue;
-                                }
-                                Ok(false) => {
-                                    // Normal completion
-                                    return Ok(());
-                                }
-                                Err(msg) if msg == "EXIT" => {
-                                    return Ok(());
-                                }
-                                Err(e) => {
-                                    return Err(e);
-                                }
+                            let flow = ast.execute_with_tco_check(stack, self, loop_stack, return_stack, memory, word)?;
+                            if flow != Flow::TailCall {
+                                return crate::ast::word_finished(flow);
                             }
                         }
                     } else {
                         // No tail recursion, execute normally
-                        match ast.execute(stack, self, loop_stack, return_stack, memory) {
-                            Err(msg) if msg == "EXIT" => Ok(()),
-                            result => result,
-                        }
+                        ast.execute(stack, self, loop_stack, return_stack, memory)
                     }
                 }
                 Word::JITCompiled(jit_fn) => {
                     // Execute JIT-compiled native code
                     // Pass memory buffer and mutable references to sp/rp
+                    memory.take_throw();
+                    self.sync_xt_table(memory)?;
                     let memory_ptr = memory.as_mut_ptr();
                     let sp_ptr = stack.sp_mut_ptr();
                     let rp_ptr = return_stack.rp_mut_ptr();
@@ -406,11 +1128,16 @@ impl Dictionary {
                     unsafe {
                         jit_fn(memory_ptr, sp_ptr, rp_ptr);
                     }
-                    Ok(())
+                    // A THROW in native code returns through every compiled frame
+                    self.pending_throw(stack, loop_stack, return_stack, memory)
+                }
+                Word::Marker(_) => {
+                    // Forgetting words needs the whole dictionary, which only the outer interpreter holds
+                    Err(QuarterError::Compile(format!("{} is a marker and must be run from the interpreter", word)))
                 }
             }
         } else {
-            Err(format!("Unknown word: {}", word))
+            Err(QuarterError::UndefinedWord(word.to_string()))
         }
     }
 }
@@ -0,0 +1,136 @@
+// Errors raised by the interpreter and compiler
+// Each kind of error carries the ANS throw code CATCH reports for it
+
+use std::fmt;
+
+use crate::source::Span;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum QuarterError {
+    /// The data stack held too few items; names the word that needed them (empty if unknown)
+    StackUnderflow(String),
+    /// The float stack held too few items for the named word
+    FloatStackUnderflow(String),
+    FloatStackOverflow,
+    /// A name that is not in the dictionary
+    UndefinedWord(String),
+    /// An execution token that names no word
+    InvalidXt(i64),
+    /// An access outside Forth memory
+    MemoryBounds(usize),
+    /// ALLOT ran into the heap
+    DictionaryOverflow,
+    DivisionByZero,
+    /// ABORT, or THROW -1
+    Abort,
+    /// ABORT" with its message
+    AbortQuote(String),
+    /// THROW with a code the interpreter has no error of its own for
+    Throw(i64),
+    /// A file could not be read or written
+    Io(String),
+    /// Source that cannot be compiled: a bad control structure, a missing name, ...
+    Compile(String),
+    /// Anything else
+    Other(String),
+    /// An error raised at a token of a file, with the line it is on
+    Located { span: Span, line: String, width: usize, error: Box<QuarterError> },
+    /// An error raised in a file INCLUDEd at `span`
+    Included { span: Span, error: Box<QuarterError> },
+}
+
+impl QuarterError {
+    /// Stack underflow in the word `word`
+    pub fn underflow(word: &str) -> Self {
+        QuarterError::StackUnderflow(word.to_string())
+    }
+
+    /// The error a non-zero THROW code unwinds with. Codes the interpreter raises
+    /// itself read as its own errors; the rest keep the code for CATCH to recover
+    pub fn from_code(code: i64) -> Self {
+        match code {
+            -1 => QuarterError::Abort,
+            -4 => QuarterError::StackUnderflow(String::new()),
+            -10 => QuarterError::DivisionByZero,
+            _ => QuarterError::Throw(code),
+        }
+    }
+
+    /// The code CATCH returns for this error
+    pub fn code(&self) -> i64 {
+        match self {
+            QuarterError::StackUnderflow(_) => -4,
+            QuarterError::FloatStackUnderflow(_) => -45,
+            QuarterError::FloatStackOverflow => -44,
+            QuarterError::UndefinedWord(_) | QuarterError::InvalidXt(_) => -13,
+            QuarterError::MemoryBounds(_) => -9,
+            QuarterError::DictionaryOverflow => -8,
+            QuarterError::DivisionByZero => -10,
+            QuarterError::Abort => -1,
+            QuarterError::AbortQuote(_) => -2,
+            QuarterError::Throw(code) => *code,
+            QuarterError::Io(_) => -37,
+            QuarterError::Compile(_) | QuarterError::Other(_) => -1,
+            QuarterError::Located { error, .. } | QuarterError::Included { error, .. } => error.code(),
+        }
+    }
+
+    /// The error itself, without the places in the source it was reported at
+    pub fn kind(&self) -> &QuarterError {
+        match self {
+            QuarterError::Located { error, .. } | QuarterError::Included { error, .. } => error.kind(),
+            error => error,
+        }
+    }
+}
+
+impl fmt::Display for QuarterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QuarterError::StackUnderflow(word) if word.is_empty() => write!(f, "Stack underflow"),
+            QuarterError::StackUnderflow(word) => write!(f, "Stack underflow in {}", word),
+            QuarterError::FloatStackUnderflow(word) => write!(f, "Floating-point stack underflow in {}", word),
+            QuarterError::FloatStackOverflow => write!(f, "Floating-point stack overflow"),
+            QuarterError::UndefinedWord(name) => write!(f, "undefined word {}", name),
+            QuarterError::InvalidXt(xt) => write!(f, "Invalid execution token: {}", xt),
+            QuarterError::MemoryBounds(addr) => write!(f, "Memory access out of bounds: address {}", addr),
+            QuarterError::DictionaryOverflow => write!(f, "Dictionary overflow"),
+            QuarterError::DivisionByZero => write!(f, "Division by zero"),
+            QuarterError::Abort => write!(f, "ABORT"),
+            QuarterError::AbortQuote(message) => write!(f, "ABORT\" {}", message),
+            QuarterError::Throw(code) => write!(f, "THROW {}", code),
+            QuarterError::Io(message) | QuarterError::Compile(message) | QuarterError::Other(message) => {
+                write!(f, "{}", message)
+            }
+            QuarterError::Located { span, line, width, error } => {
+                // Keep tabs so the caret lines up however the line is indented
+                let indent: String = line.chars().take(span.column - 1)
+                    .map(|ch| if ch == '\t' { '\t' } else { ' ' })
+                    .collect();
+                write!(f, "{}: {}\n{}\n{}{}", span, error, line, indent, "^".repeat(*width))
+            }
+            QuarterError::Included { span, error } => write!(f, "{}\nincluded from {}", error, span),
+        }
+    }
+}
+
+impl std::error::Error for QuarterError {}
+
+// Code outside the interpreter core (primitives, the LLVM bindings) still reports errors as text
+impl From<String> for QuarterError {
+    fn from(message: String) -> Self {
+        QuarterError::Other(message)
+    }
+}
+
+impl From<&str> for QuarterError {
+    fn from(message: &str) -> Self {
+        QuarterError::Other(message.to_string())
+    }
+}
+
+impl From<QuarterError> for String {
+    fn from(error: QuarterError) -> Self {
+        error.to_string()
+    }
+}
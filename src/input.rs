@@ -0,0 +1,100 @@
+// Where Forth input comes from
+// KEY, EKEY, KEY?, ACCEPT, READLINE and REFILL past the end of user input read
+// through their interpreter's session, which takes text from its Input a line at a time
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+/// A function input is asked for, returning the next line (None at the end of the input)
+pub type InputFn = Box<dyn FnMut() -> Option<String>>;
+
+/// Source of an interpreter's input
+#[derive(Default)]
+pub enum Input {
+    /// The process's standard input; READLINE edits lines typed at a terminal
+    #[default]
+    Stdin,
+    /// Text given up front, read until it runs out
+    Text(VecDeque<u8>),
+    /// Any buffered reader: a file, a socket, a pipe
+    Reader(Box<dyn BufRead>),
+    /// A function called for each line of input
+    Callback(InputFn),
+}
+
+impl Input {
+    /// Input read from `text`
+    pub fn text(text: impl Into<String>) -> Self {
+        Input::Text(text.into().into_bytes().into())
+    }
+
+    /// Input read from a file
+    pub fn file(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Input::Reader(Box::new(BufReader::new(File::open(path)?))))
+    }
+
+    /// Input asked of `func` a line at a time
+    pub fn callback(func: impl FnMut() -> Option<String> + 'static) -> Self {
+        Input::Callback(Box::new(func))
+    }
+
+    /// The next line, ending with its newline if it has one; None at the end of the input.
+    /// Forth's input words have no way to report a failed read, so an error ends the input
+    pub fn read_line(&mut self) -> Option<Vec<u8>> {
+        let mut line = Vec::new();
+        match self {
+            Input::Stdin => {
+                io::stdin().lock().read_until(b'\n', &mut line).ok()?;
+            }
+            Input::Text(text) => {
+                let end = text.iter().position(|&c| c == b'\n').map_or(text.len(), |newline| newline + 1);
+                line.extend(text.drain(..end));
+            }
+            Input::Reader(reader) => {
+                reader.read_until(b'\n', &mut line).ok()?;
+            }
+            Input::Callback(func) => {
+                line = func()?.into_bytes();
+                line.push(b'\n');
+            }
+        }
+        (!line.is_empty()).then_some(line)
+    }
+
+    /// Whether reading would return input at once. A reader or callback may wait for its
+    /// next line, so it is never ready: only what has been read from it already counts
+    pub fn ready(&self) -> bool {
+        match self {
+            Input::Stdin => stdin_ready(),
+            Input::Text(text) => !text.is_empty(),
+            Input::Reader(_) | Input::Callback(_) => false,
+        }
+    }
+}
+
+/// Whether reading standard input would return at once, with input or at its end
+#[cfg(unix)]
+pub(crate) fn stdin_ready() -> bool {
+    #[repr(C)]
+    struct PollFd {
+        fd: i32,
+        events: i16,
+        revents: i16,
+    }
+    unsafe extern "C" {
+        fn poll(fds: *mut PollFd, nfds: std::ffi::c_ulong, timeout: i32) -> i32;
+    }
+    const POLLIN: i16 = 1;
+
+    let mut stdin = PollFd { fd: 0, events: POLLIN, revents: 0 };
+    // SAFETY: one pollfd, and a zero timeout returns without waiting
+    unsafe { poll(&mut stdin, 1, 0) > 0 }
+}
+
+/// Whether reading standard input would return at once; always assumed without poll()
+#[cfg(not(unix))]
+pub(crate) fn stdin_ready() -> bool {
+    true
+}
@@ -0,0 +1,198 @@
+// A complete Forth system behind one value, for programs that embed Quarter
+// The interpreter owns the stacks, dictionary and memory the free functions in lib.rs
+// take piecemeal, and loads the standard library when it is created
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::native::{NativeContext, StackValue};
+use crate::input::Input;
+use crate::output::Output;
+use crate::{
+    execute_line, execute_word, load_file, load_stdlib, CompilerConfig, Dictionary, ExecutionOptions, LoopStack, Memory,
+    QuarterError, ReturnStack, RuntimeContext, Stack,
+};
+
+/// A Forth system embedded in a Rust program
+///
+/// ```no_run
+/// let mut forth = quarter::Interpreter::new();
+/// forth.eval(": SQUARE DUP * ;").unwrap();
+/// forth.push(7i64).unwrap();
+/// forth.call("SQUARE").unwrap();
+/// assert_eq!(forth.pop::<i64>().unwrap(), 49);
+/// ```
+pub struct Interpreter {
+    stack: Stack,
+    dict: Dictionary,
+    loop_stack: LoopStack,
+    return_stack: ReturnStack,
+    memory: Memory,
+    included_files: HashSet<String>,
+    config: CompilerConfig,
+    options: ExecutionOptions,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Interpreter {
+    /// An interpreter with the standard library loaded, running words without JIT compilation
+    pub fn new() -> Self {
+        let mut interpreter = Interpreter {
+            stack: Stack::new(),
+            dict: Dictionary::new(),
+            loop_stack: LoopStack::new(),
+            return_stack: ReturnStack::new(),
+            memory: Memory::new(),
+            included_files: HashSet::new(),
+            config: CompilerConfig::new(true, false, false),
+            options: ExecutionOptions::new(false, false),
+        };
+        // The standard library is part of the binary, so it always loads
+        interpreter
+            .run(load_stdlib)
+            .expect("the standard library failed to load");
+        interpreter
+    }
+
+    /// Interpret a string of Forth source
+    pub fn eval(&mut self, code: &str) -> Result<(), QuarterError> {
+        self.run(|ctx, config, options, included_files| execute_line(code, ctx, config, options, included_files))
+    }
+
+    /// Interpret a Forth source file; a file already loaded is not loaded again
+    pub fn load(&mut self, path: impl AsRef<Path>) -> Result<(), QuarterError> {
+        let path = path.as_ref().to_string_lossy().into_owned();
+        self.run(|ctx, config, options, included_files| load_file(&path, ctx, config, options, included_files))
+    }
+
+    /// Execute the word `name` with whatever is on the stacks
+    pub fn call(&mut self, name: &str) -> Result<(), QuarterError> {
+        let key = self
+            .dict
+            .find(&name.to_uppercase())
+            .ok_or_else(|| QuarterError::UndefinedWord(name.to_string()))?;
+        self.run(|ctx, config, _options, included_files| execute_word(&key, ctx, config, included_files))
+    }
+
+    /// Define the word `name` in Rust. The closure gets a `NativeContext` for the stacks:
+    ///
+    /// ```no_run
+    /// let mut forth = quarter::Interpreter::new();
+    /// forth.define_native("CUBE", |ctx| {
+    ///     let n: i64 = ctx.pop()?;
+    ///     ctx.push(n * n * n)
+    /// });
+    /// ```
+    pub fn define_native<F>(&mut self, name: &str, func: F)
+    where
+        F: FnMut(&mut NativeContext) -> Result<(), QuarterError> + 'static,
+    {
+        self.dict.add_native(name, func);
+    }
+
+    /// Push a value: integers, flags and characters onto the data stack, floats onto the float stack
+    pub fn push<T: StackValue>(&mut self, value: T) -> Result<(), QuarterError> {
+        value.push_onto(&mut self.stack, &mut self.memory)
+    }
+
+    /// Pop a value of type `T` off the stack it lives on
+    pub fn pop<T: StackValue>(&mut self) -> Result<T, QuarterError> {
+        T::pop_from(&mut self.stack, &mut self.memory)
+    }
+
+    /// Pop a string left as ( c-addr u ), as S" and PARSE-NAME leave one
+    pub fn pop_string(&mut self) -> Result<String, QuarterError> {
+        self.native_context().pop_string()
+    }
+
+    /// Number of cells on the data stack
+    pub fn depth(&self) -> usize {
+        self.stack.depth()
+    }
+
+    /// The data stack, bottom first
+    pub fn stack(&self) -> Vec<i64> {
+        (0..self.stack.depth())
+            .map(|i| self.memory.fetch(i * 8).unwrap_or(0))
+            .collect()
+    }
+
+    /// Read input from `input` from now on, returning where it came from before
+    ///
+    /// ```no_run
+    /// use quarter::{Input, Interpreter};
+    ///
+    /// let mut forth = Interpreter::new();
+    /// forth.set_input(Input::text("yes\n"));
+    /// forth.eval("CREATE ANSWER 80 ALLOT  ANSWER 80 ACCEPT ANSWER SWAP").unwrap();
+    /// assert_eq!(forth.pop_string().unwrap(), "yes");
+    /// ```
+    pub fn set_input(&mut self, input: Input) -> Input {
+        self.memory.session().set_input(input)
+    }
+
+    /// Send output to `output` from now on, returning where it went before
+    ///
+    /// ```no_run
+    /// use quarter::{Interpreter, Output};
+    ///
+    /// let mut forth = Interpreter::new();
+    /// forth.set_output(Output::buffer());
+    /// forth.eval("6 7 * .").unwrap();
+    /// assert_eq!(forth.take_output(), "42 ");
+    /// ```
+    pub fn set_output(&mut self, output: Output) -> Output {
+        std::mem::replace(&mut self.memory.session().output, output)
+    }
+
+    /// The text in the output buffer, leaving it empty; nothing unless the output is an `Output::Buffer`
+    pub fn take_output(&mut self) -> String {
+        match &mut self.memory.session().output {
+            Output::Buffer(buffer) => String::from_utf8_lossy(&std::mem::take(buffer)).into_owned(),
+            _ => String::new(),
+        }
+    }
+
+    pub fn dictionary(&self) -> &Dictionary {
+        &self.dict
+    }
+
+    pub fn memory(&mut self) -> &mut Memory {
+        &mut self.memory
+    }
+
+    fn native_context(&mut self) -> NativeContext<'_> {
+        NativeContext {
+            stack: &mut self.stack,
+            loop_stack: &mut self.loop_stack,
+            return_stack: &mut self.return_stack,
+            memory: &mut self.memory,
+        }
+    }
+
+    /// Run `f` on this interpreter's state. A >STRING the error skipped the STRING> of
+    /// stops capturing, so later output isn't lost
+    fn run(
+        &mut self,
+        f: impl FnOnce(&mut RuntimeContext, CompilerConfig, ExecutionOptions, &mut HashSet<String>) -> Result<(), QuarterError>,
+    ) -> Result<(), QuarterError> {
+        let capture_depth = self.memory.session().capture_depth();
+        let mut ctx = RuntimeContext::new(
+            &mut self.stack,
+            &mut self.dict,
+            &mut self.loop_stack,
+            &mut self.return_stack,
+            &mut self.memory,
+        );
+        let result = f(&mut ctx, self.config, self.options, &mut self.included_files);
+        if result.is_err() {
+            self.memory.session().truncate_captures(capture_depth);
+        }
+        result
+    }
+}
@@ -1,21 +1,30 @@
 pub mod ast;
 pub mod ast_forth;
 pub mod dictionary;
+pub mod error;
+pub mod input;
+pub mod interpreter;
 pub mod llvm_forth;
+pub mod native;
+pub mod output;
+pub mod session;
+pub mod source;
 pub mod stack;
 pub mod words;
 
 pub use ast::AstNode;
 pub use dictionary::Dictionary;
+pub use error::QuarterError;
+pub use input::Input;
+pub use interpreter::Interpreter;
+pub use native::{NativeContext, NativeFn, StackValue};
+pub use output::Output;
+pub use session::Session;
+use session::{Created, Reentered};
+pub use source::{Source, Span};
 pub use stack::Stack;
 
 use std::fs;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::cell::RefCell;
-use std::collections::HashSet;
-
-/// Track whether the Forth compiler has been loaded
-static FORTH_COMPILER_LOADED: AtomicBool = AtomicBool::new(false);
 
 // ============================================================================
 // Configuration and Options Structs
@@ -48,6 +57,14 @@ impl ExecutionOptions {
     }
 }
 
+/// How the program is being run, as `S" QUARTER-MODE" ENVIRONMENT?` reports it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExecutionMode {
+    Interpreted = 0,
+    Jit = 1,
+    Aot = 2,
+}
+
 /// Runtime context grouping all mutable state
 /// Used to reduce parameter counts in functions
 pub struct RuntimeContext<'a> {
@@ -71,148 +88,23 @@ impl<'a> RuntimeContext<'a> {
 }
 
 // ============================================================================
-// Global Execution Context (for EVALUATE and self-hosting REPL)
+// Exception Handling
 // ============================================================================
 
-/// Catch frame for exception handling
+/// Catch frame for exception handling: the stack pointers a THROW unwinds to
+/// Frames are kept on the dictionary, which every executing AST node can reach
 #[derive(Clone, Debug)]
 pub struct CatchFrame {
-    pub stack_depth: usize,
-    pub return_stack_depth: usize,
-}
-
-/// Global execution context accessible from primitive words
-pub struct ExecutionContext {
-    pub stack: Stack,
-    pub dict: Dictionary,
-    pub loop_stack: LoopStack,
-    pub return_stack: ReturnStack,
-    pub memory: Memory,
-    pub included_files: HashSet<String>,
-    pub config: CompilerConfig,
-    // Raw pointers for re-entrant access (used by EVALUATE)
-    pub dict_ptr: *mut Dictionary,
-    pub loop_stack_ptr: *mut LoopStack,
-    pub return_stack_ptr: *mut ReturnStack,
-    pub memory_ptr: *mut Memory,
-    // Exception handling
-    pub catch_stack: Vec<CatchFrame>,
-}
-
-thread_local! {
-    /// Thread-local execution context for EVALUATE and REPL
-    static EXECUTION_CONTEXT: RefCell<Option<ExecutionContext>> = const { RefCell::new(None) };
-
-    /// Raw pointers for re-entrant access (stored separately to avoid RefCell conflicts)
-    static REENTRANT_POINTERS: std::cell::Cell<(*mut Dictionary, *mut LoopStack, *mut ReturnStack, *mut Memory, *mut HashSet<String>)> =
-        const { std::cell::Cell::new((std::ptr::null_mut(), std::ptr::null_mut(), std::ptr::null_mut(), std::ptr::null_mut(), std::ptr::null_mut())) };
-
-    /// Config flags for re-entrant access
-    static REENTRANT_CONFIG: std::cell::Cell<(bool, bool, bool)> = const { std::cell::Cell::new((false, false, false)) };
-}
-
-/// Initialize the global execution context
-pub fn init_execution_context(
-    stack: Stack,
-    dict: Dictionary,
-    loop_stack: LoopStack,
-    return_stack: ReturnStack,
-    memory: Memory,
-    included_files: HashSet<String>,
-    config: CompilerConfig,
-) {
-    EXECUTION_CONTEXT.with(|ctx| {
-        let context = ExecutionContext {
-            stack,
-            dict,
-            loop_stack,
-            return_stack,
-            memory,
-            included_files,
-            config,
-            dict_ptr: std::ptr::null_mut(),
-            loop_stack_ptr: std::ptr::null_mut(),
-            return_stack_ptr: std::ptr::null_mut(),
-            memory_ptr: std::ptr::null_mut(),
-            catch_stack: Vec::new(),
-        };
-
-        // Store context FIRST
-        *ctx.borrow_mut() = Some(context);
-
-        // NOW create pointers to the stored context
-        if let Some(context_ref) = ctx.borrow_mut().as_mut() {
-            let dict_ptr = &mut context_ref.dict as *mut Dictionary;
-            let loop_stack_ptr = &mut context_ref.loop_stack as *mut LoopStack;
-            let return_stack_ptr = &mut context_ref.return_stack as *mut ReturnStack;
-            let memory_ptr = &mut context_ref.memory as *mut Memory;
-
-            // Update the pointers in the context itself
-            context_ref.dict_ptr = dict_ptr;
-            context_ref.loop_stack_ptr = loop_stack_ptr;
-            context_ref.return_stack_ptr = return_stack_ptr;
-            context_ref.memory_ptr = memory_ptr;
-
-            let included_files_ptr = &mut context_ref.included_files as *mut HashSet<String>;
-
-            // Store pointers in separate thread-local for re-entrant access
-            REENTRANT_POINTERS.with(|ptrs| {
-                ptrs.set((dict_ptr, loop_stack_ptr, return_stack_ptr, memory_ptr, included_files_ptr));
-            });
-
-            // Store config flags
-            REENTRANT_CONFIG.with(|cfg| {
-                cfg.set((context_ref.config.no_jit, context_ref.config.dump_ir, context_ref.config.verify_ir));
-            });
-        }
-    });
-}
-
-/// Access the global execution context
-pub fn with_execution_context<F, R>(f: F) -> Option<R>
-where
-    F: FnOnce(&mut ExecutionContext) -> R,
-{
-    EXECUTION_CONTEXT.with(|ctx| {
-        ctx.borrow_mut().as_mut().map(f)
-    })
-}
-
-/// Take the execution context out (for returning from REPL to main)
-pub fn take_execution_context() -> Option<ExecutionContext> {
-    EXECUTION_CONTEXT.with(|ctx| ctx.borrow_mut().take())
-}
-
-/// Get raw pointers for re-entrant access (used by EVALUATE and CATCH)
-/// SAFETY: Caller must ensure pointers are valid and not used across re-entrant calls
-pub fn get_reentrant_pointers() -> Option<(*mut Dictionary, *mut LoopStack, *mut ReturnStack, *mut Memory, *mut HashSet<String>)> {
-    REENTRANT_POINTERS.with(|ptrs| {
-        let (dict_ptr, loop_stack_ptr, return_stack_ptr, memory_ptr, included_files_ptr) = ptrs.get();
-
-        // Check if pointers are null (not initialized)
-        if dict_ptr.is_null() || loop_stack_ptr.is_null() || return_stack_ptr.is_null() || memory_ptr.is_null() || included_files_ptr.is_null() {
-            None
-        } else {
-            Some((dict_ptr, loop_stack_ptr, return_stack_ptr, memory_ptr, included_files_ptr))
-        }
-    })
-}
-
-/// Get config flags for re-entrant access
-pub fn get_reentrant_config() -> (bool, bool, bool) {
-    REENTRANT_CONFIG.with(|cfg| cfg.get())
+    pub sp: usize,  // Data stack pointer below the xt given to CATCH
+    pub rp: usize,
+    pub loop_depth: usize,
+    pub capture_depth: usize,  // >STRING captures open
 }
 
 // Embedded standard library files
 const CORE_FTH: &str = include_str!("../stdlib/core.fth");
 #[allow(dead_code)] // TODO: Re-enable once DEPTH in loops is fixed
 const TEST_FRAMEWORK_FTH: &str = include_str!("../stdlib/test-framework.fth");
-#[allow(dead_code)] // TODO: Re-enable once test framework is fixed
-/// Clear all global registries (for testing)
-/// Call this between tests to avoid state pollution
-pub fn clear_test_state() {
-    ast_forth::ast_clear_registry();
-}
 
 // Loop stack for DO...LOOP counters
 #[derive(Debug, Clone)]
@@ -239,6 +131,15 @@ impl LoopStack {
         self.stack.pop()
     }
 
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Drop the innermost loops until `depth` remain (used by THROW)
+    pub fn truncate(&mut self, depth: usize) {
+        self.stack.truncate(depth);
+    }
+
     pub fn get_index(&self) -> Option<i64> {
         self.stack.last().map(|(index, _)| *index)
     }
@@ -339,18 +240,104 @@ impl ReturnStack {
 // Memory layout:
 // 0x000000-0x00FFFF: Data Stack (64KB)
 // 0x010000-0x01FFFF: Return Stack (64KB)
-// 0x020000-0x7FFFFF: User Memory and Dictionary (~7.5MB)
+// 0x020000-0x3FFFFF: User Memory and Dictionary (~3.9MB)
+// 0x400000-0x74FFFF: Heap for ALLOCATE, FREE and RESIZE (~3.3MB)
+// 0x750000-0x7CFFFF: Source texts (text of each source being interpreted, 512KB)
+// 0x7D0000-0x7DFFFF: Name area (name of each execution token, laid down when it is created)
+// 0x7E0000-0x7EFFFF: xt table (native entry point of each execution token, 0 if none)
+// 0x7F0000-0x7F7FFF: Float Stack (32KB)
+// 0x7F8000-0x7FFFFF: System cells (WORD buffer, terminal input buffer, open files, BASE, STATE,
+//                    pictured output, float stack pointer, heap top, throw code, input source,
+//                    execution mode, native word handler, session)
 
 // Fixed memory location for dictionary pointer (8 bytes before user memory)
 const DP_ADDR: usize = 0x01FFF8;
 
+// Start of user memory, where the dictionary's data space begins
+const USER_MEMORY_START: usize = 0x020000;
+
+// Start of the ALLOCATE heap, which bounds the dictionary
+const HEAP_BASE: usize = 0x400000;
+
+// Source texts: the text of each source being interpreted, stacked down from the end
+// as sources nest. The heap ends where they begin
+const INPUT_TEXT_BASE: usize = 0x750000;
+const INPUT_TEXT_END: usize = 0x7D0000;
+
+// Fixed memory location for the start of the lowest source text (0 while there is none)
+const INPUT_TEXT_ADDR: usize = 0x7FFE98;
+
+// Terminal input buffer: the line REFILL reads once user input runs out (longer lines are cut)
+const TIB_ADDR: usize = 0x7F8100;
+const TIB_SIZE: usize = 0x1000;
+
+// Cells compiled EXECUTE loads the native entry point of an xt from, indexed by xt
+pub const XT_TABLE_ADDR: usize = 0x7E0000;
+pub const XT_TABLE_CELLS: i64 = 0x2000;
+
+// Name area NAME>STRING finds the name of each execution token in, laid out in xt order
+pub(crate) const NAME_AREA_ADDR: usize = 0x7D0000;
+pub(crate) const NAME_AREA_SIZE: usize = 0x10000;
+
+// Float stack: 8-byte IEEE doubles growing upward from FLOAT_STACK_BASE
+const FLOAT_STACK_BASE: usize = 0x7F0000;
+const FLOAT_STACK_SIZE: usize = 0x8000;
+
+// Fixed memory location for the float stack pointer (depth of the float stack in bytes)
+const FSP_ADDR: usize = 0x7FFEE0;
+
+// Fixed memory location for a throw code raised by a primitive (0 = none)
+const THROW_ADDR: usize = 0x7FFED0;
+
+// Fixed memory locations describing the input source: the buffer SOURCE returns, >IN,
+// SOURCE-ID, and the whole text REFILL and RESTORE-INPUT move within
+const SOURCE_ADDR: usize = 0x7FFEC8;
+const SOURCE_LEN_ADDR: usize = 0x7FFEC0;
+const TO_IN_ADDR: usize = 0x7FFEB8;
+const SOURCE_ID_ADDR: usize = 0x7FFEB0;
+const INPUT_START_ADDR: usize = 0x7FFEA8;
+const INPUT_END_ADDR: usize = 0x7FFEA0;
+
+// Fixed memory location for the execution mode ENVIRONMENT? reports
+const EXECUTION_MODE_ADDR: usize = 0x7FFE90;
+
+// Fixed memory location for the function compiled code runs native words through
+// (quarter_call_native passes it the xt)
+const NATIVE_HANDLER_ADDR: usize = 0x7FFE88;
+
+// Fixed memory location for the address of the interpreter's session, which compiled
+// code calling back into the dictionary finds its state through
+pub(crate) const SESSION_ADDR: usize = 0x7FFE80;
+
+// Fixed memory location for the function compiled code writes output through
+const OUTPUT_HANDLER_ADDR: usize = 0x7FFE78;
+
 // Fixed memory location for BASE (numeric radix for I/O)
 const BASE_ADDR: usize = 0x7FFFF8;
 
+// Fixed memory location for STATE (true while compiling a colon definition)
+const STATE_ADDR: usize = 0x7FFFF0;
+
+/// The input source as the system cells describe it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct InputSource {
+    /// SOURCE-ID: 0 for user input, -1 for EVALUATE, otherwise the file being INCLUDEd
+    pub id: i64,
+    /// Bounds of the whole text
+    pub start: usize,
+    pub end: usize,
+    /// The input buffer SOURCE returns, the line being interpreted
+    pub line: usize,
+    pub len: usize,
+    /// >IN, the offset of the parse area in the input buffer
+    pub to_in: usize,
+}
+
 #[derive(Debug)]
 pub struct Memory {
     bytes: Vec<u8>,
     dp: usize, // Dictionary pointer - tracks next allocation address
+    session: Box<Session>,
 }
 
 impl Default for Memory {
@@ -363,15 +350,28 @@ impl Memory {
     pub fn new() -> Self {
         let mut memory = Memory {
             bytes: vec![0; 8 * 1024 * 1024], // 8MB like gforth
-            dp: 0x020000,                    // Start dictionary at beginning of user memory
+            dp: USER_MEMORY_START,           // Start dictionary at beginning of user memory
+            session: Box::default(),
         };
         // Sync dp to memory
         memory.sync_dp_to_memory();
         // Initialize BASE to default value (10 = decimal)
         memory.init_base();
+        // Compiled calls to native words come back to the dictionary as EXECUTE
+        let _ = memory.store(NATIVE_HANDLER_ADDR, crate::words::quarter_execute as *const () as i64);
+        // The session is boxed, so its address stays put when the Memory moves
+        let session = &*memory.session as *const Session as i64;
+        let _ = memory.store(SESSION_ADDR, session);
+        // Compiled output words write to the session like interpreted ones
+        let _ = memory.store(OUTPUT_HANDLER_ADDR, crate::words::quarter_write_output as *const () as i64);
         memory
     }
 
+    /// The interpreter state kept outside Forth memory
+    pub fn session(&mut self) -> &mut Session {
+        &mut self.session
+    }
+
     // Sync dictionary pointer to fixed memory location
     fn sync_dp_to_memory(&mut self) {
         let bytes = (self.dp as i64).to_le_bytes();
@@ -423,11 +423,108 @@ impl Memory {
         BASE_ADDR as i64
     }
 
+    // STATE - return address of compilation state variable
+    pub fn state(&self) -> i64 {
+        STATE_ADDR as i64
+    }
+
+    // Raise a throw code from a primitive, which has no other way to report an error
+    // The first code raised is kept until taken
+    pub fn raise(&mut self, code: i64) {
+        if self.fetch(THROW_ADDR) == Ok(0) {
+            let _ = self.store(THROW_ADDR, code);
+        }
+    }
+
+    // Take the throw code raised since the last call, if any
+    pub fn take_throw(&mut self) -> Option<i64> {
+        let code = self.fetch(THROW_ADDR).ok().filter(|&code| code != 0)?;
+        self.store(THROW_ADDR, 0).ok()?;
+        Some(code)
+    }
+
+    // The input source SOURCE, >IN and SOURCE-ID report
+    pub(crate) fn input_source(&self) -> InputSource {
+        let cell = |addr| self.fetch(addr).unwrap_or(0);
+        InputSource {
+            id: cell(SOURCE_ID_ADDR),
+            start: cell(INPUT_START_ADDR) as usize,
+            end: cell(INPUT_END_ADDR) as usize,
+            line: cell(SOURCE_ADDR) as usize,
+            len: cell(SOURCE_LEN_ADDR) as usize,
+            to_in: cell(TO_IN_ADDR) as usize,
+        }
+    }
+
+    // Record how the program is being run, for [IF] to select code by
+    pub fn set_execution_mode(&mut self, mode: ExecutionMode) {
+        let _ = self.store(EXECUTION_MODE_ADDR, mode as i64);
+    }
+
+    // Make `input` the input source
+    pub(crate) fn set_input_source(&mut self, input: InputSource) {
+        for (addr, value) in [
+            (SOURCE_ID_ADDR, input.id),
+            (INPUT_START_ADDR, input.start as i64),
+            (INPUT_END_ADDR, input.end as i64),
+            (SOURCE_ADDR, input.line as i64),
+            (SOURCE_LEN_ADDR, input.len as i64),
+            (TO_IN_ADDR, input.to_in as i64),
+        ] {
+            let _ = self.store(addr, value);
+        }
+    }
+
+    // REFILL past the end of user input: make the next line of the session's input the
+    // input buffer. False at the end of the input
+    pub(crate) fn refill_terminal(&mut self) -> bool {
+        let Some(mut line) = self.session.next_line() else {
+            return false;
+        };
+        line.truncate(TIB_SIZE);
+        let _ = self.store_bytes(TIB_ADDR, &line);
+        self.set_input_source(InputSource {
+            id: 0,
+            start: TIB_ADDR,
+            end: TIB_ADDR + line.len(),
+            line: TIB_ADDR,
+            len: line.len(),
+            to_in: 0,
+        });
+        true
+    }
+
+    // Number of floats on the float stack
+    pub fn fdepth(&self) -> usize {
+        self.fetch(FSP_ADDR).unwrap_or(0) as usize / 8
+    }
+
+    // Push a float onto the float stack
+    pub fn fpush(&mut self, value: f64) -> Result<(), QuarterError> {
+        let fsp = self.fdepth() * 8;
+        if fsp + 8 > FLOAT_STACK_SIZE {
+            return Err(QuarterError::FloatStackOverflow);
+        }
+        self.store(FLOAT_STACK_BASE + fsp, value.to_bits() as i64)?;
+        self.store(FSP_ADDR, (fsp + 8) as i64)
+    }
+
+    // Pop a float from the float stack
+    pub fn fpop(&mut self) -> Option<f64> {
+        let fsp = self.fdepth() * 8;
+        if fsp == 0 {
+            return None;
+        }
+        let bits = self.fetch(FLOAT_STACK_BASE + fsp - 8).ok()?;
+        self.store(FSP_ADDR, (fsp - 8) as i64).ok()?;
+        Some(f64::from_bits(bits as u64))
+    }
+
     // ALLOT - allocate n bytes in dictionary space
-    pub fn allot(&mut self, n: i64) -> Result<(), String> {
+    pub fn allot(&mut self, n: i64) -> Result<(), QuarterError> {
         let new_dp = (self.dp as i64 + n) as usize;
-        if new_dp >= self.bytes.len() {
-            return Err("Dictionary overflow".to_string());
+        if new_dp >= HEAP_BASE {
+            return Err(QuarterError::DictionaryOverflow);
         }
         self.dp = new_dp;
         // Sync to memory so JIT code can access it
@@ -436,9 +533,9 @@ impl Memory {
     }
 
     // @ - fetch cell (8 bytes as i64, little-endian)
-    pub fn fetch(&self, addr: usize) -> Result<i64, String> {
+    pub fn fetch(&self, addr: usize) -> Result<i64, QuarterError> {
         if addr + 8 > self.bytes.len() {
-            return Err(format!("Memory fetch out of bounds: address {}", addr));
+            return Err(QuarterError::MemoryBounds(addr));
         }
         let bytes = [
             self.bytes[addr],
@@ -454,9 +551,9 @@ impl Memory {
     }
 
     // ! - store cell (i64 as 8 bytes, little-endian)
-    pub fn store(&mut self, addr: usize, value: i64) -> Result<(), String> {
+    pub fn store(&mut self, addr: usize, value: i64) -> Result<(), QuarterError> {
         if addr + 8 > self.bytes.len() {
-            return Err(format!("Memory store out of bounds: address {}", addr));
+            return Err(QuarterError::MemoryBounds(addr));
         }
         let bytes = value.to_le_bytes();
         self.bytes[addr] = bytes[0];
@@ -471,22 +568,51 @@ impl Memory {
     }
 
     // C@ - fetch byte (return as i64)
-    pub fn fetch_byte(&self, addr: usize) -> Result<i64, String> {
+    pub fn fetch_byte(&self, addr: usize) -> Result<i64, QuarterError> {
         if addr >= self.bytes.len() {
-            return Err(format!("Memory byte fetch out of bounds: address {}", addr));
+            return Err(QuarterError::MemoryBounds(addr));
         }
         Ok(self.bytes[addr] as i64)
     }
 
     // C! - store byte (store low byte of i64)
-    pub fn store_byte(&mut self, addr: usize, value: i64) -> Result<(), String> {
+    pub fn store_byte(&mut self, addr: usize, value: i64) -> Result<(), QuarterError> {
         if addr >= self.bytes.len() {
-            return Err(format!("Memory byte store out of bounds: address {}", addr));
+            return Err(QuarterError::MemoryBounds(addr));
         }
         self.bytes[addr] = (value & 0xFF) as u8;
         Ok(())
     }
 
+    // Copy the text of a source below the source texts already there
+    // Returns its address
+    pub(crate) fn push_input_text(&mut self, text: &[u8]) -> Result<usize, QuarterError> {
+        let bottom = match self.fetch(INPUT_TEXT_ADDR)? {
+            0 => INPUT_TEXT_END,
+            bottom => bottom as usize,
+        };
+        let addr = bottom.checked_sub(text.len())
+            .map(|addr| addr & !7)
+            .filter(|&addr| addr >= INPUT_TEXT_BASE)
+            .ok_or_else(|| QuarterError::Other("Not enough room for the source text".to_string()))?;
+        self.store_bytes(addr, text)?;
+        self.store(INPUT_TEXT_ADDR, addr as i64)?;
+        Ok(addr)
+    }
+
+    // Release the text push_input_text put at addr
+    pub(crate) fn pop_input_text(&mut self, addr: usize, len: usize) {
+        let _ = self.store(INPUT_TEXT_ADDR, ((addr + len + 7) & !7) as i64);
+    }
+
+    // Copy bytes into memory starting at addr
+    pub fn store_bytes(&mut self, addr: usize, bytes: &[u8]) -> Result<(), QuarterError> {
+        let end = addr.checked_add(bytes.len()).filter(|&end| end <= self.bytes.len())
+            .ok_or(QuarterError::MemoryBounds(addr))?;
+        self.bytes[addr..end].copy_from_slice(bytes);
+        Ok(())
+    }
+
     // Get a mutable pointer to memory at a given address
     // Used for JIT-compiled functions
     pub fn get_ptr_at(&mut self, addr: usize) -> *mut i64 {
@@ -499,22 +625,59 @@ impl Memory {
     }
 }
 
-pub fn parse_tokens(tokens: &[&str], dict: &crate::Dictionary, current_word: Option<&str>) -> Result<AstNode, String> {
+/// Words handled by parse_tokens itself rather than looked up in the dictionary
+/// POSTPONE accepts these so immediate words can build control structures
+const COMPILER_KEYWORDS: &[&str] = &[
+    "IF", "ELSE", "THEN", "BEGIN", "UNTIL", "WHILE", "REPEAT",
+    "DO", "?DO", "LOOP", "+LOOP", "LEAVE", "EXIT", "UNLOOP", "RECURSE",
+    "CASE", "OF", "ENDOF", "ENDCASE",
+];
+
+pub fn parse_tokens(tokens: &[&str], dict: &crate::Dictionary, current_word: Option<&str>) -> Result<AstNode, QuarterError> {
+    parse_with_locals(tokens, dict, current_word, &[])
+}
+
+/// Parse tokens where `locals` (uppercase, in frame order) are the names
+/// declared by an enclosing {: ... :}
+fn parse_with_locals(tokens: &[&str], dict: &crate::Dictionary, current_word: Option<&str>, locals: &[String]) -> Result<AstNode, QuarterError> {
+    let (nodes, _) = parse_nodes(tokens, dict, current_word, locals)?;
+
+    if nodes.len() == 1 {
+        Ok(nodes.into_iter().next().unwrap())
+    } else {
+        Ok(AstNode::Sequence(nodes))
+    }
+}
+
+/// Parse tokens into a list of nodes, along with the index of the token each node starts at
+fn parse_nodes(tokens: &[&str], dict: &crate::Dictionary, current_word: Option<&str>, locals: &[String]) -> Result<(Vec<AstNode>, Vec<usize>), QuarterError> {
     let mut nodes = Vec::new();
+    let mut starts = Vec::new();
+    let mut start = 0;
     let mut i = 0;
 
     while i < tokens.len() {
+        // Nodes added since the last token started there
+        starts.resize(nodes.len(), start);
+        start = i;
         let token = tokens[i];
         let token_upper = token.to_uppercase();
 
+        // Locals shadow every other word
+        if let Some(index) = locals.iter().position(|name| *name == token_upper) {
+            nodes.push(AstNode::LocalFetch(index));
+            i += 1;
+            continue;
+        }
+
         match token_upper.as_str() {
             "RECURSE" => {
                 // RECURSE - compile-only word for recursion
                 if let Some(word_name) = current_word {
-                    nodes.push(AstNode::CallWord(word_name.to_string()));
+                    nodes.push(AstNode::CallWord(dict.definition_key(word_name)));
                     i += 1;
                 } else {
-                    return Err("RECURSE can only be used inside a word definition".to_string());
+                    return Err(QuarterError::Compile("RECURSE can only be used inside a word definition".to_string()));
                 }
             }
             ".\"" => {
@@ -686,30 +849,140 @@ pub fn parse_tokens(tokens: &[&str], dict: &crate::Dictionary, current_word: Opt
                 // ['] <name> (BRACKET-TICK) - compile-only
                 // Parse next word name at compile time, compile code to push xt at runtime
                 if i + 1 >= tokens.len() {
-                    return Err("['] requires a word name".to_string());
+                    return Err(QuarterError::Compile("['] requires a word name".to_string()));
                 }
 
                 let word_name = tokens[i + 1].to_uppercase();
 
                 // Check that the word exists (compile-time validation)
-                if !dict.has_word(&word_name) {
-                    return Err(format!("['] cannot find word: {}", word_name));
+                let Some(key) = dict.find(&word_name) else {
+                    return Err(QuarterError::Compile(format!("['] cannot find word: {}", word_name)));
+                };
+
+                // xts never change, so the xt is compiled as a literal
+                nodes.push(AstNode::PushNumber(dict.xt(&key).unwrap_or(0)));
+                i += 2;
+            }
+            "{:" => {
+                // {: args | others -- outputs :} - Forth-2012 locals
+                // The rest of the definition is parsed as the scope of the locals
+                if !locals.is_empty() {
+                    return Err(QuarterError::Compile("Only one {: ... :} allowed per definition".to_string()));
+                }
+
+                let end = tokens[i + 1..].iter().position(|t| *t == ":}")
+                    .ok_or_else(|| QuarterError::Compile("Missing :}".to_string()))? + i + 1;
+
+                let mut names: Vec<String> = Vec::new();
+                let mut args = None;
+                for &name in &tokens[i + 1..end] {
+                    match name {
+                        "|" if args.is_none() => args = Some(names.len()),
+                        "--" => break,  // Outputs are only a comment
+                        _ => {
+                            let name_upper = name.to_uppercase();
+                            if names.contains(&name_upper) {
+                                return Err(QuarterError::Compile(format!("Duplicate local: {}", name_upper)));
+                            }
+                            names.push(name_upper);
+                        }
+                    }
+                }
+
+                let body = parse_with_locals(&tokens[end + 1..], dict, current_word, &names)?;
+                nodes.push(AstNode::Locals {
+                    count: names.len(),
+                    args: args.unwrap_or(names.len()),
+                    body: match body {
+                        AstNode::Sequence(body_nodes) => body_nodes,
+                        node => vec![node],
+                    },
+                });
+                i = tokens.len();
+            }
+            ":}" => {
+                return Err(QuarterError::Compile(":} without {:".to_string()));
+            }
+            "TO" | "+TO" => {
+                // <x> TO <name> / <n> +TO <name>
+                // Compiles a store to the local, or to the VALUE's data cell
+                if i + 1 >= tokens.len() {
+                    return Err(QuarterError::Compile(format!("{} requires a VALUE name", token_upper)));
+                }
+
+                let value_name = tokens[i + 1].to_uppercase();
+                if let Some(index) = locals.iter().position(|name| *name == value_name) {
+                    if token_upper == "+TO" {
+                        nodes.push(AstNode::LocalFetch(index));
+                        nodes.push(AstNode::CallWord("+".to_string()));
+                    }
+                    nodes.push(AstNode::LocalStore(index));
+                    i += 2;
+                    continue;
+                }
+
+                let addr = dict.get_value_addr(&value_name)
+                    .ok_or_else(|| QuarterError::Compile(format!("{} is not a VALUE", value_name)))? as i64;
+
+                if token_upper == "+TO" {
+                    nodes.push(AstNode::PushNumber(addr));
+                    nodes.push(AstNode::CallWord("@".to_string()));
+                    nodes.push(AstNode::CallWord("+".to_string()));
+                }
+                nodes.push(AstNode::PushNumber(addr));
+                nodes.push(AstNode::CallWord("!".to_string()));
+                i += 2;
+            }
+            "IS" | "ACTION-OF" => {
+                // <xt> IS <name> / ACTION-OF <name>
+                // Compiles a store to (or fetch from) the DEFER word's xt cell
+                if i + 1 >= tokens.len() {
+                    return Err(QuarterError::Compile(format!("{} requires a DEFER name", token_upper)));
+                }
+
+                let defer_name = tokens[i + 1].to_uppercase();
+                let addr = dict.get_deferred_addr(&defer_name)
+                    .ok_or_else(|| QuarterError::Compile(format!("{} is not a deferred word", defer_name)))? as i64;
+
+                nodes.push(AstNode::PushNumber(addr));
+                if token_upper == "IS" {
+                    nodes.push(AstNode::CallWord("!".to_string()));
+                } else {
+                    nodes.push(AstNode::CallWord("@".to_string()));
+                }
+                i += 2;
+            }
+            "POSTPONE" => {
+                // POSTPONE <name> - compile-only
+                // Immediate words are called when this word runs; any other word is
+                // appended to the definition being compiled at that point
+                if i + 1 >= tokens.len() {
+                    return Err(QuarterError::Compile("POSTPONE requires a word name".to_string()));
                 }
 
-                // Compile TickLiteral node - creates xt at runtime
-                nodes.push(AstNode::TickLiteral(word_name));
+                let word_name = tokens[i + 1].to_uppercase();
+
+                if dict.is_immediate(&word_name) {
+                    nodes.push(AstNode::CallWord(dict.find(&word_name).unwrap_or(word_name)));
+                } else if let Some(key) = dict.find(&word_name) {
+                    nodes.push(AstNode::Postpone(key));
+                } else if COMPILER_KEYWORDS.contains(&word_name.as_str()) {
+                    nodes.push(AstNode::Postpone(word_name));
+                } else {
+                    return Err(QuarterError::Compile(format!("POSTPONE cannot find word: {}", word_name)));
+                }
                 i += 2;
             }
             "[CHAR]" => {
                 // [CHAR] <name> - compile-only
                 // Parse next word, compile code to push ASCII value of first character
                 if i + 1 >= tokens.len() {
-                    return Err("[CHAR] requires a word".to_string());
+                    return Err(QuarterError::Compile("[CHAR] requires a word".to_string()));
                 }
 
                 let word = tokens[i + 1];
                 if word.is_empty() {
-                    return Err("[CHAR] requires a non-empty word".to_string());
+                    return Err(QuarterError::Compile("[CHAR] requires a non-empty word".to_string()));
                 }
 
                 // Get first character and compile a literal
@@ -721,12 +994,12 @@ pub fn parse_tokens(tokens: &[&str], dict: &crate::Dictionary, current_word: Opt
                 // CHAR <name> - can be used in definitions too
                 // Parse next word, compile code to push ASCII value of first character
                 if i + 1 >= tokens.len() {
-                    return Err("CHAR requires a word".to_string());
+                    return Err(QuarterError::Compile("CHAR requires a word".to_string()));
                 }
 
                 let word = tokens[i + 1];
                 if word.is_empty() {
-                    return Err("CHAR requires a non-empty word".to_string());
+                    return Err(QuarterError::Compile("CHAR requires a non-empty word".to_string()));
                 }
 
                 // Get first character and compile a literal
@@ -738,18 +1011,18 @@ pub fn parse_tokens(tokens: &[&str], dict: &crate::Dictionary, current_word: Opt
                 // ' <name> (TICK) - can be used in definitions
                 // Parse next word name, compile code to create xt at runtime
                 if i + 1 >= tokens.len() {
-                    return Err("' requires a word name".to_string());
+                    return Err(QuarterError::Compile("' requires a word name".to_string()));
                 }
 
                 let word_name = tokens[i + 1].to_uppercase();
 
                 // Check that the word exists (compile-time validation)
-                if !dict.has_word(&word_name) {
-                    return Err(format!("' cannot find word: {}", word_name));
-                }
+                let Some(key) = dict.find(&word_name) else {
+                    return Err(QuarterError::Compile(format!("' cannot find word: {}", word_name)));
+                };
 
-                // Compile TickLiteral node - creates xt at runtime
-                nodes.push(AstNode::TickLiteral(word_name));
+                // xts never change, so the xt is compiled as a literal
+                nodes.push(AstNode::PushNumber(dict.xt(&key).unwrap_or(0)));
                 i += 2;
             }
             "BEGIN" => {
@@ -762,7 +1035,7 @@ pub fn parse_tokens(tokens: &[&str], dict: &crate::Dictionary, current_word: Opt
                 if end_keyword == "UNTIL" {
                     // BEGIN...UNTIL loop
                     let body_tokens = &tokens[i + 1..i + 1 + end_pos.0];
-                    let body_ast = parse_tokens(body_tokens, dict, current_word)?;
+                    let body_ast = parse_with_locals(body_tokens, dict, current_word, locals)?;
 
                     nodes.push(AstNode::BeginUntil {
                         body: if let AstNode::Sequence(v) = body_ast {
@@ -779,8 +1052,8 @@ pub fn parse_tokens(tokens: &[&str], dict: &crate::Dictionary, current_word: Opt
                         let condition_tokens = &tokens[i + 1..i + 1 + while_pos];
                         let body_tokens = &tokens[i + 1 + while_pos + 1..i + 1 + end_pos.0];
 
-                        let condition_ast = parse_tokens(condition_tokens, dict, current_word)?;
-                        let body_ast = parse_tokens(body_tokens, dict, current_word)?;
+                        let condition_ast = parse_with_locals(condition_tokens, dict, current_word, locals)?;
+                        let body_ast = parse_with_locals(body_tokens, dict, current_word, locals)?;
 
                         nodes.push(AstNode::BeginWhileRepeat {
                             condition: if let AstNode::Sequence(v) = condition_ast {
@@ -797,10 +1070,10 @@ pub fn parse_tokens(tokens: &[&str], dict: &crate::Dictionary, current_word: Opt
 
                         i += end_pos.0 + 2; // Skip past REPEAT
                     } else {
-                        return Err("BEGIN...REPEAT requires WHILE".to_string());
+                        return Err(QuarterError::Compile("BEGIN...REPEAT requires WHILE".to_string()));
                     }
                 } else {
-                    return Err(format!("Unexpected {} after BEGIN", end_keyword));
+                    return Err(QuarterError::Compile(format!("Unexpected {} after BEGIN", end_keyword)));
                 }
             }
             "DO" | "?DO" => {
@@ -809,7 +1082,7 @@ pub fn parse_tokens(tokens: &[&str], dict: &crate::Dictionary, current_word: Opt
                 let loop_keyword = tokens[i + 1 + loop_pos];
 
                 let body_tokens = &tokens[i + 1..i + 1 + loop_pos];
-                let body_ast = parse_tokens(body_tokens, dict, current_word)?;
+                let body_ast = parse_with_locals(body_tokens, dict, current_word, locals)?;
 
                 let increment = if loop_keyword == "+LOOP" {
                     0 // Special marker for +LOOP (stack-based increment)
@@ -841,12 +1114,12 @@ pub fn parse_tokens(tokens: &[&str], dict: &crate::Dictionary, current_word: Opt
                 } else {
                     &tokens[i + 1..i + 1 + then_end]
                 };
-                let then_branch = parse_tokens(then_tokens, dict, current_word)?;
+                let then_branch = parse_with_locals(then_tokens, dict, current_word, locals)?;
 
                 // Parse ELSE branch if it exists (from after ELSE to THEN)
                 let else_branch = if let Some(else_pos) = else_start {
                     let else_tokens = &tokens[i + 1 + else_pos + 1..i + 1 + then_end];
-                    Some(parse_tokens(else_tokens, dict, current_word)?)
+                    Some(parse_with_locals(else_tokens, dict, current_word, locals)?)
                 } else {
                     None
                 };
@@ -869,7 +1142,42 @@ pub fn parse_tokens(tokens: &[&str], dict: &crate::Dictionary, current_word: Opt
                 i += then_end + 2; // Skip past THEN
             }
             "THEN" | "ELSE" => {
-                return Err("Unexpected THEN or ELSE".to_string());
+                return Err(QuarterError::Compile("Unexpected THEN or ELSE".to_string()));
+            }
+            "CASE" => {
+                // Find matching ENDCASE and the OF/ENDOF pairs at this nesting level
+                let (endcase_pos, of_pairs) = find_case_clauses(&tokens[i + 1..])?;
+                let case_tokens = &tokens[i + 1..i + 1 + endcase_pos];
+
+                // Each clause is test code before OF and a body up to ENDOF
+                let mut clauses = Vec::new();
+                let mut start = 0;
+                for (of_pos, endof_pos) in of_pairs {
+                    let test_ast = parse_with_locals(&case_tokens[start..of_pos], dict, current_word, locals)?;
+                    let body_ast = parse_with_locals(&case_tokens[of_pos + 1..endof_pos], dict, current_word, locals)?;
+                    clauses.push((
+                        if let AstNode::Sequence(v) = test_ast { v } else { vec![test_ast] },
+                        if let AstNode::Sequence(v) = body_ast { v } else { vec![body_ast] },
+                    ));
+                    start = endof_pos + 1;
+                }
+
+                // Anything after the last ENDOF is the default code
+                let default_ast = parse_with_locals(&case_tokens[start..], dict, current_word, locals)?;
+
+                nodes.push(AstNode::Case {
+                    clauses,
+                    default: if let AstNode::Sequence(v) = default_ast {
+                        v
+                    } else {
+                        vec![default_ast]
+                    },
+                });
+
+                i += endcase_pos + 2; // Skip past ENDCASE
+            }
+            "OF" | "ENDOF" | "ENDCASE" => {
+                return Err(QuarterError::Compile(format!("{} without CASE", token_upper)));
             }
             "LEAVE" => {
                 nodes.push(AstNode::Leave);
@@ -886,7 +1194,7 @@ pub fn parse_tokens(tokens: &[&str], dict: &crate::Dictionary, current_word: Opt
             "INLINE" => {
                 // INLINE <instruction> - marks next token as an inline LLVM instruction
                 if i + 1 >= tokens.len() {
-                    return Err("INLINE requires an instruction name".to_string());
+                    return Err(QuarterError::Compile("INLINE requires an instruction name".to_string()));
                 }
                 let instruction = tokens[i + 1].to_uppercase();
                 nodes.push(AstNode::InlineInstruction(instruction));
@@ -896,37 +1204,81 @@ pub fn parse_tokens(tokens: &[&str], dict: &crate::Dictionary, current_word: Opt
                 // Try to parse as number, otherwise it's a word
                 if let Ok(num) = token.parse::<i64>() {
                     nodes.push(AstNode::PushNumber(num));
+                } else if let Some(value) = parse_double(token) {
+                    // Double-cell literal such as 123. pushes ( lo hi )
+                    nodes.push(AstNode::PushNumber(value as i64));
+                    nodes.push(AstNode::PushNumber((value >> 64) as i64));
+                } else if let Some(value) = parse_float(token) {
+                    nodes.push(AstNode::PushFloat(value));
                 } else {
                     // Check if word is a simple constant (VARIABLE or CONSTANT)
-                    // If so, inline it to avoid JIT lookup errors
+                    // If so, inline it to avoid JIT lookup errors. The word CREATE made
+                    // last isn't, as DOES> may still change it
                     if let Some(word) = dict.get_word(&token_upper)
                         && let crate::dictionary::Word::Compiled(ast_node) = word
-                        && let AstNode::PushNumber(value) = ast_node {
+                        && let AstNode::PushNumber(value) = ast_node
+                        && dict.last_created().is_none_or(|(name, _)| name != token_upper) {
                             // Inline the constant value directly
                             nodes.push(AstNode::PushNumber(*value));
                             i += 1;
                             continue;
                         }
 
+                    // Same for a 2CONSTANT, which pushes a pair of numbers. A colon
+                    // definition with the same body isn't one, and is called
+                    if dict.is_2constant(&token_upper)
+                        && let Some(crate::dictionary::Word::Compiled(AstNode::Sequence(pair))) = dict.get_word(&token_upper)
+                        && let [AstNode::PushNumber(x1), AstNode::PushNumber(x2)] = pair.as_slice() {
+                            nodes.push(AstNode::PushNumber(*x1));
+                            nodes.push(AstNode::PushNumber(*x2));
+                            i += 1;
+                            continue;
+                        }
+
+                    // And for an FCONSTANT
+                    if let Some(crate::dictionary::Word::Compiled(AstNode::PushFloat(value))) = dict.get_word(&token_upper) {
+                        nodes.push(AstNode::PushFloat(*value));
+                        i += 1;
+                        continue;
+                    }
+
                     // Store word names in uppercase for case-insensitive lookup
-                    nodes.push(AstNode::CallWord(token_upper.clone()));
+                    nodes.push(AstNode::CallWord(dict.bind(&token_upper, current_word)));
                 }
                 i += 1;
             }
         }
     }
+    starts.resize(nodes.len(), start);
 
-    if nodes.len() == 1 {
-        Ok(nodes.into_iter().next().unwrap())
-    } else {
-        Ok(AstNode::Sequence(nodes))
+    Ok((nodes, starts))
+}
+
+/// Parse a double-cell literal: an integer with a trailing dot, e.g. 123. or -5.
+fn parse_double(token: &str) -> Option<i128> {
+    let digits = token.strip_suffix('.')?;
+    digits.parse::<i128>().ok()
+}
+
+/// Parse a floating-point literal, which always has an exponent: 1.5e0, -2E3, 1e
+fn parse_float(token: &str) -> Option<f64> {
+    let first = token.chars().next()?;
+    if !(first.is_ascii_digit() || matches!(first, '+' | '-' | '.')) || !token.contains(['e', 'E']) {
+        return None;
     }
+    // An empty exponent means zero
+    let mut text = token.to_string();
+    if text.ends_with(['e', 'E', '+', '-']) {
+        text.push('0');
+    }
+    text.parse::<f64>().ok()
 }
 
-fn find_then_else(tokens: &[&str]) -> Result<(usize, Option<usize>), String> {
+fn find_then_else(tokens: &[&str]) -> Result<(usize, Option<usize>), QuarterError> {
     let mut depth = 0;
     let mut else_pos = None;
     let mut in_string = false; // Track if we're inside .( or ."
+    let mut postponed = false; // Token after POSTPONE is a name, not a keyword
 
     for (i, &token) in tokens.iter().enumerate() {
         // Track if we're inside string literals to avoid matching keywords
@@ -940,6 +1292,14 @@ fn find_then_else(tokens: &[&str]) -> Result<(usize, Option<usize>), String> {
             }
             continue;
         }
+        if postponed {
+            postponed = false;
+            continue;
+        }
+        if token.eq_ignore_ascii_case("POSTPONE") {
+            postponed = true;
+            continue;
+        }
 
         let token_upper = token.to_uppercase();
         match token_upper.as_str() {
@@ -959,15 +1319,16 @@ fn find_then_else(tokens: &[&str]) -> Result<(usize, Option<usize>), String> {
         }
     }
 
-    Err("Missing THEN".to_string())
+    Err(QuarterError::Compile("Missing THEN".to_string()))
 }
 
 // Find matching UNTIL or WHILE/REPEAT for BEGIN
 // Returns (end_pos, while_pos)
-fn find_begin_end(tokens: &[&str]) -> Result<(usize, Option<usize>), String> {
+fn find_begin_end(tokens: &[&str]) -> Result<(usize, Option<usize>), QuarterError> {
     let mut depth = 0;
     let mut while_pos = None;
     let mut in_string = false; // Track if we're inside .( or ."
+    let mut postponed = false; // Token after POSTPONE is a name, not a keyword
 
     for (i, &token) in tokens.iter().enumerate() {
         // Track if we're inside string literals to avoid matching keywords
@@ -981,6 +1342,14 @@ fn find_begin_end(tokens: &[&str]) -> Result<(usize, Option<usize>), String> {
             }
             continue;
         }
+        if postponed {
+            postponed = false;
+            continue;
+        }
+        if token.eq_ignore_ascii_case("POSTPONE") {
+            postponed = true;
+            continue;
+        }
 
         let token_upper = token.to_uppercase();
         match token_upper.as_str() {
@@ -1006,13 +1375,14 @@ fn find_begin_end(tokens: &[&str]) -> Result<(usize, Option<usize>), String> {
         }
     }
 
-    Err("Missing UNTIL or REPEAT".to_string())
+    Err(QuarterError::Compile("Missing UNTIL or REPEAT".to_string()))
 }
 
 // Find matching LOOP or +LOOP for DO
-fn find_do_loop(tokens: &[&str]) -> Result<usize, String> {
+fn find_do_loop(tokens: &[&str]) -> Result<usize, QuarterError> {
     let mut depth = 0;
     let mut in_string = false;
+    let mut postponed = false; // Token after POSTPONE is a name, not a keyword
 
     for (i, &token) in tokens.iter().enumerate() {
         // Track if we're inside string literals to avoid matching keywords
@@ -1026,6 +1396,14 @@ fn find_do_loop(tokens: &[&str]) -> Result<usize, String> {
             }
             continue;
         }
+        if postponed {
+            postponed = false;
+            continue;
+        }
+        if token.eq_ignore_ascii_case("POSTPONE") {
+            postponed = true;
+            continue;
+        }
 
         let token_upper = token.to_uppercase();
         match token_upper.as_str() {
@@ -1040,92 +1418,135 @@ fn find_do_loop(tokens: &[&str]) -> Result<usize, String> {
         }
     }
 
-    Err("Missing LOOP or +LOOP".to_string())
+    Err(QuarterError::Compile("Missing LOOP or +LOOP".to_string()))
+}
+
+// Find matching ENDCASE for CASE
+// Returns (endcase_pos, [(of_pos, endof_pos)]) for the clauses of this CASE
+fn find_case_clauses(tokens: &[&str]) -> Result<(usize, Vec<(usize, usize)>), QuarterError> {
+    let mut depth = 0;
+    let mut of_pos = None;
+    let mut clauses = Vec::new();
+    let mut in_string = false;
+    let mut postponed = false; // Token after POSTPONE is a name, not a keyword
+
+    for (i, &token) in tokens.iter().enumerate() {
+        // Track if we're inside string literals to avoid matching keywords
+        if token == ".(" || token == ".\"" {
+            in_string = true;
+            continue;
+        }
+        if in_string {
+            if token.ends_with(')') || token.ends_with('"') {
+                in_string = false;
+            }
+            continue;
+        }
+        if postponed {
+            postponed = false;
+            continue;
+        }
+        if token.eq_ignore_ascii_case("POSTPONE") {
+            postponed = true;
+            continue;
+        }
+
+        let token_upper = token.to_uppercase();
+        match token_upper.as_str() {
+            "CASE" => depth += 1,
+            "OF" if depth == 0 => {
+                if of_pos.is_some() {
+                    return Err(QuarterError::Compile("OF without ENDOF".to_string()));
+                }
+                of_pos = Some(i);
+            }
+            "ENDOF" if depth == 0 => match of_pos.take() {
+                Some(pos) => clauses.push((pos, i)),
+                None => return Err(QuarterError::Compile("ENDOF without OF".to_string())),
+            },
+            "ENDCASE" => {
+                if depth == 0 {
+                    if of_pos.is_some() {
+                        return Err(QuarterError::Compile("OF without ENDOF".to_string()));
+                    }
+                    return Ok((i, clauses));
+                }
+                depth -= 1;
+            }
+            _ => {}
+        }
+    }
+
+    Err(QuarterError::Compile("Missing ENDCASE".to_string()))
 }
 
 /// Strip comments from a line of Forth code
-/// Handles both backslash comments (\) and parenthesis comments ( )
+/// Handles both backslash comments (\) and parenthesis comments ( ), each started by a word of its own
 /// Preserves .( ... ) which is the print-string word, not a comment
 pub fn strip_comments(input: &str) -> String {
-    // First, strip backslash comments (everything after \)
-    let line = if let Some(pos) = input.find('\\') {
-        &input[..pos]
-    } else {
-        input
-    };
+    input.chars()
+        .zip(comment_mask(input))
+        .filter_map(|(ch, keep)| keep.then_some(ch))
+        .collect()
+}
+
+/// Which characters of a line strip_comments keeps, one flag per character
+/// The text of S" ." C" ABORT" and .( strings is kept whole, parentheses and backslashes included
+pub(crate) fn comment_mask(input: &str) -> Vec<bool> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut mask = vec![false; chars.len()];
 
-    // Then strip parenthesis comments ( ... ) but preserve .( ... )
-    let mut result = String::new();
     let mut in_paren_comment = false;
-    let mut in_dot_paren = false;
-    let chars: Vec<char> = line.chars().collect();
-    let mut i = 0;
+    // Closing delimiter of the string being kept, while inside one
+    let mut in_string: Option<char> = None;
+    let mut token_start = 0;
 
-    while i < chars.len() {
+    for i in 0..chars.len() {
         let ch = chars[i];
 
-        if ch == '(' {
-            // Only check for .( if we're not already in a comment
-            // (to handle nested parentheses in stack comments like ( n -- fib(n) ))
-            if !in_paren_comment {
-                // Check if this is .( (print-string) or just ( (comment)
-                // Look back to see if previous non-whitespace char is .
-                let is_dot_paren_start = if i > 0 {
-                    // Find the previous non-whitespace character
-                    let mut j = i - 1;
-                    let mut found_dot = false;
-                    loop {
-                        if chars[j] == '.' {
-                            // Found . immediately before (, this is .(
-                            found_dot = true;
-                            break;
-                        } else if chars[j].is_whitespace() {
-                            // Skip whitespace
-                            if j == 0 {
-                                break;
-                            }
-                            j -= 1;
-                        } else {
-                            // Found some other character, not .(
-                            break;
-                        }
-                    }
-                    found_dot
-                } else {
-                    false
-                };
-
-                if is_dot_paren_start {
-                    // Keep .( and its content
-                    in_dot_paren = true;
-                    result.push(ch);
-                } else {
-                    // Regular comment, start skipping
-                    in_paren_comment = true;
-                }
+        if let Some(close) = in_string {
+            mask[i] = true;
+            if ch == close {
+                in_string = None;
             }
-            // If already in comment, just skip this ( character
-        } else if ch == ')' {
-            if in_paren_comment {
-                // End of regular comment
+            continue;
+        }
+
+        if in_paren_comment {
+            // Nested parentheses in stack comments like ( n -- fib(n) ) end at the first )
+            if ch == ')' {
                 in_paren_comment = false;
-                // Don't include the ) itself
-                i += 1;
-                continue;
-            } else if in_dot_paren {
-                // This is the closing ) of .(, keep it
-                in_dot_paren = false;
-                result.push(ch);
             }
-            // Else: standalone ) outside any context - skip it
-        } else if !in_paren_comment {
-            result.push(ch);
+            continue;
         }
 
-        i += 1;
+        let standalone = token_start == i && chars.get(i + 1).is_none_or(|next| next.is_whitespace());
+        if ch.is_whitespace() {
+            mask[i] = true;
+            token_start = i + 1;
+        } else if ch == '\\' && token_start == i {
+            // Backslash comment: the rest of the line
+            break;
+        } else if ch == '(' && standalone {
+            in_paren_comment = true;
+        } else if ch == '(' && token_start + 1 == i && chars[token_start] == '.' {
+            // .( is the print-string word, not a comment
+            mask[i] = true;
+            in_string = Some(')');
+        } else if !(ch == ')' && standalone) {
+            // A standalone ) outside any comment is skipped
+            mask[i] = true;
+            let at_token_end = chars.get(i + 1).is_none_or(|next| next.is_whitespace());
+            if ch == '"' && at_token_end {
+                let word: String = chars[token_start..=i].iter().collect();
+                if ["S\"", ".\"", "C\"", "ABORT\""].iter().any(|quote| word.eq_ignore_ascii_case(quote)) {
+                    in_string = Some('"');
+                }
+            }
+        }
     }
 
-    result
+    mask
 }
 
 pub fn load_file(
@@ -1134,7 +1555,7 @@ pub fn load_file(
     config: CompilerConfig,
     options: ExecutionOptions,
     included_files: &mut std::collections::HashSet<String>,
-) -> Result<(), String> {
+) -> Result<(), QuarterError> {
     // Check if file has already been included
     if included_files.contains(filename) {
         return Ok(());
@@ -1148,57 +1569,423 @@ pub fn load_file(
         ctx.dict.start_file_tracking();
     }
 
-    let contents = fs::read_to_string(filename).map_err(|e| format!("Cannot read file: {}", e))?;
+    let contents = fs::read_to_string(filename).map_err(|e| QuarterError::Io(format!("Cannot read file {}: {}", filename, e)))?;
 
-    // Process file as token stream to support multi-line definitions
-    let mut processed = String::new();
-
-    for line in contents.lines() {
-        let line = line.trim();
-
-        // Strip comments from this line
-        let line = strip_comments(line);
+    // Process file as token stream to support multi-line definitions,
+    // remembering where each token came from for error messages
+    let source = Source::new(filename, &contents);
+    interpret_source(&source, ctx, config, options, included_files)
+}
 
-        processed.push_str(&line);
-        processed.push(' ');
-    }
+/// Compile a colon definition body with STATE set
+/// Immediate words execute now and may append tokens via POSTPONE, LITERAL and COMPILE,;
+/// text between [ and ] is interpreted. Returns the tokens to hand to parse_tokens.
+/// `position` is kept at the token being compiled, so an error can be located
+fn compile_definition(
+    tokens: &[&str],
+    input: &SourceText,
+    position: &mut usize,
+    ctx: &mut RuntimeContext,
+) -> Result<Vec<String>, QuarterError> {
+    let state_addr = ctx.memory.state() as usize;
+    ctx.memory.store(state_addr, -1)?;
+    // Discard anything compiled outside a definition
+    ctx.dict.take_compiled_tokens();
 
-    // Now execute the entire file as one token stream
-    execute_line(
-        &processed,
-        ctx,
-        config,
-        options,
-        included_files,
-    )?;
+    let result = compile_definition_body(tokens, input, position, ctx);
 
-    Ok(())
+    ctx.memory.store(state_addr, 0)?;
+    result
 }
 
+/// Whether the token after `upper` in a definition is a name rather than a word to run
+fn takes_name(upper: &str) -> bool {
+    matches!(upper, "POSTPONE" | "[']" | "[CHAR]" | "CHAR" | "'" | "INLINE" | "TO" | "+TO" | "IS" | "ACTION-OF")
+}
 
-pub fn execute_line(
-    input: &str,
-    ctx: &mut RuntimeContext,
-    config: CompilerConfig,
+/// Find `word` in a compiled definition, passing over string contents, local names
+/// and names, so `S" CREATE X"` or `POSTPONE CREATE` isn't mistaken for it
+fn find_definition_word(tokens: &[&str], word: &str) -> Option<usize> {
+    let mut i = 0;
+    while i < tokens.len() {
+        let upper = tokens[i].to_uppercase();
+        i += 1;
+        match upper.as_str() {
+            ".\"" | "S\"" | "C\"" | "ABORT\"" | ".(" => {
+                let close = if upper == ".(" { ')' } else { '"' };
+                while i < tokens.len() {
+                    i += 1;
+                    if tokens[i - 1].ends_with(close) {
+                        break;
+                    }
+                }
+            }
+            "{:" => {
+                while i < tokens.len() {
+                    i += 1;
+                    if tokens[i - 1] == ":}" {
+                        break;
+                    }
+                }
+            }
+            upper if takes_name(upper) => i += 1,
+            upper if upper == word => return Some(i - 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn compile_definition_body(
+    tokens: &[&str],
+    input: &SourceText,
+    position: &mut usize,
+    ctx: &mut RuntimeContext,
+) -> Result<Vec<String>, QuarterError> {
+    let state_addr = ctx.memory.state() as usize;
+    let mut compiled: Vec<String> = Vec::new();
+    let mut i = 0;
+
+    while i < tokens.len() {
+        *position = i;
+        if ctx.memory.fetch(state_addr)? == 0 {
+            // Interpretation state: execute everything up to and including ]
+            let end = tokens[i..]
+                .iter()
+                .position(|t| *t == "]")
+                .map(|pos| i + pos + 1)
+                .ok_or_else(|| QuarterError::Compile("Missing ] in definition".to_string()))?;
+            let ast = parse_tokens(&tokens[i..end], ctx.dict, None)?;
+            ast.execute(ctx.stack, ctx.dict, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
+            i = end;
+            continue;
+        }
+
+        let token = tokens[i];
+        let token_upper = token.to_uppercase();
+        match token_upper.as_str() {
+            ".\"" | "S\"" | "C\"" | "ABORT\"" | ".(" => {
+                // String literals are copied through untouched
+                let close = if token_upper == ".(" { ')' } else { '"' };
+                compiled.push(token.to_string());
+                i += 1;
+                while i < tokens.len() {
+                    compiled.push(tokens[i].to_string());
+                    i += 1;
+                    if tokens[i - 1].ends_with(close) {
+                        break;
+                    }
+                }
+            }
+            "{:" => {
+                // Local names are copied through untouched
+                while i < tokens.len() {
+                    compiled.push(tokens[i].to_string());
+                    i += 1;
+                    if tokens[i - 1] == ":}" {
+                        break;
+                    }
+                }
+            }
+            upper if takes_name(upper) => {
+                // The next token is a name, not something to execute
+                let end = (i + 2).min(tokens.len());
+                compiled.extend(tokens[i..end].iter().map(|t| t.to_string()));
+                i = end;
+            }
+            _ if ctx.dict.is_immediate(&token_upper) => {
+                let key = ctx.dict.bind(&token_upper, None);
+                let expected = input.parse_past(token, ctx.memory);
+                ctx.dict.execute_word(&key, ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
+                compiled.extend(ctx.dict.take_compiled_tokens());
+                i += 1;
+
+                // An immediate word that parsed consumed the text it read
+                if let Some(resume) = expected.and_then(|expected| input.resume_point(expected, ctx.memory)) {
+                    while i < tokens.len() && input.source.text_offset(tokens[i]).is_some_and(|offset| offset < resume) {
+                        i += 1;
+                    }
+                }
+            }
+            _ => {
+                compiled.push(token.to_string());
+                i += 1;
+            }
+        }
+    }
+
+    Ok(compiled)
+}
+
+/// Define the words CREATE and DOES> made while the last word ran. A word DOES> changed
+/// pushes its data-field address, then EXECUTEs the code after DOES>, which every word
+/// made by the same defining word shares
+fn define_created_words(
+    ctx: &mut RuntimeContext,
+    config: CompilerConfig,
     options: ExecutionOptions,
     included_files: &mut std::collections::HashSet<String>,
-) -> Result<(), String> {
-    // Strip comments from input
-    let input = strip_comments(input);
-    let tokens: Vec<&str> = input.split_whitespace().collect();
+) -> Result<(), QuarterError> {
+    for created in ctx.memory.session().take_created() {
+        match created {
+            Created::Word { name, addr } => ctx.dict.add_created(name, addr),
+            Created::Does(xt) => {
+                let (name, addr) = ctx.dict.last_created()
+                    .ok_or_else(|| QuarterError::Compile("DOES> needs a word made by CREATE".to_string()))?;
+                // Executing the xt keeps the child running that code if the defining word is redefined
+                let child_ast = AstNode::Sequence(vec![AstNode::PushNumber(addr), AstNode::PushNumber(xt), AstNode::Execute]);
+
+                if !(options.use_forth_compiler
+                    && crate::try_forth_compile_word(name.clone(), &child_ast, ctx, config, included_files))
+                {
+                    ctx.dict.replace_compiled(name, child_ast);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Define the colon definition `word_name` from its compiled tokens. The code after
+/// DOES> becomes a word of its own, "<name> DOES>", which no name finds, and the
+/// definition ends by passing its xt to (DOES>)
+fn define_colon(
+    word_name: String,
+    word_tokens: &[&str],
+    ctx: &mut RuntimeContext,
+    config: CompilerConfig,
+    options: ExecutionOptions,
+    included_files: &mut std::collections::HashSet<String>,
+) -> Result<(), QuarterError> {
+    let (word_tokens, does_xt) = match find_definition_word(word_tokens, "DOES>") {
+        Some(pos) => {
+            let does_name = format!("{} DOES>", word_name);
+            define_colon(does_name.clone(), &word_tokens[pos + 1..], ctx, config, options, included_files)?;
+            let xt = ctx.dict.xt(&ctx.dict.definition_key(&does_name))
+                .ok_or(QuarterError::UndefinedWord(does_name))?;
+            (&word_tokens[..pos], Some(xt))
+        }
+        None => (word_tokens, None),
+    };
+
+    let mut ast = parse_tokens(word_tokens, ctx.dict, Some(&word_name))?;
+    if let Some(xt) = does_xt {
+        ast = AstNode::Sequence(vec![ast, AstNode::PushNumber(xt), AstNode::CallWord("(DOES>)".to_string())]);
+    }
+    // Validate that all words in the AST exist (allow forward reference for recursion)
+    ast.validate_with_name(ctx.dict, Some(&word_name))?;
+
+    // Track word definition in current file (detects same-file redefinitions)
+    if options.define_only {
+        ctx.dict.track_word_definition(&word_name);
+    }
+
+    // Check for redefinition (for JIT compilation decision)
+    let is_redefinition = ctx.dict.has_word(&word_name);
+
+    // Try JIT compilation if enabled
+    if options.use_forth_compiler && !is_redefinition {
+        // Try to JIT compile - if successful, try_forth_compile will add it to dict
+        // If it fails, we fall back to interpreted mode below
+        if crate::try_forth_compile_word(word_name.clone(), &ast, ctx, config, included_files) {
+            // JIT compilation succeeded, word is already in dictionary
+            return Ok(());
+        }
+    }
+
+    // Add word to dictionary in interpreted mode (fallback or default)
+    ctx.dict.add_compiled(word_name, ast);
+    Ok(())
+}
+
+pub fn execute_line(
+    input: &str,
+    ctx: &mut RuntimeContext,
+    config: CompilerConfig,
+    options: ExecutionOptions,
+    included_files: &mut std::collections::HashSet<String>,
+) -> Result<(), QuarterError> {
+    interpret_source(&Source::unnamed(input), ctx, config, options, included_files)
+}
+
+/// Execute the word `name` (a dictionary key, as `Dictionary::find` returns) as the
+/// outer interpreter would, so EVALUATE and CATCH in it run on `ctx`
+pub fn execute_word(
+    name: &str,
+    ctx: &mut RuntimeContext,
+    config: CompilerConfig,
+    included_files: &mut std::collections::HashSet<String>,
+) -> Result<(), QuarterError> {
+    let _reentered = Reentered::new(ctx, config, included_files);
+    ctx.dict.execute_word(name, ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
+    define_created_words(ctx, config, ExecutionOptions::new(false, false), included_files)
+}
+
+/// EVALUATE ( i*x c-addr u -- j*x ): interpret the string at `addr`, which SOURCE
+/// returns while it runs
+pub fn evaluate(
+    addr: usize,
+    len: usize,
+    ctx: &mut RuntimeContext,
+    config: CompilerConfig,
+    options: ExecutionOptions,
+    included_files: &mut std::collections::HashSet<String>,
+) -> Result<(), QuarterError> {
+    let mut bytes = Vec::with_capacity(len);
+    for offset in 0..len {
+        bytes.push(ctx.memory.fetch_byte(addr + offset)? as u8);
+    }
+    let text = String::from_utf8(bytes).map_err(|_| QuarterError::Other("Invalid UTF-8 in EVALUATE string".to_string()))?;
+
+    let source = Source::unnamed(&text);
+    interpret(&SourceText { source: &source, addr, id: -1 }, 0, ctx, config, options, included_files)
+}
+
+/// Interpret a file or a line of user input, copied to the source texts while it
+/// runs so SOURCE can point into it. A file is identified to SOURCE-ID by the copy's address
+fn interpret_source(
+    source: &Source,
+    ctx: &mut RuntimeContext,
+    config: CompilerConfig,
+    options: ExecutionOptions,
+    included_files: &mut std::collections::HashSet<String>,
+) -> Result<(), QuarterError> {
+    let text = source.contents().as_bytes();
+    let addr = ctx.memory.push_input_text(text)?;
+    let id = if source.file().is_some() { addr as i64 } else { 0 };
+    let result = interpret(&SourceText { source, addr, id }, 0, ctx, config, options, included_files);
+    ctx.memory.pop_input_text(addr, text.len());
+    result
+}
+
+/// Carry on in the line REFILL read past the end of user input, from where >IN left it.
+/// The line is copied out of the terminal input buffer, which the next REFILL overwrites
+fn interpret_terminal_line(
+    ctx: &mut RuntimeContext,
+    config: CompilerConfig,
+    options: ExecutionOptions,
+    included_files: &mut std::collections::HashSet<String>,
+) -> Result<(), QuarterError> {
+    let line = ctx.memory.input_source();
+    let mut bytes = Vec::with_capacity(line.len);
+    for offset in 0..line.len {
+        bytes.push(ctx.memory.fetch_byte(line.line + offset)? as u8);
+    }
+    let source = Source::unnamed(&String::from_utf8_lossy(&bytes));
+    let addr = ctx.memory.push_input_text(&bytes)?;
+    let result = interpret(&SourceText { source: &source, addr, id: 0 }, line.to_in, ctx, config, options, included_files);
+    ctx.memory.pop_input_text(addr, bytes.len());
+    result
+}
+
+/// Text being interpreted and where it lies in Forth memory, so words that parse can read it
+struct SourceText<'a> {
+    source: &'a Source,
+    // Address of the source's contents in Forth memory
+    addr: usize,
+    // What SOURCE-ID reports
+    id: i64,
+}
+
+impl SourceText<'_> {
+    /// The input source with the parse area starting at byte `offset` of the contents
+    fn at(&self, offset: usize) -> InputSource {
+        let (start, end) = self.source.line_bounds(offset);
+        InputSource {
+            id: self.id,
+            start: self.addr,
+            end: self.addr + self.source.contents().len(),
+            line: self.addr + start,
+            len: end - start,
+            to_in: offset - start,
+        }
+    }
+
+    /// Leave SOURCE and >IN just past `token`, as reading it does. Returns the input
+    /// source set, to tell afterwards whether the word it names parsed further
+    fn parse_past(&self, token: &str, memory: &mut Memory) -> Option<InputSource> {
+        let input = self.at(self.source.end_of(token)?);
+        memory.set_input_source(input);
+        Some(input)
+    }
+
+    /// Where in the comment-free text interpretation carries on, if a word moved
+    /// >IN or SOURCE away from `expected`
+    fn resume_point(&self, expected: InputSource, memory: &Memory) -> Option<usize> {
+        let input = memory.input_source();
+        if input == expected {
+            return None;
+        }
+        if input.start != self.addr {
+            // REFILL read on from the terminal, so nothing is left of this text
+            return Some(self.source.resume_at(usize::MAX));
+        }
+        let offset = (input.line + input.to_in.min(input.len)).saturating_sub(self.addr);
+        Some(self.source.resume_at(offset))
+    }
+}
+
+/// Run a source from byte `start` of its contents through the outer interpreter with
+/// SOURCE, >IN and SOURCE-ID describing it. When the source is a file, an error is
+/// reported at the token it happened at
+fn interpret(
+    input: &SourceText,
+    start: usize,
+    ctx: &mut RuntimeContext,
+    config: CompilerConfig,
+    options: ExecutionOptions,
+    included_files: &mut std::collections::HashSet<String>,
+) -> Result<(), QuarterError> {
+    let outer = ctx.memory.input_source();
+    ctx.memory.set_input_source(input.at(start));
+    // Words that re-enter the interpreter run on this context
+    let _reentered = Reentered::new(ctx, config, included_files);
+
+    let mut offset = input.source.resume_at(start);
+    let mut result = loop {
+        let tokens = input.source.tokens_from(offset);
+        let mut position = 0;
+        match interpret_tokens(&tokens, input, &mut position, ctx, config, options, included_files) {
+            Ok(Some(resume)) => offset = resume,
+            Ok(None) => break Ok(()),
+            Err(e) => break Err(input.source.locate(e, &tokens[position.min(tokens.len())..])),
+        }
+    };
+    if result.is_ok() && input.id == 0 && ctx.memory.input_source().start == TIB_ADDR {
+        result = interpret_terminal_line(ctx, config, options, included_files);
+    }
 
+    // The source this one was nested in carries on where it was
+    ctx.memory.set_input_source(outer);
+    result
+}
+
+/// The outer interpreter loop. `position` is kept at the start of the construct
+/// being run, so a failure can be traced back to it. Returns where to carry on in the
+/// comment-free text when a word parsed input past the token it was called by
+fn interpret_tokens(
+    tokens: &[&str],
+    input: &SourceText,
+    position: &mut usize,
+    ctx: &mut RuntimeContext,
+    config: CompilerConfig,
+    options: ExecutionOptions,
+    included_files: &mut std::collections::HashSet<String>,
+) -> Result<Option<usize>, QuarterError> {
     if tokens.is_empty() {
-        return Ok(());
+        return Ok(None);
     }
 
     // Process tokens sequentially, handling multiple definitions
     let mut i = 0;
     while i < tokens.len() {
+        *position = i;
         let token_upper = tokens[i].to_uppercase();
+        ctx.dict.note_here(ctx.memory.here());
         if token_upper == "INCLUDE" {
             // INCLUDE <filename>
             if i + 1 >= tokens.len() {
-                return Err("INCLUDE requires a filename".to_string());
+                return Err(QuarterError::Compile("INCLUDE requires a filename".to_string()));
             }
 
             let filename = tokens[i + 1];
@@ -1221,7 +2008,7 @@ pub fn execute_line(
 
             if let Some(end) = semicolon_pos {
                 if end - i < 2 {
-                    return Err("Invalid word definition".to_string());
+                    return Err(QuarterError::Compile("Invalid word definition".to_string()));
                 }
 
                 // Store word names in uppercase for case-insensitive lookup
@@ -1233,41 +2020,28 @@ pub fn execute_line(
                     continue;
                 }
 
-                let word_tokens = &tokens[i + 2..end];
-
-                let ast = parse_tokens(word_tokens, ctx.dict, Some(&word_name))?;
-                // Validate that all words in the AST exist (allow forward reference for recursion)
-                ast.validate_with_name(ctx.dict, Some(&word_name))?;
-
-                // Track word definition in current file (detects same-file redefinitions)
-                if options.define_only {
-                    ctx.dict.track_word_definition(&word_name);
-                }
-
-                // Check for redefinition (for JIT compilation decision)
-                let is_redefinition = ctx.dict.has_word(&word_name);
-
-                // Try JIT compilation if enabled
-                if options.use_forth_compiler && !is_redefinition {
-                    // Try to JIT compile - if successful, try_forth_compile will add it to dict
-                    // If it fails, we fall back to interpreted mode below
-                    if crate::try_forth_compile_word(word_name.clone(), &ast, ctx, config, included_files) {
-                        // JIT compilation succeeded, word is already in dictionary
-                        i = end + 1;
-                        continue;
+                // Run the body through the compile state so immediate words execute now
+                let mut at = 0;
+                let compiled_tokens = match compile_definition(&tokens[i + 2..end], input, &mut at, ctx) {
+                    Ok(compiled_tokens) => compiled_tokens,
+                    Err(e) => {
+                        *position = i + 2 + at;
+                        return Err(e);
                     }
-                }
+                };
+                // Words CREATE made while the body compiled, by immediate words or in [ ]
+                define_created_words(ctx, config, options, included_files)?;
 
-                // Add word to dictionary in interpreted mode (fallback or default)
-                ctx.dict.add_compiled(word_name, ast);
+                let word_tokens: Vec<&str> = compiled_tokens.iter().map(|t| t.as_str()).collect();
+                define_colon(word_name, &word_tokens, ctx, config, options, included_files)?;
                 i = end + 1;
             } else {
-                return Err("Missing ; in word definition".to_string());
+                return Err(QuarterError::Compile("Missing ; in word definition".to_string()));
             }
         } else if token_upper == "VARIABLE" {
             // VARIABLE <name>
             if i + 1 >= tokens.len() {
-                return Err("VARIABLE requires a name".to_string());
+                return Err(QuarterError::Compile("VARIABLE requires a name".to_string()));
             }
 
             let var_name = tokens[i + 1].to_uppercase();
@@ -1283,21 +2057,106 @@ pub fn execute_line(
         } else if token_upper == "CONSTANT" {
             // <value> CONSTANT <name>
             if i + 1 >= tokens.len() {
-                return Err("CONSTANT requires a name".to_string());
+                return Err(QuarterError::Compile("CONSTANT requires a name".to_string()));
             }
 
             // Pop value from stack
-            let value = ctx.stack.pop(ctx.memory).ok_or("Stack underflow for CONSTANT")?;
+            let value = ctx.stack.pop(ctx.memory).ok_or_else(|| QuarterError::underflow("CONSTANT"))?;
             let const_name = tokens[i + 1].to_uppercase();
 
             // Create a word that pushes the constant value
             let const_ast = AstNode::PushNumber(value);
             ctx.dict.add_compiled(const_name, const_ast);
             i += 2;
+        } else if token_upper == "2VARIABLE" {
+            // 2VARIABLE <name>
+            if i + 1 >= tokens.len() {
+                return Err(QuarterError::Compile("2VARIABLE requires a name".to_string()));
+            }
+
+            let var_name = tokens[i + 1].to_uppercase();
+            let addr = ctx.memory.here();
+
+            // Allocate 2 cells (16 bytes) for the cell pair
+            ctx.memory.allot(16)?;
+
+            let var_ast = AstNode::PushNumber(addr);
+            ctx.dict.add_compiled(var_name, var_ast);
+            i += 2;
+        } else if token_upper == "2CONSTANT" {
+            // <x1> <x2> 2CONSTANT <name>
+            if i + 1 >= tokens.len() {
+                return Err(QuarterError::Compile("2CONSTANT requires a name".to_string()));
+            }
+
+            let x2 = ctx.stack.pop(ctx.memory).ok_or_else(|| QuarterError::underflow("2CONSTANT"))?;
+            let x1 = ctx.stack.pop(ctx.memory).ok_or_else(|| QuarterError::underflow("2CONSTANT"))?;
+            let const_name = tokens[i + 1].to_uppercase();
+
+            ctx.dict.add_2constant(const_name, x1, x2);
+            i += 2;
+        } else if token_upper == "FVARIABLE" {
+            // FVARIABLE <name>
+            if i + 1 >= tokens.len() {
+                return Err(QuarterError::Compile("FVARIABLE requires a name".to_string()));
+            }
+
+            let var_name = tokens[i + 1].to_uppercase();
+            let addr = ctx.memory.here();
+
+            // Allocate 1 float (8 bytes)
+            ctx.memory.allot(8)?;
+
+            let var_ast = AstNode::PushNumber(addr);
+            ctx.dict.add_compiled(var_name, var_ast);
+            i += 2;
+        } else if token_upper == "FCONSTANT" {
+            // FCONSTANT <name> ( F: r -- )
+            if i + 1 >= tokens.len() {
+                return Err(QuarterError::Compile("FCONSTANT requires a name".to_string()));
+            }
+
+            let value = ctx.memory.fpop().ok_or_else(|| QuarterError::FloatStackUnderflow("FCONSTANT".to_string()))?;
+            let const_name = tokens[i + 1].to_uppercase();
+
+            let const_ast = AstNode::PushFloat(value);
+            ctx.dict.add_compiled(const_name, const_ast);
+            i += 2;
+        } else if token_upper == "VALUE" {
+            // <value> VALUE <name>
+            if i + 1 >= tokens.len() {
+                return Err(QuarterError::Compile("VALUE requires a name".to_string()));
+            }
+
+            let value = ctx.stack.pop(ctx.memory).ok_or_else(|| QuarterError::underflow("VALUE"))?;
+            let value_name = tokens[i + 1].to_uppercase();
+            let addr = ctx.memory.here();
+
+            // Allocate 1 cell (8 bytes) for the value, updated by TO and +TO
+            ctx.memory.allot(8)?;
+            ctx.memory.store(addr as usize, value)?;
+
+            ctx.dict.add_value(value_name, addr as usize);
+            i += 2;
+        } else if token_upper == "DEFER" {
+            // DEFER <name>
+            if i + 1 >= tokens.len() {
+                return Err(QuarterError::Compile("DEFER requires a name".to_string()));
+            }
+
+            let defer_name = tokens[i + 1].to_uppercase();
+            let addr = ctx.memory.here();
+
+            // Allocate 1 cell (8 bytes) for the xt, set by IS and DEFER!
+            ctx.memory.allot(8)?;
+            ctx.memory.store(addr as usize, 0)?;
+
+            ctx.dict.add_deferred(defer_name, addr as usize);
+            i += 2;
         } else if token_upper == "CREATE" {
             // CREATE <name>
             if i + 1 >= tokens.len() {
-                return Err("CREATE requires a name".to_string());
+                return Err(QuarterError::Compile("CREATE requires a name".to_string()));
             }
 
             let create_name = tokens[i + 1].to_uppercase();
@@ -1305,52 +2164,59 @@ pub fn execute_line(
 
             // Create a word that pushes the data address
             // User will typically follow with ALLOT to allocate space
-            let create_ast = AstNode::PushNumber(addr);
-            ctx.dict.add_compiled(create_name, create_ast);
+            ctx.dict.add_created(create_name, addr);
+            i += 2;
+        } else if token_upper == "MARKER" {
+            // MARKER <name>
+            if i + 1 >= tokens.len() {
+                return Err(QuarterError::Compile("MARKER requires a name".to_string()));
+            }
+
+            let marker_name = tokens[i + 1].to_uppercase();
+            ctx.dict.add_marker(marker_name, ctx.memory.here());
+            i += 2;
+        } else if let Some(marker) = ctx.dict.get_marker(&token_upper).cloned() {
+            // <marker>: forget it and everything defined after it, releasing their data space
+            ctx.dict.restore_marker(&marker);
+            ctx.memory.allot(marker.here - ctx.memory.here())?;
+            i += 1;
+        } else if token_upper == "FORGET" {
+            // FORGET <name>
+            if i + 1 >= tokens.len() {
+                return Err(QuarterError::Compile("FORGET requires a word name".to_string()));
+            }
+
+            let word_name = tokens[i + 1].to_uppercase();
+            if let Some(here) = ctx.dict.forget(&word_name)? {
+                ctx.memory.allot(here - ctx.memory.here())?;
+            }
             i += 2;
         } else if token_upper == "'" {
             // ' <name> (TICK)
-            // Parse next word name, store as counted string at HERE, push address
+            // Parse next word name, push its xt
             if i + 1 >= tokens.len() {
-                return Err("' requires a word name".to_string());
+                return Err(QuarterError::Compile("' requires a word name".to_string()));
             }
 
             let word_name = tokens[i + 1].to_uppercase();
 
             // Check that the word exists
-            if !ctx.dict.has_word(&word_name) {
-                return Err(format!("' cannot find word: {}", word_name));
-            }
-
-            // Store word name as counted string at HERE
-            // Format: [length byte][character bytes...]
-            let xt_addr = ctx.memory.here();
-            let name_bytes = word_name.as_bytes();
+            let Some(key) = ctx.dict.find(&word_name) else {
+                return Err(QuarterError::Compile(format!("' cannot find word: {}", word_name)));
+            };
 
-            // Store length byte
-            ctx.memory.store_byte(xt_addr as usize, name_bytes.len() as i64)?;
-
-            // Store character bytes
-            for (offset, &byte) in name_bytes.iter().enumerate() {
-                ctx.memory.store_byte(xt_addr as usize + 1 + offset, byte as i64)?;
-            }
-
-            // Advance HERE
-            ctx.memory.allot((1 + name_bytes.len()) as i64)?;
-
-            // Push xt (address of counted string) onto stack
-            ctx.stack.push(xt_addr, ctx.memory);
+            ctx.stack.push(ctx.dict.xt(&key).unwrap_or(0), ctx.memory);
             i += 2;
         } else if token_upper == "CHAR" {
             // CHAR <name>
             // Parse next word, push ASCII value of first character
             if i + 1 >= tokens.len() {
-                return Err("CHAR requires a word".to_string());
+                return Err(QuarterError::Compile("CHAR requires a word".to_string()));
             }
 
             let word = tokens[i + 1];
             if word.is_empty() {
-                return Err("CHAR requires a non-empty word".to_string());
+                return Err(QuarterError::Compile("CHAR requires a non-empty word".to_string()));
             }
 
             // Get first character and push its ASCII value
@@ -1367,10 +2233,10 @@ pub fn execute_line(
             // Takes filename from stack and loads the file
             let len = ctx.stack
                 .pop(ctx.memory)
-                .ok_or("Stack underflow for INCLUDED (length)")?;
+                .ok_or_else(|| QuarterError::underflow("INCLUDED"))?;
             let addr = ctx.stack
                 .pop(ctx.memory)
-                .ok_or("Stack underflow for INCLUDED (address)")?;
+                .ok_or_else(|| QuarterError::underflow("INCLUDED"))?;
 
             // Read the filename from memory
             let mut filename_bytes = Vec::new();
@@ -1380,7 +2246,7 @@ pub fn execute_line(
             }
 
             let filename =
-                String::from_utf8(filename_bytes).map_err(|_| "Invalid UTF-8 in filename")?;
+                String::from_utf8(filename_bytes).map_err(|_| QuarterError::Io("Invalid UTF-8 in filename".to_string()))?;
 
             // Load the file - INCLUDED always executes (not define-only)
             // because it's used for loading dependencies that need to run
@@ -1394,7 +2260,9 @@ pub fn execute_line(
             )?;
             i += 1;
         } else {
-            // Collect tokens until we hit : or INCLUDE or INCLUDED or VARIABLE or CONSTANT or CREATE or end
+            // Collect tokens until we hit : or INCLUDE or INCLUDED or VARIABLE or CONSTANT (or their
+            // 2-cell and float forms), VALUE, DEFER, CREATE, MARKER, FORGET, a marker, or end
+            let batch_start = i;
             let mut exec_tokens = Vec::new();
             while i < tokens.len() {
                 let check_upper = tokens[i].to_uppercase();
@@ -1403,18 +2271,56 @@ pub fn execute_line(
                     || check_upper == "INCLUDED"
                     || check_upper == "VARIABLE"
                     || check_upper == "CONSTANT"
+                    || check_upper == "2VARIABLE"
+                    || check_upper == "2CONSTANT"
+                    || check_upper == "FVARIABLE"
+                    || check_upper == "FCONSTANT"
+                    || check_upper == "VALUE"
+                    || check_upper == "DEFER"
                     || check_upper == "CREATE"
+                    || check_upper == "MARKER"
+                    || check_upper == "FORGET"
+                    || ctx.dict.get_marker(&check_upper).is_some()
                 {
                     break;
                 }
                 exec_tokens.push(tokens[i]);
                 i += 1;
+
+                // A name after ' or POSTPONE is never run, e.g. ' CREATE EXECUTE
+                if takes_name(&check_upper) && i < tokens.len() {
+                    exec_tokens.push(tokens[i]);
+                    i += 1;
+                }
+
+                // Names after a search-order change are looked up in the new order
+                if ctx.dict.changes_search_order(&check_upper) {
+                    break;
+                }
+
+                // A defining word parses the name after it, defining it before the rest runs
+                if ctx.dict.is_defining_word(&check_upper) {
+                    break;
+                }
+
+                // String contents are never defining words, e.g. S" stores a value" TEST:
+                // A string cut short by a \ comment still stops at the next definition
+                if check_upper == "S\"" || check_upper == ".\"" || check_upper == "C\"" {
+                    while i < tokens.len() && tokens[i] != ":" {
+                        exec_tokens.push(tokens[i]);
+                        i += 1;
+                        if tokens[i - 1].ends_with('"') {
+                            break;
+                        }
+                    }
+                }
             }
 
             if !exec_tokens.is_empty() {
                 // In define_only mode, still execute if:
                 // 1. The last collected token is INCLUDE or INCLUDED
                 // 2. The NEXT token (not yet collected) is INCLUDED
+                // 3. The last collected token is a defining word, which defines the name after it
                 // This handles both "INCLUDE file" and "S\" file\" INCLUDED" patterns
                 let next_token_is_included = i < tokens.len() &&
                     tokens[i].to_uppercase() == "INCLUDED";
@@ -1423,7 +2329,7 @@ pub fn execute_line(
                     next_token_is_included ||
                     exec_tokens.last().map(|t| {
                         let upper = t.to_uppercase();
-                        upper == "INCLUDED" || upper == "INCLUDE"
+                        upper == "INCLUDED" || upper == "INCLUDE" || ctx.dict.is_defining_word(&upper)
                     }).unwrap_or(false);
 
                 if should_execute {
@@ -1499,34 +2405,31 @@ pub fn execute_line(
                         idx += 1;
                     }
 
-                    if found_compile_only {
-                        return Err("Control flow words (IF/THEN/ELSE/BEGIN/UNTIL/WHILE/REPEAT/DO/?DO/LOOP/LEAVE/EXIT/UNLOOP) are compile-only".to_string());
+                    // Run node by node so a failure is traced to the token its node starts at.
+                    // Words before a compile-only word still run: one of them may parse it away
+                    let (nodes, starts) = parse_nodes(&exec_tokens[..idx], ctx.dict, None, &[])?;
+                    for (index, (node, &start)) in nodes.iter().zip(&starts).enumerate() {
+                        *position = batch_start + start;
+                        // Words the node calls parse on from after its last token
+                        let end = starts[index + 1..].iter().find(|&&next| next > start).copied().unwrap_or(idx);
+                        let expected = input.parse_past(exec_tokens[end - 1], ctx.memory);
+                        node.execute(ctx.stack, ctx.dict, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
+                        define_created_words(ctx, config, options, included_files)?;
+                        if let Some(resume) = expected.and_then(|expected| input.resume_point(expected, ctx.memory)) {
+                            return Ok(Some(resume));
+                        }
                     }
 
-                    let ast = parse_tokens(&exec_tokens, ctx.dict, None)?;
-                    ast.execute(ctx.stack, ctx.dict, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
+                    if found_compile_only {
+                        *position = batch_start + idx;
+                        return Err(QuarterError::Compile("Control flow words (IF/THEN/ELSE/BEGIN/UNTIL/WHILE/REPEAT/DO/?DO/LOOP/LEAVE/EXIT/UNLOOP) are compile-only".to_string()));
+                    }
                 }
             }
         }
     }
 
-    Ok(())
-}
-
-/// Helper function to process embedded stdlib file content
-/// Strips comments and joins lines, similar to load_file
-fn process_stdlib_content(content: &str) -> String {
-    let mut processed = String::new();
-
-    for line in content.lines() {
-        let line = line.trim();
-        // Strip comments from this line
-        let line = strip_comments(line);
-        processed.push_str(&line);
-        processed.push(' ');
-    }
-
-    processed
+    Ok(None)
 }
 
 /// Load standard library files embedded in the binary
@@ -1536,67 +2439,69 @@ pub fn load_stdlib(
     config: CompilerConfig,
     options: ExecutionOptions,
     included_files: &mut std::collections::HashSet<String>,
-) -> Result<(), String> {
+) -> Result<(), QuarterError> {
     // Load core definitions
-    let core_processed = process_stdlib_content(CORE_FTH);
+    let core = Source::new("stdlib/core.fth", CORE_FTH);
     let stdlib_options = ExecutionOptions::new(options.use_forth_compiler, false);
-    execute_line(
-        &core_processed,
-        ctx,
-        config,
-        stdlib_options,
-        included_files,
-    )?;
+    interpret_source(&core, ctx, config, stdlib_options, included_files)?;
 
     // TODO: Load test framework - currently has issues with DEPTH in loops
-    // let test_framework_processed = process_stdlib_content(TEST_FRAMEWORK_FTH);
-    // execute_line(&test_framework_processed, stack, dict, loop_stack, return_stack, memory, no_jit, dump_ir, verify_ir)?;
+    // let test_framework = Source::new("stdlib/test-framework.fth", TEST_FRAMEWORK_FTH);
+    // interpret_source(&test_framework, ctx, config, stdlib_options, included_files)?;
 
     // TODO: Load test suite - temporarily disabled to debug segfault
-    // let tests_processed = process_stdlib_content(TESTS_FTH);
-    // execute_line(&tests_processed, stack, dict, loop_stack, return_stack, memory, no_jit, dump_ir, verify_ir)?;
+    // let tests = Source::new("stdlib/tests.fth", TESTS_FTH);
+    // interpret_source(&tests, ctx, config, stdlib_options, included_files)?;
 
     Ok(())
 }
 
+/// Whether stdlib/compiler.fth has been loaded into this dictionary
+fn forth_compiler_loaded(dict: &Dictionary) -> bool {
+    dict.has_word(&Dictionary::compiler_word("COMPILE-WORD"))
+}
+
 /// Batch compile all Word::Compiled entries in the dictionary to JIT
 /// This creates one global LLVM module with all functions, then JITs them all at once
 pub fn batch_compile_all_words(
     ctx: &mut RuntimeContext,
     config: CompilerConfig,
     included_files: &mut std::collections::HashSet<String>,
-) -> Result<(), String> {
+) -> Result<(), QuarterError> {
     // IMPORTANT: Get list of words to compile BEFORE loading the compiler
     // Otherwise we'll try to compile the compiler's internal words too!
     let all_words = ctx.dict.get_all_words();
     let mut words_to_compile: Vec<(String, AstNode)> = Vec::new();
 
     for (name, word) in all_words {
-        if let crate::dictionary::Word::Compiled(ast) = word {
-            words_to_compile.push((name, ast.clone()));
-        }
+        if let crate::dictionary::Word::Compiled(ast) = word
+            && !ast.compiles_code() {
+                words_to_compile.push((name, ast.clone()));
+            }
     }
 
     // Load the Forth compiler if not already loaded (after capturing words to compile)
-    if !FORTH_COMPILER_LOADED.load(Ordering::Relaxed) {
+    if !forth_compiler_loaded(ctx.dict) {
         // Load compiler
         let compiler_options = ExecutionOptions::new(false, false);
         if let Err(e) = load_file("stdlib/compiler.fth", ctx, config, compiler_options, included_files) {
-            return Err(format!("Failed to load Forth compiler: {}", e));
+            return Err(QuarterError::Compile(format!("Failed to load Forth compiler: {}", e)));
         }
-        FORTH_COMPILER_LOADED.store(true, Ordering::Relaxed);
     }
 
+    // The compiler looks up native words in the dictionary being compiled
+    let _reentered = Reentered::new(ctx, config, included_files);
+
     if words_to_compile.is_empty() {
         return Ok(());
     }
 
     // Step 1: Initialize batch compiler
-    ctx.dict.execute_word("INIT-BATCH-COMPILER", ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
+    ctx.dict.execute_word(&Dictionary::compiler_word("INIT-BATCH-COMPILER"), ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
 
     // Set COMPILING-AOT? to 0 (false) for JIT mode
     ctx.stack.push(0, ctx.memory); // false
-    ctx.dict.execute_word("COMPILING-AOT?", ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
+    ctx.dict.execute_word(&Dictionary::compiler_word("COMPILING-AOT?"), ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
     ctx.dict.execute_word("!", ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
 
     if std::env::var("QUARTER_DEBUG").is_ok() {
@@ -1620,8 +2525,8 @@ pub fn batch_compile_all_words(
         if std::env::var("QUARTER_DEBUG").is_ok() {
             eprintln!("DEBUG (lib.rs): Declaring function: {}", name);
         }
-        ctx.dict.execute_word("DECLARE-FUNCTION", ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)
-            .map_err(|e| format!("Declaration failed for {}: {}", name, e))?;
+        ctx.dict.execute_word(&Dictionary::compiler_word("DECLARE-FUNCTION"), ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)
+            .map_err(|e| QuarterError::Compile(format!("Declaration failed for {}: {}", name, e)))?;
     }
     if std::env::var("QUARTER_DEBUG").is_ok() {
         eprintln!("DEBUG (lib.rs): All functions declared, starting compilation");
@@ -1633,7 +2538,7 @@ pub fn batch_compile_all_words(
             eprintln!("DEBUG (lib.rs): Compiling word: {}", name);
         }
         // Register AST node to get a handle
-        let ast_handle = crate::ast_forth::ast_register_node(ast.clone());
+        let ast_handle = crate::ast_forth::ast_register_node(&mut ctx.memory.session().ast, ast.clone());
 
         // Store word name in memory at HERE
         let here = ctx.memory.here() as usize;
@@ -1650,8 +2555,8 @@ pub fn batch_compile_all_words(
         ctx.stack.push(name_bytes.len() as i64, ctx.memory);
 
         // Call COMPILE-WORD (returns 0 in batch mode)
-        ctx.dict.execute_word("COMPILE-WORD", ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)
-            .map_err(|e| format!("Compilation failed for {}: {}", name, e))?;
+        ctx.dict.execute_word(&Dictionary::compiler_word("COMPILE-WORD"), ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)
+            .map_err(|e| QuarterError::Compile(format!("Compilation failed for {}: {}", name, e)))?;
         // Pop and discard the result (0 in batch mode)
         ctx.stack.pop(ctx.memory);
         if std::env::var("QUARTER_DEBUG").is_ok() {
@@ -1663,13 +2568,13 @@ pub fn batch_compile_all_words(
     if std::env::var("QUARTER_DEBUG").is_ok() {
         eprintln!("DEBUG (lib.rs): All words compiled, calling FINALIZE-BATCH");
     }
-    ctx.dict.execute_word("FINALIZE-BATCH", ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
+    ctx.dict.execute_word(&Dictionary::compiler_word("FINALIZE-BATCH"), ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
     if std::env::var("QUARTER_DEBUG").is_ok() {
         eprintln!("DEBUG (lib.rs): FINALIZE-BATCH completed");
     }
 
     // Stack now has JIT handle
-    let jit_handle = ctx.stack.peek(ctx.memory).ok_or("Failed to get JIT handle")?;
+    let jit_handle = ctx.stack.peek(ctx.memory).ok_or_else(|| QuarterError::Compile("Failed to get JIT handle".to_string()))?;
 
     // Step 5: Get function pointers and update dictionary
     if std::env::var("QUARTER_DEBUG").is_ok() {
@@ -1704,8 +2609,8 @@ pub fn batch_compile_all_words(
         ctx.dict.execute_word("LLVM-GET-FUNCTION", ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
 
         // Get function pointer (two 32-bit values: low, then high)
-        let high = ctx.stack.pop(ctx.memory).ok_or(format!("Failed to get function pointer high word for {}", name))?;
-        let low = ctx.stack.pop(ctx.memory).ok_or(format!("Failed to get function pointer low word for {}", name))?;
+        let high = ctx.stack.pop(ctx.memory).ok_or_else(|| QuarterError::Compile(format!("Failed to get function pointer high word for {}", name)))?;
+        let low = ctx.stack.pop(ctx.memory).ok_or_else(|| QuarterError::Compile(format!("Failed to get function pointer low word for {}", name)))?;
 
         // Combine into 64-bit pointer
         let fn_ptr = ((high as u64) << 32) | (low as u64 & 0xFFFFFFFF);
@@ -1732,43 +2637,57 @@ pub fn compile_to_object_file(
     opt_level: u8,
     config: CompilerConfig,
     included_files: &mut std::collections::HashSet<String>,
-) -> Result<(), String> {
+) -> Result<(), QuarterError> {
     // Get list of words to compile BEFORE loading the compiler
     let all_words = ctx.dict.get_all_words();
     let mut words_to_compile: Vec<(String, AstNode)> = Vec::new();
 
     for (name, word) in all_words {
-        if let crate::dictionary::Word::Compiled(ast) = word {
-            words_to_compile.push((name, ast.clone()));
-        }
+        if let crate::dictionary::Word::Compiled(ast) = word
+            && !ast.compiles_code() {
+                words_to_compile.push((name, ast.clone()));
+            }
     }
 
     if words_to_compile.is_empty() {
-        return Err("No words to compile".to_string());
+        return Err(QuarterError::Compile("No words to compile".to_string()));
+    }
+
+    // The data space loading the program left, before the compiler allots its own.
+    // Memory starts zeroed, so only the other cells need storing
+    let data_end = ctx.memory.here() as usize;
+    let mut data_image = Vec::new();
+    for addr in (USER_MEMORY_START..data_end).step_by(8) {
+        let value = ctx.memory.fetch(addr)?;
+        if value != 0 {
+            data_image.push((addr, value));
+        }
     }
 
     // Load the Forth compiler if not already loaded
-    if !FORTH_COMPILER_LOADED.load(Ordering::Relaxed) {
+    if !forth_compiler_loaded(ctx.dict) {
         let compiler_options = ExecutionOptions::new(false, false);
         if let Err(e) = load_file("stdlib/compiler.fth", ctx, config, compiler_options, included_files) {
-            return Err(format!("Failed to load Forth compiler: {}", e));
+            return Err(QuarterError::Compile(format!("Failed to load Forth compiler: {}", e)));
         }
-        FORTH_COMPILER_LOADED.store(true, Ordering::Relaxed);
     }
 
+    // The compiler looks up native words in the dictionary being compiled
+    let _reentered = Reentered::new(ctx, config, included_files);
+
     // Step 1: Initialize batch compiler (sets up CURRENT-MODULE variable)
-    ctx.dict.execute_word("INIT-BATCH-COMPILER", ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
+    ctx.dict.execute_word(&Dictionary::compiler_word("INIT-BATCH-COMPILER"), ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
 
     // Set COMPILING-AOT? to -1 (true) for AOT mode
     ctx.stack.push(-1, ctx.memory); // true
-    ctx.dict.execute_word("COMPILING-AOT?", ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
+    ctx.dict.execute_word(&Dictionary::compiler_word("COMPILING-AOT?"), ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
     ctx.dict.execute_word("!", ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
 
     // Step 2: Get module handle from CURRENT-MODULE variable
-    ctx.dict.execute_word("CURRENT-MODULE", ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
+    ctx.dict.execute_word(&Dictionary::compiler_word("CURRENT-MODULE"), ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
     ctx.dict.execute_word("@", ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
     let module_handle = ctx.stack.pop(ctx.memory)
-        .ok_or("Failed to get module handle from CURRENT-MODULE")?;
+        .ok_or_else(|| QuarterError::Compile("Failed to get module handle from CURRENT-MODULE".to_string()))?;
 
     // Step 3: Declare all functions (pass 1)
     for (name, _ast) in &words_to_compile {
@@ -1781,8 +2700,8 @@ pub fn compile_to_object_file(
         ctx.stack.push(here as i64, ctx.memory);
         ctx.stack.push(name_bytes.len() as i64, ctx.memory);
 
-        ctx.dict.execute_word("DECLARE-FUNCTION", ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)
-            .map_err(|e| format!("Declaration failed for {}: {}", name, e))?;
+        ctx.dict.execute_word(&Dictionary::compiler_word("DECLARE-FUNCTION"), ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)
+            .map_err(|e| QuarterError::Compile(format!("Declaration failed for {}: {}", name, e)))?;
     }
 
     // Step 4: Compile each word body (pass 2)
@@ -1791,7 +2710,7 @@ pub fn compile_to_object_file(
             eprintln!("DEBUG: Compiling word: {}", name);
         }
 
-        let ast_handle = crate::ast_forth::ast_register_node(ast.clone());
+        let ast_handle = crate::ast_forth::ast_register_node(&mut ctx.memory.session().ast, ast.clone());
 
         let here = ctx.memory.here() as usize;
         let name_bytes = name.as_bytes();
@@ -1803,17 +2722,49 @@ pub fn compile_to_object_file(
         ctx.stack.push(here as i64, ctx.memory);
         ctx.stack.push(name_bytes.len() as i64, ctx.memory);
 
-        ctx.dict.execute_word("COMPILE-WORD", ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)
-            .map_err(|e| format!("Compilation failed for {}: {}", name, e))?;
+        ctx.dict.execute_word(&Dictionary::compiler_word("COMPILE-WORD"), ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)
+            .map_err(|e| QuarterError::Compile(format!("Compilation failed for {}: {}", name, e)))?;
 
         // Pop and discard the result (0 in batch mode)
         ctx.stack.pop(ctx.memory);
     }
 
-    // Step 5: Initialize native target
+    // Step 5: Fill the xt table at startup, so EXECUTE calls compiled words directly
+    ctx.dict.execute_word(&Dictionary::compiler_word("BEGIN-XT-TABLE"), ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
+    for (name, _ast) in &words_to_compile {
+        let Some(xt) = ctx.dict.xt(name).filter(|&xt| xt < XT_TABLE_CELLS) else {
+            continue;
+        };
+
+        let here = ctx.memory.here() as usize;
+        let name_bytes = name.as_bytes();
+        for (i, &byte) in name_bytes.iter().enumerate() {
+            ctx.memory.store_byte(here + i, byte as i64)?;
+        }
+
+        ctx.stack.push(xt, ctx.memory);
+        ctx.stack.push(here as i64, ctx.memory);
+        ctx.stack.push(name_bytes.len() as i64, ctx.memory);
+
+        ctx.dict.execute_word(&Dictionary::compiler_word("COMPILE-XT-ENTRY"), ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)
+            .map_err(|e| QuarterError::Compile(format!("xt table entry failed for {}: {}", name, e)))?;
+    }
+    ctx.dict.execute_word(&Dictionary::compiler_word("END-INIT-FUNCTION"), ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
+
+    // Step 6: Restore data space at startup, so CREATE, VARIABLE and VALUE cells hold what
+    // loading stored there and HERE continues after them
+    ctx.dict.execute_word(&Dictionary::compiler_word("BEGIN-DATA-IMAGE"), ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
+    for (addr, value) in data_image.into_iter().chain([(DP_ADDR, data_end as i64)]) {
+        ctx.stack.push(value, ctx.memory);
+        ctx.stack.push(addr as i64, ctx.memory);
+        ctx.dict.execute_word(&Dictionary::compiler_word("COMPILE-DATA-CELL"), ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
+    }
+    ctx.dict.execute_word(&Dictionary::compiler_word("END-INIT-FUNCTION"), ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
+
+    // Step 7: Initialize native target
     ctx.dict.execute_word("LLVM-INITIALIZE-NATIVE-TARGET", ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory)?;
 
-    // Step 6: Write object file
+    // Step 8: Write object file
     // Stack: ( module-handle path-addr path-len opt-level -- )
     let here = ctx.memory.here() as usize;
     let path_bytes = output_path.as_bytes();
@@ -1841,8 +2792,13 @@ pub fn try_forth_compile_word(
     config: CompilerConfig,
     included_files: &mut std::collections::HashSet<String>,
 ) -> bool {
+    // Words that extend the compiler only run under the outer interpreter
+    if ast.compiles_code() {
+        return false;
+    }
+
     // Load the Forth compiler if not already loaded
-    if !FORTH_COMPILER_LOADED.load(Ordering::Relaxed) {
+    if !forth_compiler_loaded(ctx.dict) {
         let load_options = ExecutionOptions::new(false, false);
         // Load stdlib first
         if let Err(e) = load_file("stdlib/core.fth", ctx, config, load_options, included_files) {
@@ -1854,12 +2810,13 @@ pub fn try_forth_compile_word(
             eprintln!("Failed to load Forth compiler: {}", e);
             return false;
         }
-        FORTH_COMPILER_LOADED.store(true, Ordering::Relaxed);
     }
 
+    // The compiler looks up native words in the dictionary being compiled
+    let _reentered = Reentered::new(ctx, config, included_files);
+
     // Register the AST
-    use crate::ast_forth::ast_register_node;
-    let ast_handle = ast_register_node(ast.clone());
+    let ast_handle = crate::ast_forth::ast_register_node(&mut ctx.memory.session().ast, ast.clone());
 
     // Write word name to memory at address 302000
     let name_addr = 302000;
@@ -1878,7 +2835,7 @@ pub fn try_forth_compile_word(
     ctx.stack.push(name.len() as i64, ctx.memory);
 
     // Execute COMPILE-WORD
-    if let Err(e) = ctx.dict.execute_word("COMPILE-WORD", ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory) {
+    if let Err(e) = ctx.dict.execute_word(&Dictionary::compiler_word("COMPILE-WORD"), ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory) {
         eprintln!("Forth compiler error: {}", e);
         // Restore stack pointer on failure
         ctx.stack.set_sp(saved_sp);
@@ -64,6 +64,7 @@ fn register_quarter_symbols() -> usize {
         crate::words::quarter_c_store,
         crate::words::quarter_c_fetch,
         crate::words::quarter_base,
+        crate::words::quarter_state,
 
         // Bitwise operations
         crate::words::quarter_and,
@@ -104,10 +105,103 @@ fn register_quarter_symbols() -> usize {
         crate::words::quarter_allot,
         crate::words::quarter_comma,
 
+        // Execution token operations
+        crate::words::quarter_execute,
+        crate::words::quarter_defer_fetch,
+        crate::words::quarter_defer_store,
+        crate::words::quarter_catch,
+
         // String operations
         crate::words::quarter_compare,
         crate::words::quarter_minus_trailing,
         crate::words::quarter_search,
+
+        // Double-cell arithmetic
+        crate::words::quarter_um_star,
+        crate::words::quarter_m_star,
+        crate::words::quarter_um_slash_mod,
+        crate::words::quarter_sm_slash_rem,
+        crate::words::quarter_fm_slash_mod,
+        crate::words::quarter_d_plus,
+        crate::words::quarter_d_minus,
+        crate::words::quarter_dnegate,
+        crate::words::quarter_d_dot,
+        crate::words::quarter_2fetch,
+        crate::words::quarter_2store,
+
+        // Pictured numeric output
+        crate::words::quarter_less_number_sign,
+        crate::words::quarter_number_sign,
+        crate::words::quarter_number_sign_s,
+        crate::words::quarter_hold,
+        crate::words::quarter_holds,
+        crate::words::quarter_sign,
+        crate::words::quarter_number_sign_greater,
+
+        // Floating point
+        crate::words::quarter_f_plus,
+        crate::words::quarter_f_minus,
+        crate::words::quarter_f_star,
+        crate::words::quarter_f_slash,
+        crate::words::quarter_fnegate,
+        crate::words::quarter_fabs,
+        crate::words::quarter_fsqrt,
+        crate::words::quarter_fsin,
+        crate::words::quarter_fcos,
+        crate::words::quarter_floor,
+        crate::words::quarter_fround,
+        crate::words::quarter_fdup,
+        crate::words::quarter_fdrop,
+        crate::words::quarter_fswap,
+        crate::words::quarter_fover,
+        crate::words::quarter_fdepth,
+        crate::words::quarter_f_fetch,
+        crate::words::quarter_f_store,
+        crate::words::quarter_s_to_f,
+        crate::words::quarter_f_to_s,
+        crate::words::quarter_f_less,
+        crate::words::quarter_f_zero_less,
+        crate::words::quarter_f_zero_equal,
+        crate::words::quarter_f_dot,
+
+        // File access
+        crate::words::quarter_open_file,
+        crate::words::quarter_create_file,
+        crate::words::quarter_close_file,
+        crate::words::quarter_delete_file,
+        crate::words::quarter_read_file,
+        crate::words::quarter_read_line,
+        crate::words::quarter_write_file,
+        crate::words::quarter_write_line,
+        crate::words::quarter_file_position,
+        crate::words::quarter_file_size,
+        crate::words::quarter_reposition_file,
+
+        // Memory allocation
+        crate::words::quarter_allocate,
+        crate::words::quarter_free,
+        crate::words::quarter_resize,
+
+        // Exceptions
+        crate::words::quarter_throw,
+        crate::words::quarter_catch_compiled,
+
+        // Input source
+        crate::words::quarter_source,
+        crate::words::quarter_to_in,
+        crate::words::quarter_source_id,
+        crate::words::quarter_parse,
+        crate::words::quarter_parse_name,
+        crate::words::quarter_word,
+        crate::words::quarter_refill,
+        crate::words::quarter_save_input,
+        crate::words::quarter_restore_input,
+
+        // Environment queries
+        crate::words::quarter_environment_query,
+
+        // Native words
+        crate::words::quarter_call_native,
     );
     symbols[0] // Return something to prevent optimization
 }
@@ -117,13 +211,6 @@ use inkwell::types::BasicType;
 use inkwell::OptimizationLevel;
 use inkwell::AddressSpace;
 use std::collections::HashMap;
-use std::cell::RefCell;
-
-thread_local! {
-    /// Thread-local registry of LLVM objects accessible from Forth
-    /// Using thread_local because LLVM types are not Send/Sync
-    static LLVM_REGISTRY: RefCell<LLVMRegistry> = RefCell::new(LLVMRegistry::new());
-}
 
 /// Handle types for different LLVM objects
 pub type ContextHandle = i64;
@@ -135,6 +222,7 @@ pub type ValueHandle = i64;
 pub type EngineHandle = i64;
 
 /// Registry storing all LLVM objects with handles
+/// Each interpreter's session has its own; LLVM types are not Send, so neither is a session
 pub struct LLVMRegistry {
     next_id: i64,
 
@@ -149,8 +237,14 @@ pub struct LLVMRegistry {
     phis: HashMap<ValueHandle, PhiValue<'static>>,
 }
 
+impl Default for LLVMRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LLVMRegistry {
-    fn new() -> Self {
+    pub fn new() -> Self {
         LLVMRegistry {
             next_id: 1,
             contexts: HashMap::new(),
@@ -503,6 +597,37 @@ impl LLVMRegistry {
         Ok(handle)
     }
 
+    /// Build alloca for one i64 in the entry block of the function being built
+    /// Entry-block allocas are promoted to registers by LLVM's mem2reg pass
+    pub fn build_alloca(&mut self,
+                       builder_handle: BuilderHandle,
+                       ctx_handle: ContextHandle) -> Result<ValueHandle, String> {
+        let builder = self.builders.get(&builder_handle)
+            .ok_or_else(|| format!("Invalid builder handle: {}", builder_handle))?;
+
+        let context = self.contexts.get(&ctx_handle)
+            .ok_or_else(|| format!("Invalid context handle: {}", ctx_handle))?;
+
+        let entry = builder.get_insert_block()
+            .and_then(|block| block.get_parent())
+            .and_then(|function| function.get_first_basic_block())
+            .ok_or_else(|| "Builder is not positioned inside a function".to_string())?;
+
+        // Use a separate builder so the caller's insertion point is unchanged
+        let entry_builder = context.create_builder();
+        match entry.get_first_instruction() {
+            Some(instruction) => entry_builder.position_before(&instruction),
+            None => entry_builder.position_at_end(entry),
+        }
+
+        let slot = entry_builder.build_alloca(context.i64_type(), "local")
+            .map_err(|e| format!("Failed to build alloca: {}", e))?;
+
+        let handle = self.next_handle();
+        self.values.insert(handle, slot.into());
+        Ok(handle)
+    }
+
     /// Build integer add instruction
     pub fn build_add(&mut self,
                     builder_handle: BuilderHandle,
@@ -623,6 +748,48 @@ impl LLVMRegistry {
         Ok(handle)
     }
 
+    /// Build a double-precision arithmetic instruction on two i64 cells holding
+    /// IEEE-754 bits: op 0=fadd, 1=fsub, 2=fmul, 3=fdiv. The result is an i64 again
+    pub fn build_float_op(&mut self,
+                          builder_handle: BuilderHandle,
+                          lhs_handle: ValueHandle,
+                          rhs_handle: ValueHandle,
+                          op: i64) -> Result<ValueHandle, String> {
+        let builder = self.builders.get(&builder_handle)
+            .ok_or_else(|| format!("Invalid builder handle: {}", builder_handle))?;
+
+        let lhs = self.values.get(&lhs_handle)
+            .ok_or_else(|| format!("Invalid LHS handle: {}", lhs_handle))?
+            .into_int_value();
+
+        let rhs = self.values.get(&rhs_handle)
+            .ok_or_else(|| format!("Invalid RHS handle: {}", rhs_handle))?
+            .into_int_value();
+
+        let cell_type = lhs.get_type();
+        let f64_type = cell_type.get_context().f64_type();
+        let to_double = |value, name| builder.build_bit_cast(value, f64_type, name)
+            .map(|v| v.into_float_value())
+            .map_err(|e| format!("Failed to build bitcast: {}", e));
+        let lhs = to_double(lhs, "lhs_f")?;
+        let rhs = to_double(rhs, "rhs_f")?;
+
+        let result = match op {
+            0 => builder.build_float_add(lhs, rhs, "fadd"),
+            1 => builder.build_float_sub(lhs, rhs, "fsub"),
+            2 => builder.build_float_mul(lhs, rhs, "fmul"),
+            3 => builder.build_float_div(lhs, rhs, "fdiv"),
+            _ => return Err(format!("Unknown float op: {}", op)),
+        }.map_err(|e| format!("Failed to build float op: {}", e))?;
+
+        let result = builder.build_bit_cast(result, cell_type, "fbits")
+            .map_err(|e| format!("Failed to build bitcast: {}", e))?;
+
+        let handle = self.next_handle();
+        self.values.insert(handle, result);
+        Ok(handle)
+    }
+
     /// Build bitwise AND instruction
     pub fn build_and(&mut self,
                     builder_handle: BuilderHandle,
@@ -1004,6 +1171,54 @@ impl LLVMRegistry {
         Ok(())
     }
 
+    /// Build a call through a native entry point held as an integer (e.g. loaded
+    /// from the xt table), which has the signature of a compiled word
+    pub fn build_indirect_call(&mut self,
+                              builder_handle: BuilderHandle,
+                              ctx_handle: ContextHandle,
+                              addr_handle: ValueHandle,
+                              args: &[ValueHandle]) -> Result<(), String> {
+        let builder = self.builders.get(&builder_handle)
+            .ok_or_else(|| format!("Invalid builder handle: {}", builder_handle))?;
+
+        let context = self.contexts.get(&ctx_handle)
+            .ok_or_else(|| format!("Invalid context handle: {}", ctx_handle))?;
+
+        let addr = self.values.get(&addr_handle)
+            .ok_or_else(|| format!("Invalid address handle: {}", addr_handle))?
+            .into_int_value();
+
+        let mut arg_values = Vec::new();
+        for &arg_handle in args {
+            let val = self.values.get(&arg_handle)
+                .ok_or_else(|| format!("Invalid argument handle: {}", arg_handle))?;
+            arg_values.push((*val).into());
+        }
+
+        // void fn(u8* memory, usize* sp, usize* rp)
+        let ptr_type = context.ptr_type(AddressSpace::default());
+        let fn_type = context.void_type().fn_type(&[ptr_type.into(), ptr_type.into(), ptr_type.into()], false);
+
+        let fn_ptr = builder.build_int_to_ptr(addr, ptr_type, "entry")
+            .map_err(|e| format!("Failed to build inttoptr: {}", e))?;
+        builder.build_indirect_call(fn_type, fn_ptr, &arg_values, "call")
+            .map_err(|e| format!("Failed to build indirect call: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Get the address of a function as a pointer value
+    pub fn function_address(&mut self, fn_handle: FunctionHandle) -> Result<ValueHandle, String> {
+        let function = self.functions.get(&fn_handle)
+            .ok_or_else(|| format!("Invalid function handle: {}", fn_handle))?;
+
+        let ptr = function.as_global_value().as_pointer_value();
+
+        let handle = self.next_handle();
+        self.values.insert(handle, ptr.into());
+        Ok(handle)
+    }
+
     /// Build PHI node (for SSA merges in loops)
     pub fn build_phi(&mut self,
                     builder_handle: BuilderHandle,
@@ -1114,311 +1329,225 @@ impl LLVMRegistry {
 
 /// Create a new LLVM context
 /// Stack: ( -- ctx-handle )
-pub fn llvm_create_context() -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        Ok(registry.create_context())
-    })
+pub fn llvm_create_context(registry: &mut LLVMRegistry) -> Result<i64, String> {
+    Ok(registry.create_context())
 }
 
 /// Create a new module
 /// Stack: ( ctx-handle name-addr name-len -- module-handle )
-pub fn llvm_create_module(ctx_handle: i64, name: &str) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.create_module(ctx_handle, name)
-    })
+pub fn llvm_create_module(registry: &mut LLVMRegistry, ctx_handle: i64, name: &str) -> Result<i64, String> {
+    registry.create_module(ctx_handle, name)
 }
 
 /// Declare an external function in the module
 /// Stack: ( module-handle ctx-handle name-addr name-len -- )
-pub fn llvm_declare_external(module_handle: i64, ctx_handle: i64, name: &str) -> Result<(), String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.declare_external_function(module_handle, ctx_handle, name)
-    })
+pub fn llvm_declare_external(registry: &mut LLVMRegistry, module_handle: i64, ctx_handle: i64, name: &str) -> Result<(), String> {
+    registry.declare_external_function(module_handle, ctx_handle, name)
 }
 
 /// Create a new builder
 /// Stack: ( ctx-handle -- builder-handle )
-pub fn llvm_create_builder(ctx_handle: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.create_builder(ctx_handle)
-    })
+pub fn llvm_create_builder(registry: &mut LLVMRegistry, ctx_handle: i64) -> Result<i64, String> {
+    registry.create_builder(ctx_handle)
 }
 
 /// Create a new function
 /// Stack: ( module-handle ctx-handle name-addr name-len -- fn-handle )
-pub fn llvm_create_function(module_handle: i64, ctx_handle: i64, name: &str) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.create_function(module_handle, ctx_handle, name)
-    })
+pub fn llvm_create_function(registry: &mut LLVMRegistry, module_handle: i64, ctx_handle: i64, name: &str) -> Result<i64, String> {
+    registry.create_function(module_handle, ctx_handle, name)
 }
 
 /// Get existing function from module by name
 /// Stack: ( module-handle name-addr name-len -- fn-handle )
-pub fn llvm_get_function(module_handle: i64, name: &str) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.get_function(module_handle, name)
-    })
+pub fn llvm_get_function(registry: &mut LLVMRegistry, module_handle: i64, name: &str) -> Result<i64, String> {
+    registry.get_function(module_handle, name)
 }
 
 /// Create a basic block
 /// Stack: ( ctx-handle fn-handle name-addr name-len -- block-handle )
-pub fn llvm_create_block(ctx_handle: i64, fn_handle: i64, name: &str) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.create_block(ctx_handle, fn_handle, name)
-    })
+pub fn llvm_create_block(registry: &mut LLVMRegistry, ctx_handle: i64, fn_handle: i64, name: &str) -> Result<i64, String> {
+    registry.create_block(ctx_handle, fn_handle, name)
 }
 
 /// Position builder at end of block
 /// Stack: ( builder-handle block-handle -- )
-pub fn llvm_position_at_end(builder_handle: i64, block_handle: i64) -> Result<(), String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.position_at_end(builder_handle, block_handle)
-    })
+pub fn llvm_position_at_end(registry: &mut LLVMRegistry, builder_handle: i64, block_handle: i64) -> Result<(), String> {
+    registry.position_at_end(builder_handle, block_handle)
 }
 
 /// Build return void instruction
 /// Stack: ( builder-handle -- )
-pub fn llvm_build_ret_void(builder_handle: i64) -> Result<(), String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_ret_void(builder_handle)
-    })
+pub fn llvm_build_ret_void(registry: &mut LLVMRegistry, builder_handle: i64) -> Result<(), String> {
+    registry.build_ret_void(builder_handle)
 }
 
 /// Build return instruction with value
 /// Stack: ( builder-handle value-handle -- )
-pub fn llvm_build_ret(builder_handle: i64, value_handle: i64) -> Result<(), String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_ret(builder_handle, value_handle)
-    })
+pub fn llvm_build_ret(registry: &mut LLVMRegistry, builder_handle: i64, value_handle: i64) -> Result<(), String> {
+    registry.build_ret(builder_handle, value_handle)
 }
 
 /// Dump module IR to stdout
 /// Stack: ( module-handle -- )
-pub fn llvm_dump_module(module_handle: i64) -> Result<(), String> {
-    LLVM_REGISTRY.with(|cell| {
-        let registry = cell.borrow();
-        let ir = registry.dump_module_ir(module_handle)?;
-        println!("\n=== LLVM IR from Forth Compiler ===");
-        println!("{}", ir);
-        println!("====================================\n");
-        Ok(())
-    })
+pub fn llvm_dump_module(registry: &LLVMRegistry, module_handle: i64) -> Result<(), String> {
+    let ir = registry.dump_module_ir(module_handle)?;
+    println!("\n=== LLVM IR from Forth Compiler ===");
+    println!("{}", ir);
+    println!("====================================\n");
+    Ok(())
 }
 
 /// Create JIT execution engine
 /// Stack: ( module-handle -- engine-handle )
-pub fn llvm_create_jit_engine(module_handle: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.create_jit_engine(module_handle)
-    })
+pub fn llvm_create_jit_engine(registry: &mut LLVMRegistry, module_handle: i64) -> Result<i64, String> {
+    registry.create_jit_engine(module_handle)
 }
 
 /// Get JIT function pointer
 /// Stack: ( engine-handle name-addr name-len -- fn-ptr )
-pub fn llvm_get_jit_function(engine_handle: i64, name: &str) -> Result<usize, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let registry = cell.borrow();
-        registry.get_jit_function(engine_handle, name)
-    })
+pub fn llvm_get_jit_function(registry: &LLVMRegistry, engine_handle: i64, name: &str) -> Result<usize, String> {
+    registry.get_jit_function(engine_handle, name)
 }
 
 // Additional IR builder primitives
 
 /// Build constant integer
 /// Stack: ( ctx-handle value bit-width -- value-handle )
-pub fn llvm_build_const_int(ctx_handle: i64, value: i64, bit_width: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_const_int(ctx_handle, value, bit_width)
-    })
+pub fn llvm_build_const_int(registry: &mut LLVMRegistry, ctx_handle: i64, value: i64, bit_width: i64) -> Result<i64, String> {
+    registry.build_const_int(ctx_handle, value, bit_width)
 }
 
 /// Build load instruction
 /// Stack: ( builder-handle ctx-handle ptr-handle bit-width -- value-handle )
-pub fn llvm_build_load(builder_handle: i64, ctx_handle: i64, ptr_handle: i64, bit_width: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_load(builder_handle, ctx_handle, ptr_handle, bit_width)
-    })
+pub fn llvm_build_load(registry: &mut LLVMRegistry, builder_handle: i64, ctx_handle: i64, ptr_handle: i64, bit_width: i64) -> Result<i64, String> {
+    registry.build_load(builder_handle, ctx_handle, ptr_handle, bit_width)
 }
 
 /// Build store instruction
 /// Stack: ( builder-handle value-handle ptr-handle -- )
-pub fn llvm_build_store(builder_handle: i64, value_handle: i64, ptr_handle: i64) -> Result<(), String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_store(builder_handle, value_handle, ptr_handle)
-    })
+pub fn llvm_build_store(registry: &mut LLVMRegistry, builder_handle: i64, value_handle: i64, ptr_handle: i64) -> Result<(), String> {
+    registry.build_store(builder_handle, value_handle, ptr_handle)
 }
 
 /// Build GEP instruction
 /// Stack: ( builder-handle ctx-handle ptr-handle offset-handle -- result-handle )
-pub fn llvm_build_gep(builder_handle: i64, ctx_handle: i64, ptr_handle: i64, offset_handle: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_gep(builder_handle, ctx_handle, ptr_handle, offset_handle)
-    })
+pub fn llvm_build_gep(registry: &mut LLVMRegistry, builder_handle: i64, ctx_handle: i64, ptr_handle: i64, offset_handle: i64) -> Result<i64, String> {
+    registry.build_gep(builder_handle, ctx_handle, ptr_handle, offset_handle)
+}
+
+/// Build alloca instruction
+/// Stack: ( builder-handle ctx-handle -- ptr-handle )
+pub fn llvm_build_alloca(registry: &mut LLVMRegistry, builder_handle: i64, ctx_handle: i64) -> Result<i64, String> {
+    registry.build_alloca(builder_handle, ctx_handle)
 }
 
 /// Build add instruction
 /// Stack: ( builder-handle lhs-handle rhs-handle -- result-handle )
-pub fn llvm_build_add(builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_add(builder_handle, lhs_handle, rhs_handle)
-    })
+pub fn llvm_build_add(registry: &mut LLVMRegistry, builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
+    registry.build_add(builder_handle, lhs_handle, rhs_handle)
 }
 
 /// Build sub instruction
 /// Stack: ( builder-handle lhs-handle rhs-handle -- result-handle )
-pub fn llvm_build_sub(builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_sub(builder_handle, lhs_handle, rhs_handle)
-    })
+pub fn llvm_build_sub(registry: &mut LLVMRegistry, builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
+    registry.build_sub(builder_handle, lhs_handle, rhs_handle)
 }
 
 /// Build mul instruction
 /// Stack: ( builder-handle lhs-handle rhs-handle -- result-handle )
-pub fn llvm_build_mul(builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_mul(builder_handle, lhs_handle, rhs_handle)
-    })
+pub fn llvm_build_mul(registry: &mut LLVMRegistry, builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
+    registry.build_mul(builder_handle, lhs_handle, rhs_handle)
 }
 
 /// Build sdiv instruction
 /// Stack: ( builder-handle lhs-handle rhs-handle -- result-handle )
-pub fn llvm_build_sdiv(builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_sdiv(builder_handle, lhs_handle, rhs_handle)
-    })
+pub fn llvm_build_sdiv(registry: &mut LLVMRegistry, builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
+    registry.build_sdiv(builder_handle, lhs_handle, rhs_handle)
 }
 
 /// Build srem instruction
 /// Stack: ( builder-handle lhs-handle rhs-handle -- result-handle )
-pub fn llvm_build_srem(builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_srem(builder_handle, lhs_handle, rhs_handle)
-    })
+pub fn llvm_build_srem(registry: &mut LLVMRegistry, builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
+    registry.build_srem(builder_handle, lhs_handle, rhs_handle)
+}
+
+/// Build float arithmetic instruction
+/// Stack: ( builder-handle lhs-handle rhs-handle op -- result-handle )
+pub fn llvm_build_float_op(registry: &mut LLVMRegistry, builder_handle: i64, lhs_handle: i64, rhs_handle: i64, op: i64) -> Result<i64, String> {
+    registry.build_float_op(builder_handle, lhs_handle, rhs_handle, op)
 }
 
 /// Build and instruction
 /// Stack: ( builder-handle lhs-handle rhs-handle -- result-handle )
-pub fn llvm_build_and(builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_and(builder_handle, lhs_handle, rhs_handle)
-    })
+pub fn llvm_build_and(registry: &mut LLVMRegistry, builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
+    registry.build_and(builder_handle, lhs_handle, rhs_handle)
 }
 
 /// Build or instruction
 /// Stack: ( builder-handle lhs-handle rhs-handle -- result-handle )
-pub fn llvm_build_or(builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_or(builder_handle, lhs_handle, rhs_handle)
-    })
+pub fn llvm_build_or(registry: &mut LLVMRegistry, builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
+    registry.build_or(builder_handle, lhs_handle, rhs_handle)
 }
 
 /// Build xor instruction
 /// Stack: ( builder-handle lhs-handle rhs-handle -- result-handle )
-pub fn llvm_build_xor(builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_xor(builder_handle, lhs_handle, rhs_handle)
-    })
+pub fn llvm_build_xor(registry: &mut LLVMRegistry, builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
+    registry.build_xor(builder_handle, lhs_handle, rhs_handle)
 }
 
 /// Build shl (shift left) instruction
 /// Stack: ( builder-handle lhs-handle rhs-handle -- result-handle )
-pub fn llvm_build_shl(builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_shl(builder_handle, lhs_handle, rhs_handle)
-    })
+pub fn llvm_build_shl(registry: &mut LLVMRegistry, builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
+    registry.build_shl(builder_handle, lhs_handle, rhs_handle)
 }
 
 /// Build ashr (arithmetic shift right) instruction
 /// Stack: ( builder-handle lhs-handle rhs-handle -- result-handle )
-pub fn llvm_build_ashr(builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_ashr(builder_handle, lhs_handle, rhs_handle)
-    })
+pub fn llvm_build_ashr(registry: &mut LLVMRegistry, builder_handle: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
+    registry.build_ashr(builder_handle, lhs_handle, rhs_handle)
 }
 
 /// Build unconditional branch
 /// Stack: ( builder-handle block-handle -- )
-pub fn llvm_build_br(builder_handle: i64, block_handle: i64) -> Result<(), String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_br(builder_handle, block_handle)
-    })
+pub fn llvm_build_br(registry: &mut LLVMRegistry, builder_handle: i64, block_handle: i64) -> Result<(), String> {
+    registry.build_br(builder_handle, block_handle)
 }
 
 /// Build conditional branch
 /// Stack: ( builder-handle cond-handle then-block else-block -- )
-pub fn llvm_build_cond_br(builder_handle: i64, cond_handle: i64, then_block: i64, else_block: i64) -> Result<(), String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_cond_br(builder_handle, cond_handle, then_block, else_block)
-    })
+pub fn llvm_build_cond_br(registry: &mut LLVMRegistry, builder_handle: i64, cond_handle: i64, then_block: i64, else_block: i64) -> Result<(), String> {
+    registry.build_cond_br(builder_handle, cond_handle, then_block, else_block)
 }
 
 /// Build integer comparison
 /// Stack: ( builder-handle predicate lhs-handle rhs-handle -- result-handle )
 /// Predicates: 0=eq, 1=ne, 2=slt, 3=sle, 4=sgt, 5=sge
-pub fn llvm_build_icmp(builder_handle: i64, predicate: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_icmp(builder_handle, predicate, lhs_handle, rhs_handle)
-    })
+pub fn llvm_build_icmp(registry: &mut LLVMRegistry, builder_handle: i64, predicate: i64, lhs_handle: i64, rhs_handle: i64) -> Result<i64, String> {
+    registry.build_icmp(builder_handle, predicate, lhs_handle, rhs_handle)
 }
 
 /// Build sign-extend instruction (i1 -> i64 for Forth booleans)
 /// Stack: ( builder-handle ctx-handle value-handle -- result-handle )
-pub fn llvm_build_sext(builder_handle: i64, ctx_handle: i64, value_handle: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_sext(builder_handle, ctx_handle, value_handle)
-    })
+pub fn llvm_build_sext(registry: &mut LLVMRegistry, builder_handle: i64, ctx_handle: i64, value_handle: i64) -> Result<i64, String> {
+    registry.build_sext(builder_handle, ctx_handle, value_handle)
 }
 
 /// Build select instruction (picks one of two values based on condition)
 /// Stack: ( builder-handle cond-handle true-value false-value -- result-handle )
-pub fn llvm_build_select(builder_handle: i64, cond_handle: i64, true_handle: i64, false_handle: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_select(builder_handle, cond_handle, true_handle, false_handle)
-    })
+pub fn llvm_build_select(registry: &mut LLVMRegistry, builder_handle: i64, cond_handle: i64, true_handle: i64, false_handle: i64) -> Result<i64, String> {
+    registry.build_select(builder_handle, cond_handle, true_handle, false_handle)
 }
 
 /// Build truncate instruction (i64 -> i8 for byte operations)
 /// Stack: ( builder-handle ctx-handle value-handle bit-width -- result-handle )
-pub fn llvm_build_trunc(builder_handle: i64, ctx_handle: i64, value_handle: i64, bit_width: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_trunc(builder_handle, ctx_handle, value_handle, bit_width)
-    })
+pub fn llvm_build_trunc(registry: &mut LLVMRegistry, builder_handle: i64, ctx_handle: i64, value_handle: i64, bit_width: i64) -> Result<i64, String> {
+    registry.build_trunc(builder_handle, ctx_handle, value_handle, bit_width)
 }
 
 /// Build function call with up to 3 arguments (for now)
 /// Stack: ( builder-handle fn-handle arg1 arg2 arg3 nargs -- )
-pub fn llvm_build_call(builder_handle: i64, fn_handle: i64, arg1: i64, arg2: i64, arg3: i64, nargs: i64, is_tail_call: i64) -> Result<(), String> {
+#[allow(clippy::too_many_arguments)]
+pub fn llvm_build_call(registry: &mut LLVMRegistry, builder_handle: i64, fn_handle: i64, arg1: i64, arg2: i64, arg3: i64, nargs: i64, is_tail_call: i64) -> Result<(), String> {
     let args: Vec<i64> = match nargs {
         0 => vec![],
         1 => vec![arg1],
@@ -1427,46 +1556,43 @@ pub fn llvm_build_call(builder_handle: i64, fn_handle: i64, arg1: i64, arg2: i64
         _ => return Err(format!("Unsupported number of arguments: {}", nargs)),
     };
 
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_call(builder_handle, fn_handle, &args, is_tail_call != 0)
-    })
+    registry.build_call(builder_handle, fn_handle, &args, is_tail_call != 0)
+}
+
+/// Build a call through a native entry point held as an integer
+/// Stack: ( builder-handle ctx-handle addr-handle arg1 arg2 arg3 -- )
+pub fn llvm_build_indirect_call(registry: &mut LLVMRegistry, builder_handle: i64, ctx_handle: i64, addr_handle: i64, arg1: i64, arg2: i64, arg3: i64) -> Result<(), String> {
+    registry.build_indirect_call(builder_handle, ctx_handle, addr_handle, &[arg1, arg2, arg3])
+}
+
+/// Get the address of a function as a pointer value
+/// Stack: ( fn-handle -- value-handle )
+pub fn llvm_function_address(registry: &mut LLVMRegistry, fn_handle: i64) -> Result<i64, String> {
+    registry.function_address(fn_handle)
 }
 
 /// Get function parameter as value
 /// Stack: ( fn-handle index -- value-handle )
-pub fn llvm_get_param(fn_handle: i64, index: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.get_param(fn_handle, index as u32)
-    })
+pub fn llvm_get_param(registry: &mut LLVMRegistry, fn_handle: i64, index: i64) -> Result<i64, String> {
+    registry.get_param(fn_handle, index as u32)
 }
 
 /// Build PHI node (for SSA merges in loops)
 /// Stack: ( builder-handle ctx-handle name-addr name-len -- phi-handle )
-pub fn llvm_build_phi(builder_handle: i64, ctx_handle: i64, name: &str) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_phi(builder_handle, ctx_handle, name)
-    })
+pub fn llvm_build_phi(registry: &mut LLVMRegistry, builder_handle: i64, ctx_handle: i64, name: &str) -> Result<i64, String> {
+    registry.build_phi(builder_handle, ctx_handle, name)
 }
 
 /// Add incoming value/block pair to PHI node
 /// Stack: ( phi-handle value-handle block-handle -- )
-pub fn llvm_phi_add_incoming(phi_handle: i64, value_handle: i64, block_handle: i64) -> Result<(), String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.phi_add_incoming(phi_handle, value_handle, block_handle)
-    })
+pub fn llvm_phi_add_incoming(registry: &mut LLVMRegistry, phi_handle: i64, value_handle: i64, block_handle: i64) -> Result<(), String> {
+    registry.phi_add_incoming(phi_handle, value_handle, block_handle)
 }
 
 /// Get current insert block
 /// Stack: ( builder-handle -- block-handle )
-pub fn llvm_get_insert_block(builder_handle: i64) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.get_insert_block(builder_handle)
-    })
+pub fn llvm_get_insert_block(registry: &mut LLVMRegistry, builder_handle: i64) -> Result<i64, String> {
+    registry.get_insert_block(builder_handle)
 }
 
 /// Initialize native LLVM target for AOT compilation
@@ -1480,6 +1606,7 @@ pub fn llvm_initialize_native_target() -> Result<(), String> {
 /// Stack: ( module-handle path-addr path-len opt-level -- )
 /// opt-level: 0=None, 1=Less, 2=Default, 3=Aggressive
 pub fn llvm_write_object_file(
+    registry: &LLVMRegistry,
     module_handle: i64,
     path: &str,
     opt_level: i64,
@@ -1488,36 +1615,29 @@ pub fn llvm_write_object_file(
         return Err(format!("Invalid optimization level: {} (must be 0-3)", opt_level));
     }
 
-    LLVM_REGISTRY.with(|cell| {
-        let registry = cell.borrow();
-        registry.write_object_file(module_handle, path, opt_level as u8)
-    })
+    registry.write_object_file(module_handle, path, opt_level as u8)
 }
 
 /// Build ptrtoint instruction (pointer -> i64)
 /// Stack: ( builder-handle ctx-handle ptr-handle -- value-handle )
 pub fn llvm_build_ptrtoint(
+    registry: &mut LLVMRegistry,
     builder_handle: i64,
     ctx_handle: i64,
     ptr_handle: i64,
 ) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.build_ptrtoint(builder_handle, ctx_handle, ptr_handle)
-    })
+    registry.build_ptrtoint(builder_handle, ctx_handle, ptr_handle)
 }
 
 /// Create a global string constant in the module
 /// Stack: ( module-handle ctx-handle string-addr string-len name-addr name-len -- value-handle )
 /// Returns a pointer to the string data (i8*) that can be used in IR
 pub fn llvm_create_global_string(
+    registry: &mut LLVMRegistry,
     module_handle: i64,
     ctx_handle: i64,
     string_data: &[u8],
     name: &str,
 ) -> Result<i64, String> {
-    LLVM_REGISTRY.with(|cell| {
-        let mut registry = cell.borrow_mut();
-        registry.create_global_string(module_handle, ctx_handle, string_data, name)
-    })
+    registry.create_global_string(module_handle, ctx_handle, string_data, name)
 }
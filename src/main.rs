@@ -1,14 +1,9 @@
-use quarter::{Dictionary, LoopStack, Stack, load_file, load_stdlib, CompilerConfig, ExecutionOptions, RuntimeContext};
+use quarter::{Dictionary, LoopStack, Stack, load_file, load_stdlib, CompilerConfig, ExecutionMode, ExecutionOptions, RuntimeContext};
 use std::collections::HashSet;
-use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Version number from Cargo.toml
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
-/// Track whether the Forth compiler has been loaded
-#[allow(dead_code)]
-static FORTH_COMPILER_LOADED: AtomicBool = AtomicBool::new(false);
-
 /// Attempt to compile using the Forth self-hosting compiler
 /// Returns true if successful, false otherwise
 #[allow(dead_code)]
@@ -26,7 +21,7 @@ fn try_forth_compile(
 
     // Load the Forth compiler if not already loaded
     #[allow(unreachable_code)]
-    if !FORTH_COMPILER_LOADED.load(Ordering::Relaxed) {
+    if !ctx.dict.has_word(&Dictionary::compiler_word("COMPILE-WORD")) {
         // Stdlib is already loaded by main(), no need to reload it here
         // Load compiler
         let compiler_options = ExecutionOptions::new(false, false);
@@ -53,13 +48,11 @@ fn try_forth_compile(
             eprintln!("Warning: Failed to JIT-compile stdlib: {}", e);
             // Continue anyway with interpreted stdlib
         }
-
-        FORTH_COMPILER_LOADED.store(true, Ordering::Relaxed);
     }
 
     // Register the AST
     use quarter::ast_forth::ast_register_node;
-    let ast_handle = ast_register_node(ast.clone());
+    let ast_handle = ast_register_node(&mut ctx.memory.session().ast, ast.clone());
 
     // Write word name to memory at address 302000
     let name_addr = 302000;
@@ -76,7 +69,7 @@ fn try_forth_compile(
     ctx.stack.push(name.len() as i64, ctx.memory);
 
     // Execute COMPILE-WORD
-    if let Err(e) = ctx.dict.execute_word("COMPILE-WORD", ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory) {
+    if let Err(e) = ctx.dict.execute_word(&Dictionary::compiler_word("COMPILE-WORD"), ctx.stack, ctx.loop_stack, ctx.return_stack, ctx.memory) {
         eprintln!("Forth compiler error: {}", e);
         return false;
     }
@@ -105,6 +98,43 @@ fn try_forth_compile(
     false
 }
 
+/// Load a source file into a fresh system and compile its words to an object file
+fn compile_source_to_object(source_file: &str, object_file: &str, opt_level: u8) -> Result<(), String> {
+    let mut stack = quarter::Stack::new();
+    let mut dict = quarter::Dictionary::new();
+    let mut loop_stack = quarter::LoopStack::new();
+    let mut return_stack = quarter::ReturnStack::new();
+    let mut memory = quarter::Memory::new();
+    let mut included_files = std::collections::HashSet::new();
+    let config = quarter::CompilerConfig::new(false, false, false);
+
+    // Load stdlib
+    let load_options = quarter::ExecutionOptions::new(false, false);
+    let mut ctx = quarter::RuntimeContext::new(
+        &mut stack,
+        &mut dict,
+        &mut loop_stack,
+        &mut return_stack,
+        &mut memory
+    );
+
+    // [IF] selects code for the compiled program while the source is loaded
+    ctx.memory.set_execution_mode(ExecutionMode::Aot);
+
+    if let Err(e) = quarter::load_stdlib(&mut ctx, config, load_options, &mut included_files) {
+        return Err(format!("Failed to load stdlib: {}", e));
+    }
+
+    // Load source file
+    if let Err(e) = quarter::load_file(source_file, &mut ctx, config, load_options, &mut included_files) {
+        return Err(format!("Failed to load source file: {}", e));
+    }
+
+    // Compile to object file
+    quarter::compile_to_object_file(&mut ctx, object_file, opt_level, config, &mut included_files)
+        .map_err(|e| e.to_string())
+}
+
 /// Generate a main() wrapper C file that initializes runtime and calls Forth code
 fn generate_main_wrapper(main_word: &str, output_path: &str) -> Result<(), String> {
     let main_c_content = format!(r#"/**
@@ -120,6 +150,13 @@ fn generate_main_wrapper(main_word: &str, output_path: &str) -> Result<(), Strin
 extern void quarter_runtime_init(void);
 extern void quarter_runtime_cleanup(void);
 extern void quarter_runtime_get_state(uint8_t** memory, size_t** sp, size_t** rp);
+extern int64_t quarter_runtime_throw_code(void);
+
+// Stores the data space the program's definitions left when it was compiled
+extern void _quarter_init_data(uint8_t* memory, size_t* sp, size_t* rp);
+
+// Fills the xt table with the compiled words, for EXECUTE
+extern void _quarter_init_xts(uint8_t* memory, size_t* sp, size_t* rp);
 
 // Forward declaration of compiled Forth word
 extern void _fn_{}(uint8_t* memory, size_t* sp, size_t* rp);
@@ -133,10 +170,20 @@ int main(void) {{
     size_t* sp;
     size_t* rp;
     quarter_runtime_get_state(&memory, &sp, &rp);
+    _quarter_init_data(memory, sp, rp);
+    _quarter_init_xts(memory, sp, rp);
 
     // Call main Forth word
     _fn_{}(memory, sp, rp);
 
+    // A THROW nothing caught unwinds all the way out of the main word
+    int64_t code = quarter_runtime_throw_code();
+    if (code != 0) {{
+        fprintf(stderr, "Uncaught THROW %lld\n", (long long)code);
+        quarter_runtime_cleanup();
+        return 1;
+    }}
+
     // Cleanup
     quarter_runtime_cleanup();
 
@@ -224,6 +271,8 @@ fn compile_to_executable(
             "src/runtime.rs",
             "-o", &runtime_obj_str
         ])
+        // ENVIRONMENT? reports the version the runtime was built as
+        .env("CARGO_PKG_VERSION", VERSION)
         .output()
         .map_err(|e| format!("Failed to run rustc: {}", e));
 
@@ -249,57 +298,20 @@ fn compile_to_executable(
     let forth_obj_path = temp_dir.join("forth.o");
     let forth_obj_str = forth_obj_path.to_string_lossy();
 
-    // Initialize Quarter execution context
-    quarter::init_execution_context(
-        quarter::Stack::new(),
-        quarter::Dictionary::new(),
-        quarter::LoopStack::new(),
-        quarter::ReturnStack::new(),
-        quarter::Memory::new(),
-        std::collections::HashSet::new(),
-        quarter::CompilerConfig::new(false, false, false),
-    );
-
     // Load source file and compile to object
-    let compile_result = quarter::with_execution_context(|exec_ctx| {
-        // Load stdlib
-        let load_options = quarter::ExecutionOptions::new(false, false);
-        let mut ctx = quarter::RuntimeContext::new(
-            &mut exec_ctx.stack,
-            &mut exec_ctx.dict,
-            &mut exec_ctx.loop_stack,
-            &mut exec_ctx.return_stack,
-            &mut exec_ctx.memory
-        );
-
-        if let Err(e) = quarter::load_stdlib(&mut ctx, exec_ctx.config, load_options, &mut exec_ctx.included_files) {
-            return Err(format!("Failed to load stdlib: {}", e));
-        }
-
-        // Load source file
-        if let Err(e) = quarter::load_file(source_file, &mut ctx, exec_ctx.config, load_options, &mut exec_ctx.included_files) {
-            return Err(format!("Failed to load source file: {}", e));
-        }
-
-        // Compile to object file
-        quarter::compile_to_object_file(&mut ctx, &forth_obj_str, opt_level, exec_ctx.config, &mut exec_ctx.included_files)
-    });
+    let compile_result = compile_source_to_object(source_file, &forth_obj_str, opt_level);
 
     match compile_result {
-        Some(Ok(())) => {
+        Ok(()) => {
             if verbose {
                 println!("  Successfully compiled to {}", forth_obj_str);
             }
         }
-        Some(Err(e)) => {
+        Err(e) => {
             eprintln!("Failed to compile Forth source:");
             eprintln!("{}", e);
             std::process::exit(1);
         }
-        None => {
-            eprintln!("No execution context available");
-            std::process::exit(1);
-        }
     }
 
     // Step 3: Generate main wrapper
@@ -654,16 +666,9 @@ fn main() {
         println!("Forth Interpreter v{}", VERSION);
     }
 
-    // Initialize global execution context for EVALUATE, CATCH/THROW, and Forth REPL
-    quarter::init_execution_context(
-        stack,
-        dict,
-        loop_stack,
-        return_stack,
-        memory,
-        included_files,
-        config,
-    );
+    if jit_mode {
+        memory.set_execution_mode(ExecutionMode::Jit);
+    }
 
     // Check for file argument
     // Supported extensions: .qtr, .fth, .forth, .quarter
@@ -690,92 +695,77 @@ fn main() {
         println!("Loading {}", file);
 
         // Load file - in JIT mode, only load definitions without executing
-        let result = quarter::with_execution_context(|exec_ctx| {
+        let result = {
             let file_options = ExecutionOptions::new(false, jit_mode);
-            let mut ctx = RuntimeContext::new(&mut exec_ctx.stack, &mut exec_ctx.dict, &mut exec_ctx.loop_stack, &mut exec_ctx.return_stack, &mut exec_ctx.memory);
+            let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
             load_file(
                 &file,
                 &mut ctx,
-                exec_ctx.config,
+                config,
                 file_options,
-                &mut exec_ctx.included_files,
+                &mut included_files,
             )
-        });
+        };
 
         match result {
-            Some(Ok(_)) => {
+            Ok(_) => {
                 // If JIT mode, check for redefinitions first
                 if jit_mode {
-                    let has_redefinitions = quarter::with_execution_context(|ctx| {
-                        ctx.dict.has_redefinitions()
-                    }).unwrap_or(false);
-
-                    if has_redefinitions {
+                    if dict.has_redefinitions() {
                         // Redefinition detected - fall back to interpreted mode
                         eprintln!("Warning: Word redefinition detected in '{}'", file);
                         eprintln!("Falling back to interpreted mode for this file.");
 
                         // Clear the stack and remove file from included_files
-                        quarter::with_execution_context(|ctx| {
-                            while ctx.stack.pop(&mut ctx.memory).is_some() {}
-                            ctx.included_files.remove(&file);
-                            // Clear redefinition flag for next file
-                            ctx.dict.clear_redefinition_flag();
-                        });
+                        while stack.pop(&mut memory).is_some() {}
+                        included_files.remove(&file);
+                        // Clear redefinition flag for next file
+                        dict.clear_redefinition_flag();
+                        memory.set_execution_mode(ExecutionMode::Interpreted);
 
                         // Execute in interpreted mode
-                        let exec_result = quarter::with_execution_context(|exec_ctx| {
-                            let exec_options = ExecutionOptions::new(false, false);
-                            let mut ctx = RuntimeContext::new(&mut exec_ctx.stack, &mut exec_ctx.dict, &mut exec_ctx.loop_stack, &mut exec_ctx.return_stack, &mut exec_ctx.memory);
-                            load_file(
-                                &file,
-                                &mut ctx,
-                                exec_ctx.config,
-                                exec_options,
-                                &mut exec_ctx.included_files,
-                            )
-                        });
-
-                        if let Some(Err(e)) = exec_result {
+                        let exec_options = ExecutionOptions::new(false, false);
+                        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+                        if let Err(e) = load_file(
+                            &file,
+                            &mut ctx,
+                            config,
+                            exec_options,
+                            &mut included_files,
+                        ) {
                             eprintln!("Interpreted execution failed: {}", e);
                             std::process::exit(1);
                         }
                     } else {
                         // No redefinitions - proceed with JIT compilation
-                        let compile_result = quarter::with_execution_context(|exec_ctx| {
-                            let mut ctx = RuntimeContext::new(&mut exec_ctx.stack, &mut exec_ctx.dict, &mut exec_ctx.loop_stack, &mut exec_ctx.return_stack, &mut exec_ctx.memory);
+                        let compile_result = {
+                            let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
                             quarter::batch_compile_all_words(
                                 &mut ctx,
-                                exec_ctx.config,
-                                &mut exec_ctx.included_files,
+                                config,
+                                &mut included_files,
                             )
-                        });
+                        };
 
-                        if let Some(Err(e)) = compile_result {
+                        if let Err(e) = compile_result {
                             eprintln!("Batch compilation failed: {}", e);
                             std::process::exit(1);
                         }
 
                         // Clear the stack and remove file from included_files
-                        quarter::with_execution_context(|ctx| {
-                            while ctx.stack.pop(&mut ctx.memory).is_some() {}
-                            ctx.included_files.remove(&file);
-                        });
+                        while stack.pop(&mut memory).is_some() {}
+                        included_files.remove(&file);
 
                         // Now execute the file with JIT-compiled code
-                        let exec_result = quarter::with_execution_context(|exec_ctx| {
-                            let exec_options = ExecutionOptions::new(false, false);
-                            let mut ctx = RuntimeContext::new(&mut exec_ctx.stack, &mut exec_ctx.dict, &mut exec_ctx.loop_stack, &mut exec_ctx.return_stack, &mut exec_ctx.memory);
-                            load_file(
-                                &file,
-                                &mut ctx,
-                                exec_ctx.config,
-                                exec_options,
-                                &mut exec_ctx.included_files,
-                            )
-                        });
-
-                        if let Some(Err(e)) = exec_result {
+                        let exec_options = ExecutionOptions::new(false, false);
+                        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+                        if let Err(e) = load_file(
+                            &file,
+                            &mut ctx,
+                            config,
+                            exec_options,
+                            &mut included_files,
+                        ) {
                             eprintln!("JIT execution failed: {}", e);
                             std::process::exit(1);
                         }
@@ -783,70 +773,33 @@ fn main() {
                 }
                 return;
             }
-            Some(Err(e)) => {
-                eprintln!("Error loading {}: {}", file, e);
-                std::process::exit(1);
-            }
-            None => {
-                eprintln!("No execution context available");
+            Err(e) => {
+                // Errors from the file already say where they happened
+                eprintln!("{}", e);
                 std::process::exit(1);
             }
         }
     }
 
     // Load the Forth REPL
-    let result = quarter::with_execution_context(|exec_ctx| {
-        let repl_options = ExecutionOptions::new(false, false);
-        let mut ctx = RuntimeContext::new(&mut exec_ctx.stack, &mut exec_ctx.dict, &mut exec_ctx.loop_stack, &mut exec_ctx.return_stack, &mut exec_ctx.memory);
-        quarter::load_file(
-            "stdlib/repl.fth",
-            &mut ctx,
-            exec_ctx.config,
-            repl_options,
-            &mut exec_ctx.included_files,
-        )
-    });
-
-    match result {
-        Some(Ok(())) => {
-            // REPL loaded successfully
-        }
-        Some(Err(e)) => {
-            eprintln!("Error loading Forth REPL: {}", e);
-            std::process::exit(1);
-        }
-        None => {
-            eprintln!("No execution context available");
-            std::process::exit(1);
-        }
+    let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+    let repl_options = ExecutionOptions::new(false, false);
+    if let Err(e) = quarter::load_file(
+        "stdlib/repl.fth",
+        &mut ctx,
+        config,
+        repl_options,
+        &mut included_files,
+    ) {
+        eprintln!("Error loading Forth REPL: {}", e);
+        std::process::exit(1);
     }
 
     // Start the Forth REPL by executing QUARTER-REPL
     println!("Type CTRL-C or CTRL-D to exit");
 
-    let result = quarter::with_execution_context(|ctx| {
-        ctx.dict.execute_word(
-            "QUARTER-REPL",
-            &mut ctx.stack,
-            &mut ctx.loop_stack,
-            &mut ctx.return_stack,
-            &mut ctx.memory,
-        )
-    });
-
-    match result {
-        Some(Ok(())) => {
-            // REPL exited normally
-        }
-        Some(Err(e)) => {
-            eprintln!("REPL error: {}", e);
-            std::process::exit(1);
-        }
-        None => {
-            eprintln!("No execution context available");
-            std::process::exit(1);
-        }
+    if let Err(e) = quarter::execute_word("QUARTER-REPL", &mut ctx, config, &mut included_files) {
+        eprintln!("REPL error: {}", e);
+        std::process::exit(1);
     }
 }
-
-
@@ -0,0 +1,128 @@
+// Words written in Rust by the program embedding Quarter
+// A native word is a closure kept in the dictionary. The interpreter calls it with a
+// NativeContext; compiled code has no closure to call, so it pushes the word's xt and
+// calls quarter_call_native (runtime.rs), which hands the xt back to the dictionary
+
+use crate::{LoopStack, Memory, QuarterError, ReturnStack, Stack};
+
+/// The body of a native word, as `Dictionary::add_native` stores it
+pub type NativeFn = Box<dyn FnMut(&mut NativeContext) -> Result<(), QuarterError>>;
+
+/// What a native word can reach while it runs: the stacks and Forth memory
+pub struct NativeContext<'a> {
+    pub stack: &'a mut Stack,
+    pub loop_stack: &'a mut LoopStack,
+    pub return_stack: &'a mut ReturnStack,
+    pub memory: &'a mut Memory,
+}
+
+impl NativeContext<'_> {
+    /// Push a value onto the stack its type lives on
+    pub fn push<T: StackValue>(&mut self, value: T) -> Result<(), QuarterError> {
+        value.push_onto(self.stack, self.memory)
+    }
+
+    /// Pop a value of type `T`; an empty stack is a stack underflow
+    pub fn pop<T: StackValue>(&mut self) -> Result<T, QuarterError> {
+        T::pop_from(self.stack, self.memory)
+    }
+
+    /// Pop a string left as ( c-addr u )
+    pub fn pop_string(&mut self) -> Result<String, QuarterError> {
+        let len: usize = self.pop()?;
+        let addr: usize = self.pop()?;
+        let bytes = (addr..addr + len)
+            .map(|byte_addr| self.memory.fetch_byte(byte_addr).map(|byte| byte as u8))
+            .collect::<Result<Vec<u8>, QuarterError>>()?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Number of cells on the data stack
+    pub fn depth(&self) -> usize {
+        self.stack.depth()
+    }
+}
+
+/// A Rust value that can be moved to and from the Forth stacks
+/// Integers and flags use the data stack, `f64` the float stack
+pub trait StackValue: Sized {
+    fn push_onto(self, stack: &mut Stack, memory: &mut Memory) -> Result<(), QuarterError>;
+    fn pop_from(stack: &mut Stack, memory: &mut Memory) -> Result<Self, QuarterError>;
+}
+
+fn pop_cell(stack: &mut Stack, memory: &mut Memory) -> Result<i64, QuarterError> {
+    stack.pop(memory).ok_or_else(|| {
+        // The error is returned, so it must not also be left pending for THROW
+        memory.take_throw();
+        QuarterError::underflow("")
+    })
+}
+
+impl StackValue for i64 {
+    fn push_onto(self, stack: &mut Stack, memory: &mut Memory) -> Result<(), QuarterError> {
+        stack.push(self, memory);
+        Ok(())
+    }
+
+    fn pop_from(stack: &mut Stack, memory: &mut Memory) -> Result<Self, QuarterError> {
+        pop_cell(stack, memory)
+    }
+}
+
+impl StackValue for i32 {
+    fn push_onto(self, stack: &mut Stack, memory: &mut Memory) -> Result<(), QuarterError> {
+        (self as i64).push_onto(stack, memory)
+    }
+
+    fn pop_from(stack: &mut Stack, memory: &mut Memory) -> Result<Self, QuarterError> {
+        let cell = pop_cell(stack, memory)?;
+        i32::try_from(cell).map_err(|_| QuarterError::Other(format!("{} does not fit in an i32", cell)))
+    }
+}
+
+impl StackValue for usize {
+    fn push_onto(self, stack: &mut Stack, memory: &mut Memory) -> Result<(), QuarterError> {
+        (self as i64).push_onto(stack, memory)
+    }
+
+    fn pop_from(stack: &mut Stack, memory: &mut Memory) -> Result<Self, QuarterError> {
+        let cell = pop_cell(stack, memory)?;
+        usize::try_from(cell).map_err(|_| QuarterError::Other(format!("{} is not an unsigned number", cell)))
+    }
+}
+
+/// Flags: true is -1, and any non-zero cell pops as true
+impl StackValue for bool {
+    fn push_onto(self, stack: &mut Stack, memory: &mut Memory) -> Result<(), QuarterError> {
+        (if self { -1i64 } else { 0 }).push_onto(stack, memory)
+    }
+
+    fn pop_from(stack: &mut Stack, memory: &mut Memory) -> Result<Self, QuarterError> {
+        Ok(pop_cell(stack, memory)? != 0)
+    }
+}
+
+/// Characters are cells holding the code point, as CHAR and EMIT use them
+impl StackValue for char {
+    fn push_onto(self, stack: &mut Stack, memory: &mut Memory) -> Result<(), QuarterError> {
+        (self as i64).push_onto(stack, memory)
+    }
+
+    fn pop_from(stack: &mut Stack, memory: &mut Memory) -> Result<Self, QuarterError> {
+        let cell = pop_cell(stack, memory)?;
+        u32::try_from(cell)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| QuarterError::Other(format!("{} is not a character", cell)))
+    }
+}
+
+impl StackValue for f64 {
+    fn push_onto(self, _stack: &mut Stack, memory: &mut Memory) -> Result<(), QuarterError> {
+        memory.fpush(self)
+    }
+
+    fn pop_from(_stack: &mut Stack, memory: &mut Memory) -> Result<Self, QuarterError> {
+        memory.fpop().ok_or_else(|| QuarterError::FloatStackUnderflow(String::new()))
+    }
+}
@@ -0,0 +1,70 @@
+// Where Forth output goes
+// Every word that prints (., EMIT, TYPE, CR, ." and the rest, interpreted or compiled)
+// writes through its interpreter's session, which passes the bytes to the innermost
+// >STRING capture or, with none open, to the session's Output
+
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// A function output is passed to
+pub type OutputFn = Box<dyn FnMut(&[u8])>;
+
+/// Destination of an interpreter's output
+#[derive(Default)]
+pub enum Output {
+    /// The process's standard output
+    #[default]
+    Stdout,
+    /// Kept in memory until taken with `Interpreter::take_output`
+    Buffer(Vec<u8>),
+    /// Any writer: a file, a socket, a pipe
+    Writer(Box<dyn Write>),
+    /// A function called with each piece of output as it is written
+    Callback(OutputFn),
+}
+
+impl Output {
+    /// An empty in-memory buffer
+    pub fn buffer() -> Self {
+        Output::Buffer(Vec::new())
+    }
+
+    /// Output written to a file, created or truncated
+    pub fn file(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Output::Writer(Box::new(File::create(path)?)))
+    }
+
+    /// Output passed to `func`
+    pub fn callback(func: impl FnMut(&[u8]) + 'static) -> Self {
+        Output::Callback(Box::new(func))
+    }
+
+    /// Write `bytes`. Forth's output words have no way to report a failed write,
+    /// so an error from a writer is dropped
+    pub fn write(&mut self, bytes: &[u8]) {
+        match self {
+            Output::Stdout => {
+                let _ = io::stdout().write_all(bytes);
+            }
+            Output::Buffer(buffer) => buffer.extend_from_slice(bytes),
+            Output::Writer(writer) => {
+                let _ = writer.write_all(bytes);
+            }
+            Output::Callback(func) => func(bytes),
+        }
+    }
+
+    /// Flush stdout or the writer
+    pub fn flush(&mut self) {
+        match self {
+            Output::Stdout => {
+                let _ = io::stdout().flush();
+            }
+            Output::Writer(writer) => {
+                let _ = writer.flush();
+            }
+            Output::Buffer(_) | Output::Callback(_) => {}
+        }
+    }
+}
@@ -3,7 +3,39 @@
 //! NO dependencies on LLVM, inkwell, or the rest of Quarter.
 
 // Memory and stack constants
+const MEMORY_SIZE: usize = 8 * 1024 * 1024; // Forth memory: 8MB, byte-addressed
+const CELL_SIZE: usize = 8;
 const DATA_STACK_END: usize = 0x020000;  // Data stack: 0-128KB
+const RETURN_STACK_BASE: usize = 0x010000; // Data stack below, return stack above
+const RETURN_STACK_END: usize = 0x01FFF8;  // The dictionary pointer takes the last cell
+const BASE_ADDR: usize = 0x7FFFF8;       // Numeric radix for I/O
+const HLD_ADDR: usize = 0x7FFFE8;        // Next free byte (from the top) of the hold buffer
+const HOLD_BUFFER_END: usize = 0x7FFFE8; // Pictured numeric output fills downward from here
+const HOLD_BUFFER_SIZE: usize = 256;     // Room for 128 binary digits plus sign and HOLDs
+const FLOAT_STACK_BASE: usize = 0x7F0000; // Float stack: 8-byte doubles growing upward
+const FLOAT_STACK_SIZE: usize = 0x8000;
+const FSP_ADDR: usize = 0x7FFEE0;        // Depth of the float stack in bytes
+const HEAP_BASE: usize = 0x400000;       // ALLOCATE heap: blocks laid out upward from here
+const HEAP_END: usize = 0x750000;        // Source texts, name area and xt table above
+const XT_TABLE_ADDR: usize = 0x7E0000;   // Entry point of each compiled word, by xt (0 if none)
+const XT_TABLE_CELLS: i64 = 0x2000;
+const HEAP_TOP_ADDR: usize = 0x7FFED8;   // End of the last heap block (0 while the heap is empty)
+const THROW_ADDR: usize = 0x7FFED0;      // Pending THROW code (0 when nothing was thrown)
+const SOURCE_ADDR: usize = 0x7FFEC8;     // Address of the input buffer SOURCE returns
+const SOURCE_LEN_ADDR: usize = 0x7FFEC0; // Length of the input buffer
+const TO_IN_ADDR: usize = 0x7FFEB8;      // >IN: offset of the parse area in the input buffer
+const SOURCE_ID_ADDR: usize = 0x7FFEB0;  // SOURCE-ID: 0 user input, -1 EVALUATE, else a file
+const INPUT_START_ADDR: usize = 0x7FFEA8; // The whole text the input buffer is a line of
+const INPUT_END_ADDR: usize = 0x7FFEA0;
+const WORD_BUFFER: usize = 0x7F8000;     // Counted string WORD returns (count byte and up to 255 chars)
+const FILE_TABLE_ADDR: usize = 0x7F9100; // FILE* of each open file, by fileid - 1 (0 if the slot is free)
+const FILE_TABLE_CELLS: usize = 64;
+const EXECUTION_MODE_ADDR: usize = 0x7FFE90; // How the program runs: 0 interpreted, 1 JIT, 2 AOT
+const NATIVE_HANDLER_ADDR: usize = 0x7FFE88; // Function that runs a native word by xt (0 if none)
+const OUTPUT_HANDLER_ADDR: usize = 0x7FFE78; // Function output is written through (0 for the C library's stdout)
+const MODE_JIT: i64 = 1;
+const MODE_AOT: i64 = 2;
+const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Check if stack pointer is valid for reading N bytes
 #[inline]
@@ -71,8 +103,8 @@ macro_rules! unary_op {
 binary_op!(quarter_add, |a, b| a + b);
 binary_op!(quarter_sub, |a, b| a - b);
 binary_op!(quarter_mul, |a, b| a * b);
-binary_op!(quarter_div, |a, b| if b != 0 { a / b } else { 0 });
-binary_op!(quarter_mod, |a, b| if b != 0 { a % b } else { 0 });
+binary_op!(quarter_div, |a: i64, b| if b != 0 { a.wrapping_div(b) } else { 0 });
+binary_op!(quarter_mod, |a: i64, b| if b != 0 { a.wrapping_rem(b) } else { 0 });
 
 unary_op!(quarter_negate, |a: i64| -a);
 unary_op!(quarter_abs, |a: i64| a.abs());
@@ -308,10 +340,20 @@ pub unsafe extern "C" fn quarter_c_fetch(memory: *mut u8, sp: *mut usize, _rp: *
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn quarter_base(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
     unsafe {
-        const BASE_ADDR: i64 = 0x7FFFF8;
         let sp_val = *sp;
         let dest = memory.add(sp_val) as *mut i64;
-        dest.write_unaligned(BASE_ADDR);
+        dest.write_unaligned(BASE_ADDR as i64);
+        *sp = sp_val + 8;
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_state(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        const STATE_ADDR: i64 = 0x7FFFF0;
+        let sp_val = *sp;
+        let dest = memory.add(sp_val) as *mut i64;
+        dest.write_unaligned(STATE_ADDR);
         *sp = sp_val + 8;
     }
 }
@@ -376,6 +418,26 @@ pub unsafe extern "C" fn quarter_r_fetch(memory: *mut u8, sp: *mut usize, rp: *m
 // I/O OPERATIONS
 // ============================================================================
 
+/// Write output through the handler in OUTPUT_HANDLER_ADDR, which the interpreter sets
+/// so compiled code prints where interpreted code does; a standalone executable has
+/// none and writes to the C library's stdout
+unsafe fn write_output(memory: *mut u8, bytes: &[u8]) {
+    unsafe extern "C" {
+        fn putchar(c: i32) -> i32;
+    }
+    unsafe {
+        let handler = (memory.add(OUTPUT_HANDLER_ADDR) as *const usize).read_unaligned();
+        if handler != 0 {
+            let handler: unsafe extern "C" fn(*mut u8, *const u8, usize) = std::mem::transmute(handler);
+            handler(memory, bytes.as_ptr(), bytes.len());
+            return;
+        }
+        for &byte in bytes {
+            putchar(byte as i32);
+        }
+    }
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn quarter_dot(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
     unsafe {
@@ -385,14 +447,9 @@ pub unsafe extern "C" fn quarter_dot(memory: *mut u8, sp: *mut usize, _rp: *mut
         }
         let addr = memory.add(sp_val - 8) as *const i64;
         let val = addr.read_unaligned();
-        
-        // Simple integer printing - write to stdout
-        unsafe extern "C" {
-            fn printf(fmt: *const u8, ...) -> i32;
-        }
-        let fmt = b"%lld \0".as_ptr();
-        printf(fmt, val);
-        
+
+        write_output(memory, format!("{} ", val).as_bytes());
+
         *sp = sp_val - 8;
     }
 }
@@ -407,22 +464,16 @@ pub unsafe extern "C" fn quarter_emit(memory: *mut u8, sp: *mut usize, _rp: *mut
         let addr = memory.add(sp_val - 8) as *const i64;
         let val = addr.read_unaligned();
 
-        unsafe extern "C" {
-            fn putchar(c: i32) -> i32;
-        }
-        putchar(val as i32);
-        
+        write_output(memory, &[val as u8]);
+
         *sp = sp_val - 8;
     }
 }
 
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn quarter_cr(_memory: *mut u8, _sp: *mut usize, _rp: *mut usize) {
-    unsafe extern "C" {
-        fn putchar(c: i32) -> i32;
-    }
+pub unsafe extern "C" fn quarter_cr(memory: *mut u8, _sp: *mut usize, _rp: *mut usize) {
     unsafe {
-        putchar(10); // newline
+        write_output(memory, b"\n");
     }
 }
 
@@ -430,9 +481,6 @@ pub unsafe extern "C" fn quarter_cr(_memory: *mut u8, _sp: *mut usize, _rp: *mut
 /// Print u characters from address c-addr
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn quarter_type(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
-    unsafe extern "C" {
-        fn putchar(c: i32) -> i32;
-    }
     unsafe {
         let sp_val = *sp;
         if !check_sp_read(sp_val, 16) {
@@ -449,28 +497,16 @@ pub unsafe extern "C" fn quarter_type(memory: *mut u8, sp: *mut usize, _rp: *mut
         }
 
         let len = len as usize;
-        // Print each character from memory
         // Handle both memory offsets (<8MB) and absolute pointers (>=8MB for global strings)
         if addr < 8 * 1024 * 1024 {
-            // Memory offset: read from memory buffer (JIT mode)
-            for i in 0..len {
-                if addr + i < 8 * 1024 * 1024 {
-                    let byte_ptr = memory.add(addr + i);
-                    let byte = *byte_ptr;
-                    if let Some(ch) = char::from_u32(byte as u32) {
-                        putchar(ch as i32);
-                    }
-                }
-            }
+            // Memory offset: read from memory buffer (JIT mode), stopping at its end
+            let len = len.min(8 * 1024 * 1024 - addr);
+            let text = std::slice::from_raw_parts(memory.add(addr), len).to_vec();
+            write_output(memory, &text);
         } else {
             // Absolute pointer: direct access (AOT mode with global strings)
-            let string_ptr = addr as *const u8;
-            for i in 0..len {
-                let byte = *string_ptr.add(i);
-                if let Some(ch) = char::from_u32(byte as u32) {
-                    putchar(ch as i32);
-                }
-            }
+            let text = std::slice::from_raw_parts(addr as *const u8, len);
+            write_output(memory, text);
         }
         *sp = sp_val - 16;
     }
@@ -655,37 +691,1689 @@ pub unsafe extern "C" fn quarter_search(memory: *mut u8, sp: *mut usize, _rp: *m
 }
 
 // ============================================================================
-// RUNTIME INITIALIZATION
+// DOUBLE-CELL ARITHMETIC
+// A double occupies two cells ( lo hi ) with the high cell on top
+// The divisions throw -10 for a zero divisor and -11 for a quotient a cell can't hold
 // ============================================================================
 
-unsafe extern "C" {
-    fn calloc(nmemb: usize, size: usize) -> *mut u8;
-    fn free(ptr: *mut u8);
+/// Read the double whose low cell is at `addr`
+#[inline]
+unsafe fn read_double(memory: *mut u8, addr: usize) -> i128 {
+    unsafe {
+        let lo = (memory.add(addr) as *const i64).read_unaligned() as u64;
+        let hi = (memory.add(addr + 8) as *const i64).read_unaligned();
+        ((hi as i128) << 64) | lo as i128
+    }
 }
 
-static mut RUNTIME_MEMORY: *mut u8 = 0 as *mut u8;
-static mut RUNTIME_SP: usize = 0;
-static mut RUNTIME_RP: usize = 0x010000;  // Return stack starts at 64KB
+/// Write `value` as a double with its low cell at `addr`
+#[inline]
+unsafe fn write_double(memory: *mut u8, addr: usize, value: i128) {
+    unsafe {
+        (memory.add(addr) as *mut i64).write_unaligned(value as i64);
+        (memory.add(addr + 8) as *mut i64).write_unaligned((value >> 64) as i64);
+    }
+}
+
+/// Record `code` as the pending throw code, unless one is pending already
+#[inline]
+unsafe fn raise(memory: *mut u8, code: i64) {
+    unsafe {
+        let pending = memory.add(THROW_ADDR) as *mut i64;
+        if pending.read_unaligned() == 0 {
+            pending.write_unaligned(code);
+        }
+    }
+}
 
+/// UM* ( u1 u2 -- ud )
+/// Unsigned multiply with a double-cell product
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn quarter_runtime_init() {
+pub unsafe extern "C" fn quarter_um_star(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
     unsafe {
-        // Allocate 8MB of zeroed memory using calloc
-        RUNTIME_MEMORY = calloc(8 * 1024 * 1024, 1);
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 16) {
+            return;
+        }
+        let u1 = (memory.add(sp_val - 16) as *const i64).read_unaligned() as u64;
+        let u2 = (memory.add(sp_val - 8) as *const i64).read_unaligned() as u64;
+        write_double(memory, sp_val - 16, (u1 as u128 * u2 as u128) as i128);
+    }
+}
 
-        // Initialize stack pointers
-        RUNTIME_SP = 0;  // Data stack starts at 0
-        RUNTIME_RP = 0x010000;  // Return stack starts at 64KB
+/// M* ( n1 n2 -- d )
+/// Signed multiply with a double-cell product
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_m_star(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 16) {
+            return;
+        }
+        let n1 = (memory.add(sp_val - 16) as *const i64).read_unaligned();
+        let n2 = (memory.add(sp_val - 8) as *const i64).read_unaligned();
+        write_double(memory, sp_val - 16, n1 as i128 * n2 as i128);
     }
 }
 
+/// UM/MOD ( ud u1 -- u2 u3 )
+/// Unsigned divide of a double by a single, giving remainder u2 and quotient u3
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn quarter_runtime_cleanup() {
+pub unsafe extern "C" fn quarter_um_slash_mod(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
     unsafe {
-        if !RUNTIME_MEMORY.is_null() {
-            free(RUNTIME_MEMORY);
-            RUNTIME_MEMORY = 0 as *mut u8;
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 24) {
+            return;
+        }
+        let divisor = (memory.add(sp_val - 8) as *const i64).read_unaligned() as u64 as u128;
+        if divisor == 0 {
+            raise(memory, -10);
+            return;
+        }
+        let ud = read_double(memory, sp_val - 24) as u128;
+        let Ok(quot) = u64::try_from(ud / divisor) else {
+            raise(memory, -11);
+            return;
+        };
+        (memory.add(sp_val - 24) as *mut i64).write_unaligned((ud % divisor) as i64);
+        (memory.add(sp_val - 16) as *mut i64).write_unaligned(quot as i64);
+        *sp = sp_val - 8;
+    }
+}
+
+/// SM/REM ( d n1 -- n2 n3 )
+/// Symmetric (truncating) divide of a double by a single, giving remainder n2 and quotient n3
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_sm_slash_rem(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 24) {
+            return;
+        }
+        let divisor = (memory.add(sp_val - 8) as *const i64).read_unaligned() as i128;
+        if divisor == 0 {
+            raise(memory, -10);
+            return;
+        }
+        let d = read_double(memory, sp_val - 24);
+        let (Some(quot), Some(rem)) = (d.checked_div(divisor), d.checked_rem(divisor)) else {
+            raise(memory, -11);
+            return;
+        };
+        let Ok(quot) = i64::try_from(quot) else {
+            raise(memory, -11);
+            return;
+        };
+        (memory.add(sp_val - 24) as *mut i64).write_unaligned(rem as i64);
+        (memory.add(sp_val - 16) as *mut i64).write_unaligned(quot);
+        *sp = sp_val - 8;
+    }
+}
+
+/// FM/MOD ( d n1 -- n2 n3 )
+/// Floored divide of a double by a single, giving remainder n2 and quotient n3
+/// The remainder takes the sign of the divisor
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_fm_slash_mod(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 24) {
+            return;
+        }
+        let divisor = (memory.add(sp_val - 8) as *const i64).read_unaligned() as i128;
+        if divisor == 0 {
+            raise(memory, -10);
+            return;
+        }
+        let d = read_double(memory, sp_val - 24);
+        let (Some(mut quot), Some(mut rem)) = (d.checked_div(divisor), d.checked_rem(divisor)) else {
+            raise(memory, -11);
+            return;
+        };
+        if rem != 0 && (rem < 0) != (divisor < 0) {
+            quot -= 1;
+            rem += divisor;
+        }
+        let Ok(quot) = i64::try_from(quot) else {
+            raise(memory, -11);
+            return;
+        };
+        (memory.add(sp_val - 24) as *mut i64).write_unaligned(rem as i64);
+        (memory.add(sp_val - 16) as *mut i64).write_unaligned(quot);
+        *sp = sp_val - 8;
+    }
+}
+
+/// D+ ( d1 d2 -- d3 )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_d_plus(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 32) {
+            return;
+        }
+        let d1 = read_double(memory, sp_val - 32);
+        let d2 = read_double(memory, sp_val - 16);
+        write_double(memory, sp_val - 32, d1.wrapping_add(d2));
+        *sp = sp_val - 16;
+    }
+}
+
+/// D- ( d1 d2 -- d3 )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_d_minus(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 32) {
+            return;
+        }
+        let d1 = read_double(memory, sp_val - 32);
+        let d2 = read_double(memory, sp_val - 16);
+        write_double(memory, sp_val - 32, d1.wrapping_sub(d2));
+        *sp = sp_val - 16;
+    }
+}
+
+/// DNEGATE ( d -- -d )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_dnegate(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 16) {
+            return;
+        }
+        let d = read_double(memory, sp_val - 16);
+        write_double(memory, sp_val - 16, d.wrapping_neg());
+    }
+}
+
+/// D. ( d -- )
+/// Print a double in decimal followed by a space
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_d_dot(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 16) {
+            return;
+        }
+        let d = read_double(memory, sp_val - 16);
+
+        // Build the digits by hand (39 digits, a sign and the space fit in 48 bytes)
+        let mut buf = [0u8; 48];
+        buf[47] = b' ';
+        let mut pos = buf.len() - 1;
+        let mut magnitude = d.unsigned_abs();
+        loop {
+            pos -= 1;
+            buf[pos] = b'0' + (magnitude % 10) as u8;
+            magnitude /= 10;
+            if magnitude == 0 {
+                break;
+            }
+        }
+        if d < 0 {
+            pos -= 1;
+            buf[pos] = b'-';
+        }
+
+        write_output(memory, &buf[pos..]);
+
+        *sp = sp_val - 16;
+    }
+}
+
+/// 2@ ( addr -- x1 x2 )
+/// Fetch the cell pair stored at addr; x2 is at addr and x1 in the next cell
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_2fetch(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 8) || !check_sp_write(sp_val, 8) {
+            return;
+        }
+        let addr = (memory.add(sp_val - 8) as *const i64).read_unaligned() as usize;
+        if addr + 16 > 8 * 1024 * 1024 {
+            return;
+        }
+        let x2 = (memory.add(addr) as *const i64).read_unaligned();
+        let x1 = (memory.add(addr + 8) as *const i64).read_unaligned();
+        (memory.add(sp_val - 8) as *mut i64).write_unaligned(x1);
+        (memory.add(sp_val) as *mut i64).write_unaligned(x2);
+        *sp = sp_val + 8;
+    }
+}
+
+/// 2! ( x1 x2 addr -- )
+/// Store the cell pair at addr; x2 goes to addr and x1 to the next cell
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_2store(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 24) {
+            return;
+        }
+        let addr = (memory.add(sp_val - 8) as *const i64).read_unaligned() as usize;
+        if addr + 16 <= 8 * 1024 * 1024 {
+            let x2 = (memory.add(sp_val - 16) as *const i64).read_unaligned();
+            let x1 = (memory.add(sp_val - 24) as *const i64).read_unaligned();
+            (memory.add(addr) as *mut i64).write_unaligned(x2);
+            (memory.add(addr + 8) as *mut i64).write_unaligned(x1);
+        }
+        *sp = sp_val - 24;
+    }
+}
+
+// ============================================================================
+// PICTURED NUMERIC OUTPUT
+// <# starts an empty string at HOLD_BUFFER_END, each HOLD prepends one
+// character, and #> returns the finished string for TYPE
+// ============================================================================
+
+/// Current BASE, falling back to decimal when it is not a usable radix
+/// (AOT memory starts zeroed, so BASE is 0 until a program sets it)
+#[inline]
+unsafe fn current_base(memory: *mut u8) -> u128 {
+    unsafe {
+        let base = (memory.add(BASE_ADDR) as *const i64).read_unaligned();
+        if (2..=36).contains(&base) { base as u128 } else { 10 }
+    }
+}
+
+/// Prepend one character to the hold buffer, ignoring it once the buffer is full
+/// or if no <# is active
+#[inline]
+unsafe fn hold_char(memory: *mut u8, c: u8) {
+    unsafe {
+        let hld = (memory.add(HLD_ADDR) as *const i64).read_unaligned() as usize;
+        if hld > HOLD_BUFFER_END - HOLD_BUFFER_SIZE && hld <= HOLD_BUFFER_END {
+            *memory.add(hld - 1) = c;
+            (memory.add(HLD_ADDR) as *mut i64).write_unaligned((hld - 1) as i64);
+        }
+    }
+}
+
+/// Divide ud by BASE and hold the remainder as a digit, returning the quotient
+#[inline]
+unsafe fn hold_digit(memory: *mut u8, ud: u128) -> u128 {
+    unsafe {
+        let base = current_base(memory);
+        let digit = (ud % base) as u8;
+        hold_char(memory, if digit < 10 { b'0' + digit } else { b'A' + digit - 10 });
+        ud / base
+    }
+}
+
+/// <# ( -- )
+/// Start a new pictured numeric output string
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_less_number_sign(memory: *mut u8, _sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        (memory.add(HLD_ADDR) as *mut i64).write_unaligned(HOLD_BUFFER_END as i64);
+    }
+}
+
+/// # ( ud1 -- ud2 )
+/// Hold the least significant digit of ud1 in the current BASE
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_number_sign(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 16) {
+            return;
+        }
+        let ud = read_double(memory, sp_val - 16) as u128;
+        write_double(memory, sp_val - 16, hold_digit(memory, ud) as i128);
+    }
+}
+
+/// #S ( ud1 -- 0 0 )
+/// Hold digits until the number is zero (at least one digit)
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_number_sign_s(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 16) {
+            return;
+        }
+        let mut ud = read_double(memory, sp_val - 16) as u128;
+        loop {
+            ud = hold_digit(memory, ud);
+            if ud == 0 {
+                break;
+            }
+        }
+        write_double(memory, sp_val - 16, 0);
+    }
+}
+
+/// HOLD ( char -- )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_hold(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 8) {
+            return;
+        }
+        let c = (memory.add(sp_val - 8) as *const i64).read_unaligned();
+        hold_char(memory, c as u8);
+        *sp = sp_val - 8;
+    }
+}
+
+/// HOLDS ( c-addr u -- )
+/// Prepend a whole string, keeping its character order
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_holds(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 16) {
+            return;
+        }
+        let len = (memory.add(sp_val - 8) as *const i64).read_unaligned();
+        let addr = (memory.add(sp_val - 16) as *const i64).read_unaligned() as usize;
+        if len > 0 && addr + (len as usize) <= 8 * 1024 * 1024 {
+            for i in (0..len as usize).rev() {
+                hold_char(memory, *memory.add(addr + i));
+            }
+        }
+        *sp = sp_val - 16;
+    }
+}
+
+/// SIGN ( n -- )
+/// Hold a minus sign if n is negative
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_sign(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 8) {
+            return;
+        }
+        let n = (memory.add(sp_val - 8) as *const i64).read_unaligned();
+        if n < 0 {
+            hold_char(memory, b'-');
+        }
+        *sp = sp_val - 8;
+    }
+}
+
+/// #> ( xd -- c-addr u )
+/// Drop the number and return the held string
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_number_sign_greater(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 16) {
+            return;
+        }
+        let hld = (memory.add(HLD_ADDR) as *const i64).read_unaligned() as usize;
+        let start = if hld >= HOLD_BUFFER_END - HOLD_BUFFER_SIZE && hld <= HOLD_BUFFER_END {
+            hld
+        } else {
+            HOLD_BUFFER_END
+        };
+        (memory.add(sp_val - 16) as *mut i64).write_unaligned(start as i64);
+        (memory.add(sp_val - 8) as *mut i64).write_unaligned((HOLD_BUFFER_END - start) as i64);
+    }
+}
+
+// ============================================================================
+// FLOATING POINT
+// Floats live on their own stack (FLOAT_STACK_BASE) whose byte depth is kept
+// at FSP_ADDR, so compiled code keeps the (memory, sp, rp) signature
+// ============================================================================
+
+/// Byte depth of the float stack (a zeroed or corrupt pointer reads as empty)
+#[inline]
+unsafe fn float_depth(memory: *mut u8) -> usize {
+    unsafe {
+        let fsp = (memory.add(FSP_ADDR) as *const i64).read_unaligned();
+        if fsp < 0 || fsp as usize > FLOAT_STACK_SIZE || fsp % 8 != 0 { 0 } else { fsp as usize }
+    }
+}
+
+#[inline]
+unsafe fn float_pop(memory: *mut u8) -> Option<f64> {
+    unsafe {
+        let fsp = float_depth(memory);
+        if fsp == 0 {
+            return None;
+        }
+        (memory.add(FSP_ADDR) as *mut i64).write_unaligned((fsp - 8) as i64);
+        Some((memory.add(FLOAT_STACK_BASE + fsp - 8) as *const f64).read_unaligned())
+    }
+}
+
+#[inline]
+unsafe fn float_push(memory: *mut u8, value: f64) {
+    unsafe {
+        let fsp = float_depth(memory);
+        if fsp + 8 > FLOAT_STACK_SIZE {
+            return;
+        }
+        (memory.add(FLOAT_STACK_BASE + fsp) as *mut f64).write_unaligned(value);
+        (memory.add(FSP_ADDR) as *mut i64).write_unaligned((fsp + 8) as i64);
+    }
+}
+
+/// Macro for float binary operations (F: r1 r2 -- r3)
+macro_rules! float_binary_op {
+    ($name:ident, $op:expr) => {
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $name(
+            memory: *mut u8,
+            _sp: *mut usize,
+            _rp: *mut usize
+        ) {
+            unsafe {
+                if float_depth(memory) < 16 {
+                    return;
+                }
+                let b = float_pop(memory).unwrap_or(0.0);
+                let a = float_pop(memory).unwrap_or(0.0);
+                float_push(memory, $op(a, b));
+            }
+        }
+    };
+}
+
+/// Macro for float unary operations (F: r1 -- r2)
+macro_rules! float_unary_op {
+    ($name:ident, $op:expr) => {
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $name(
+            memory: *mut u8,
+            _sp: *mut usize,
+            _rp: *mut usize
+        ) {
+            unsafe {
+                if let Some(a) = float_pop(memory) {
+                    float_push(memory, $op(a));
+                }
+            }
+        }
+    };
+}
+
+float_binary_op!(quarter_f_plus, |a: f64, b: f64| a + b);
+float_binary_op!(quarter_f_minus, |a: f64, b: f64| a - b);
+float_binary_op!(quarter_f_star, |a: f64, b: f64| a * b);
+float_binary_op!(quarter_f_slash, |a: f64, b: f64| a / b);
+
+float_unary_op!(quarter_fnegate, |a: f64| -a);
+float_unary_op!(quarter_fabs, |a: f64| a.abs());
+float_unary_op!(quarter_fsqrt, |a: f64| a.sqrt());
+float_unary_op!(quarter_fsin, |a: f64| a.sin());
+float_unary_op!(quarter_fcos, |a: f64| a.cos());
+float_unary_op!(quarter_floor, |a: f64| a.floor());
+float_unary_op!(quarter_fround, |a: f64| a.round_ties_even());
+
+/// FDUP ( F: r -- r r )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_fdup(memory: *mut u8, _sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        if let Some(r) = float_pop(memory) {
+            float_push(memory, r);
+            float_push(memory, r);
+        }
+    }
+}
+
+/// FDROP ( F: r -- )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_fdrop(memory: *mut u8, _sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        float_pop(memory);
+    }
+}
+
+/// FSWAP ( F: r1 r2 -- r2 r1 )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_fswap(memory: *mut u8, _sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        if float_depth(memory) < 16 {
+            return;
+        }
+        let r2 = float_pop(memory).unwrap_or(0.0);
+        let r1 = float_pop(memory).unwrap_or(0.0);
+        float_push(memory, r2);
+        float_push(memory, r1);
+    }
+}
+
+/// FOVER ( F: r1 r2 -- r1 r2 r1 )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_fover(memory: *mut u8, _sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let fsp = float_depth(memory);
+        if fsp < 16 {
+            return;
+        }
+        let r1 = (memory.add(FLOAT_STACK_BASE + fsp - 16) as *const f64).read_unaligned();
+        float_push(memory, r1);
+    }
+}
+
+/// FDEPTH ( -- +n )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_fdepth(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_write(sp_val, 8) {
+            return;
+        }
+        (memory.add(sp_val) as *mut i64).write_unaligned((float_depth(memory) / 8) as i64);
+        *sp = sp_val + 8;
+    }
+}
+
+/// F@ ( f-addr -- ) ( F: -- r )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_f_fetch(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 8) {
+            return;
+        }
+        let addr = (memory.add(sp_val - 8) as *const i64).read_unaligned() as usize;
+        *sp = sp_val - 8;
+        if addr + 8 <= 8 * 1024 * 1024 {
+            float_push(memory, (memory.add(addr) as *const f64).read_unaligned());
+        }
+    }
+}
+
+/// F! ( f-addr -- ) ( F: r -- )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_f_store(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 8) {
+            return;
+        }
+        let addr = (memory.add(sp_val - 8) as *const i64).read_unaligned() as usize;
+        *sp = sp_val - 8;
+        if let Some(r) = float_pop(memory) {
+            if addr + 8 <= 8 * 1024 * 1024 {
+                (memory.add(addr) as *mut f64).write_unaligned(r);
+            }
+        }
+    }
+}
+
+/// S>F ( n -- ) ( F: -- r )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_s_to_f(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 8) {
+            return;
+        }
+        let n = (memory.add(sp_val - 8) as *const i64).read_unaligned();
+        *sp = sp_val - 8;
+        float_push(memory, n as f64);
+    }
+}
+
+/// F>S ( -- n ) ( F: r -- )
+/// Truncates toward zero (saturating at the cell range)
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_f_to_s(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_write(sp_val, 8) {
+            return;
+        }
+        if let Some(r) = float_pop(memory) {
+            (memory.add(sp_val) as *mut i64).write_unaligned(r as i64);
+            *sp = sp_val + 8;
+        }
+    }
+}
+
+/// Push a Forth flag computed from the top one or two floats
+#[inline]
+unsafe fn float_flag(memory: *mut u8, sp: *mut usize, floats: usize, test: fn(f64, f64) -> bool) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_write(sp_val, 8) || float_depth(memory) < floats * 8 {
+            return;
+        }
+        let b = float_pop(memory).unwrap_or(0.0);
+        let a = if floats == 2 { float_pop(memory).unwrap_or(0.0) } else { 0.0 };
+        (memory.add(sp_val) as *mut i64).write_unaligned(if test(a, b) { -1 } else { 0 });
+        *sp = sp_val + 8;
+    }
+}
+
+/// F< ( -- flag ) ( F: r1 r2 -- )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_f_less(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        float_flag(memory, sp, 2, |a, b| a < b);
+    }
+}
+
+/// F0< ( -- flag ) ( F: r -- )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_f_zero_less(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        float_flag(memory, sp, 1, |_, r| r < 0.0);
+    }
+}
+
+/// F0= ( -- flag ) ( F: r -- )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_f_zero_equal(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        float_flag(memory, sp, 1, |_, r| r == 0.0);
+    }
+}
+
+/// F. ( F: r -- )
+/// Print a float followed by a space, e.g. "1.5 ", "2. ", "1e20 "
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_f_dot(memory: *mut u8, _sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let Some(r) = float_pop(memory) else {
+            return;
+        };
+        let mut text = if r != 0.0 && (r.abs() >= 1e15 || r.abs() < 1e-4) {
+            format!("{:e}", r)
+        } else {
+            format!("{}", r)
+        };
+        if r.is_finite() && !text.contains(['.', 'e']) {
+            text.push('.');
+        }
+        text.push(' ');
+
+        write_output(memory, text.as_bytes());
+    }
+}
+
+// ============================================================================
+// FILE ACCESS
+// A fileid numbers an open file from 1: the C library FILE* it stands for is
+// kept in the file table, so a fileid that was never opened or is already
+// closed is an error rather than a pointer. An ior is 0 on success or a
+// Forth-2012 throw code: -37 (file I/O exception), -38 (non-existent file)
+// ============================================================================
+
+const IOR_FILE_IO: i64 = -37;
+const IOR_NO_SUCH_FILE: i64 = -38;
+const FAM_WO: i64 = 1;  // R/O is 0, R/W is 2; BIN (4) is ignored, files are always binary
+
+unsafe extern "C" {
+    fn fopen(path: *const u8, mode: *const u8) -> *mut u8;
+    fn fclose(file: *mut u8) -> i32;
+    fn fread(buf: *mut u8, size: usize, count: usize, file: *mut u8) -> usize;
+    fn fwrite(buf: *const u8, size: usize, count: usize, file: *mut u8) -> usize;
+    fn fgetc(file: *mut u8) -> i32;
+    fn ferror(file: *mut u8) -> i32;
+    fn fseeko(file: *mut u8, offset: i64, whence: i32) -> i32;
+    fn ftello(file: *mut u8) -> i64;
+    fn remove(path: *const u8) -> i32;
+}
+
+const SEEK_SET: i32 = 0;
+const SEEK_END: i32 = 2;
+
+/// Read the cell `depth` cells below the top of the stack (1 = top)
+#[inline]
+unsafe fn stack_cell(memory: *mut u8, sp_val: usize, depth: usize) -> i64 {
+    unsafe { (memory.add(sp_val - depth * 8) as *const i64).read_unaligned() }
+}
+
+/// Replace `inputs` cells on top of the stack with `outputs` (deepest first)
+#[inline]
+unsafe fn replace_cells(memory: *mut u8, sp: *mut usize, inputs: usize, outputs: &[i64]) {
+    unsafe {
+        let base = *sp - inputs * 8;
+        for (i, value) in outputs.iter().enumerate() {
+            (memory.add(base + i * 8) as *mut i64).write_unaligned(*value);
+        }
+        *sp = base + outputs.len() * 8;
+    }
+}
+
+/// Check there are `inputs` cells to consume and room for `outputs` results
+#[inline]
+unsafe fn check_cell_args(sp_val: usize, inputs: usize, outputs: usize) -> bool {
+    unsafe {
+        check_sp_read(sp_val, inputs * 8)
+            && (outputs <= inputs || check_sp_write(sp_val, (outputs - inputs) * 8))
+    }
+}
+
+/// The bytes of a ( c-addr u ) string, if it lies inside Forth memory
+#[inline]
+unsafe fn memory_slice<'a>(memory: *mut u8, addr: i64, len: i64) -> Option<&'a mut [u8]> {
+    if addr < 0 || len < 0 || addr as usize + len as usize > 8 * 1024 * 1024 {
+        return None;
+    }
+    unsafe { Some(std::slice::from_raw_parts_mut(memory.add(addr as usize), len as usize)) }
+}
+
+/// A ( c-addr u ) file name as a NUL-terminated path
+#[inline]
+unsafe fn file_path(memory: *mut u8, addr: i64, len: i64) -> Option<std::ffi::CString> {
+    unsafe { memory_slice(memory, addr, len).and_then(|name| std::ffi::CString::new(name.to_vec()).ok()) }
+}
+
+/// The file table entry of `fileid`, if it is in range
+#[inline]
+unsafe fn file_slot(memory: *mut u8, fileid: i64) -> Option<*mut i64> {
+    if fileid < 1 || fileid > FILE_TABLE_CELLS as i64 {
+        return None;
+    }
+    unsafe { Some(memory.add(FILE_TABLE_ADDR + (fileid as usize - 1) * 8) as *mut i64) }
+}
+
+/// The FILE* of the open file `fileid`, or null if there is none
+#[inline]
+unsafe fn open_file(memory: *mut u8, fileid: i64) -> *mut u8 {
+    unsafe {
+        match file_slot(memory, fileid) {
+            Some(slot) => slot.read_unaligned() as *mut u8,
+            None => std::ptr::null_mut(),
+        }
+    }
+}
+
+/// Enter `file` in a free slot of the file table, returning its fileid (0 if the table is full)
+unsafe fn add_open_file(memory: *mut u8, file: *mut u8) -> i64 {
+    unsafe {
+        for index in 0..FILE_TABLE_CELLS {
+            let slot = memory.add(FILE_TABLE_ADDR + index * 8) as *mut i64;
+            if slot.read_unaligned() == 0 {
+                slot.write_unaligned(file as i64);
+                return index as i64 + 1;
+            }
+        }
+        0
+    }
+}
+
+/// The ior for the C library call that just failed
+fn last_ior() -> i64 {
+    if std::io::Error::last_os_error().kind() == std::io::ErrorKind::NotFound {
+        IOR_NO_SUCH_FILE
+    } else {
+        IOR_FILE_IO
+    }
+}
+
+/// Shared body of OPEN-FILE and CREATE-FILE ( c-addr u fam -- fileid ior )
+unsafe fn open_with_mode(memory: *mut u8, sp: *mut usize, create: bool) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_cell_args(sp_val, 3, 2) {
+            return;
+        }
+        let fam = stack_cell(memory, sp_val, 1) & 3;
+        let mode: &[u8] = match (create, fam == FAM_WO) {
+            (false, _) if fam == 0 => b"rb\0",
+            (false, _) => b"r+b\0",
+            (true, true) => b"wb\0",
+            (true, false) => b"w+b\0",
+        };
+        let Some(path) = file_path(memory, stack_cell(memory, sp_val, 3), stack_cell(memory, sp_val, 2)) else {
+            replace_cells(memory, sp, 3, &[0, IOR_FILE_IO]);
+            return;
+        };
+        let file = fopen(path.as_ptr() as *const u8, mode.as_ptr());
+        if file.is_null() {
+            replace_cells(memory, sp, 3, &[0, last_ior()]);
+            return;
+        }
+        let fileid = add_open_file(memory, file);
+        if fileid == 0 {
+            fclose(file);
+            replace_cells(memory, sp, 3, &[0, IOR_FILE_IO]);
+        } else {
+            replace_cells(memory, sp, 3, &[fileid, 0]);
+        }
+    }
+}
+
+/// OPEN-FILE ( c-addr u fam -- fileid ior )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_open_file(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        open_with_mode(memory, sp, false);
+    }
+}
+
+/// CREATE-FILE ( c-addr u fam -- fileid ior )
+/// Creates the file, or truncates it if it already exists
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_create_file(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        open_with_mode(memory, sp, true);
+    }
+}
+
+/// CLOSE-FILE ( fileid -- ior )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_close_file(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_cell_args(sp_val, 1, 1) {
+            return;
+        }
+        let fileid = stack_cell(memory, sp_val, 1);
+        let file = open_file(memory, fileid);
+        let ior = if file.is_null() {
+            IOR_FILE_IO
+        } else {
+            // The fileid is free again even if the close fails
+            if let Some(slot) = file_slot(memory, fileid) {
+                slot.write_unaligned(0);
+            }
+            if fclose(file) != 0 { IOR_FILE_IO } else { 0 }
+        };
+        replace_cells(memory, sp, 1, &[ior]);
+    }
+}
+
+/// DELETE-FILE ( c-addr u -- ior )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_delete_file(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_cell_args(sp_val, 2, 1) {
+            return;
+        }
+        let ior = match file_path(memory, stack_cell(memory, sp_val, 2), stack_cell(memory, sp_val, 1)) {
+            Some(path) if remove(path.as_ptr() as *const u8) == 0 => 0,
+            Some(_) => last_ior(),
+            None => IOR_FILE_IO,
+        };
+        replace_cells(memory, sp, 2, &[ior]);
+    }
+}
+
+/// READ-FILE ( c-addr u1 fileid -- u2 ior )
+/// u2 is less than u1 at end of file (0 once nothing is left)
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_read_file(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_cell_args(sp_val, 3, 2) {
+            return;
+        }
+        let file = open_file(memory, stack_cell(memory, sp_val, 1));
+        let buffer = memory_slice(memory, stack_cell(memory, sp_val, 3), stack_cell(memory, sp_val, 2));
+        let (Some(buffer), false) = (buffer, file.is_null()) else {
+            replace_cells(memory, sp, 3, &[0, IOR_FILE_IO]);
+            return;
+        };
+        let count = fread(buffer.as_mut_ptr(), 1, buffer.len(), file);
+        let ior = if ferror(file) != 0 { IOR_FILE_IO } else { 0 };
+        replace_cells(memory, sp, 3, &[count as i64, ior]);
+    }
+}
+
+/// READ-LINE ( c-addr u1 fileid -- u2 flag ior )
+/// Reads up to u1 characters of the next line, without its terminator (LF or
+/// CRLF). flag is false only at end of file
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_read_line(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_cell_args(sp_val, 3, 3) {
+            return;
+        }
+        let file = open_file(memory, stack_cell(memory, sp_val, 1));
+        let buffer = memory_slice(memory, stack_cell(memory, sp_val, 3), stack_cell(memory, sp_val, 2));
+        let (Some(buffer), false) = (buffer, file.is_null()) else {
+            replace_cells(memory, sp, 3, &[0, 0, IOR_FILE_IO]);
+            return;
+        };
+
+        let mut count = 0;
+        let mut found = false;
+        while count < buffer.len() {
+            let c = fgetc(file);
+            if c < 0 {
+                break;
+            }
+            found = true;
+            if c == b'\n' as i32 {
+                if count > 0 && buffer[count - 1] == b'\r' {
+                    count -= 1;
+                }
+                break;
+            }
+            buffer[count] = c as u8;
+            count += 1;
+        }
+
+        let ior = if ferror(file) != 0 { IOR_FILE_IO } else { 0 };
+        replace_cells(memory, sp, 3, &[count as i64, if found { -1 } else { 0 }, ior]);
+    }
+}
+
+/// Shared body of WRITE-FILE and WRITE-LINE ( c-addr u fileid -- ior )
+unsafe fn write_string(memory: *mut u8, sp: *mut usize, newline: bool) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_cell_args(sp_val, 3, 1) {
+            return;
+        }
+        let file = open_file(memory, stack_cell(memory, sp_val, 1));
+        let text = memory_slice(memory, stack_cell(memory, sp_val, 3), stack_cell(memory, sp_val, 2));
+        let (Some(text), false) = (text, file.is_null()) else {
+            replace_cells(memory, sp, 3, &[IOR_FILE_IO]);
+            return;
+        };
+        let mut ok = fwrite(text.as_ptr(), 1, text.len(), file) == text.len();
+        if newline {
+            ok = ok && fwrite(b"\n".as_ptr(), 1, 1, file) == 1;
+        }
+        replace_cells(memory, sp, 3, &[if ok { 0 } else { IOR_FILE_IO }]);
+    }
+}
+
+/// WRITE-FILE ( c-addr u fileid -- ior )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_write_file(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        write_string(memory, sp, false);
+    }
+}
+
+/// WRITE-LINE ( c-addr u fileid -- ior )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_write_line(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        write_string(memory, sp, true);
+    }
+}
+
+/// FILE-POSITION ( fileid -- ud ior )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_file_position(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_cell_args(sp_val, 1, 3) {
+            return;
+        }
+        let file = open_file(memory, stack_cell(memory, sp_val, 1));
+        let position = if file.is_null() { -1 } else { ftello(file) };
+        if position < 0 {
+            replace_cells(memory, sp, 1, &[0, 0, IOR_FILE_IO]);
+        } else {
+            replace_cells(memory, sp, 1, &[position, 0, 0]);
+        }
+    }
+}
+
+/// FILE-SIZE ( fileid -- ud ior )
+/// Leaves the file position unchanged
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_file_size(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_cell_args(sp_val, 1, 3) {
+            return;
+        }
+        let file = open_file(memory, stack_cell(memory, sp_val, 1));
+        let position = if file.is_null() { -1 } else { ftello(file) };
+        let size = if position >= 0 && fseeko(file, 0, SEEK_END) == 0 {
+            let size = ftello(file);
+            if fseeko(file, position, SEEK_SET) == 0 { size } else { -1 }
+        } else {
+            -1
+        };
+        if size < 0 {
+            replace_cells(memory, sp, 1, &[0, 0, IOR_FILE_IO]);
+        } else {
+            replace_cells(memory, sp, 1, &[size, 0, 0]);
+        }
+    }
+}
+
+/// REPOSITION-FILE ( ud fileid -- ior )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_reposition_file(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_cell_args(sp_val, 3, 1) {
+            return;
+        }
+        let file = open_file(memory, stack_cell(memory, sp_val, 1));
+        let high = stack_cell(memory, sp_val, 2);
+        let low = stack_cell(memory, sp_val, 3);
+        let ok = !file.is_null() && high == 0 && low >= 0 && fseeko(file, low, SEEK_SET) == 0;
+        replace_cells(memory, sp, 3, &[if ok { 0 } else { IOR_FILE_IO }]);
+    }
+}
+
+// ============================================================================
+// MEMORY ALLOCATION
+// Heap blocks lie back to back from HEAP_BASE. Each starts with a header cell
+// holding the block size in bytes (header included, a multiple of 8) with
+// bit 0 set while the block is in use; ALLOCATE hands out the address after
+// the header. An ior is 0 on success or a Forth-2012 throw code: -59
+// (ALLOCATE), -60 (FREE) or -61 (RESIZE)
+// ============================================================================
+
+const IOR_ALLOCATE: i64 = -59;
+const IOR_FREE: i64 = -60;
+const IOR_RESIZE: i64 = -61;
+const BLOCK_USED: usize = 1;
+
+#[inline]
+unsafe fn read_heap_cell(memory: *mut u8, addr: usize) -> usize {
+    unsafe { (memory.add(addr) as *const usize).read_unaligned() }
+}
+
+#[inline]
+unsafe fn write_heap_cell(memory: *mut u8, addr: usize, value: usize) {
+    unsafe { (memory.add(addr) as *mut usize).write_unaligned(value) }
+}
+
+/// End of the last heap block
+#[inline]
+unsafe fn heap_top(memory: *mut u8) -> usize {
+    match unsafe { read_heap_cell(memory, HEAP_TOP_ADDR) } {
+        0 => HEAP_BASE,
+        top => top,
+    }
+}
+
+/// Block size (header included) needed for `u` bytes of data
+fn block_size(u: i64) -> Option<usize> {
+    let u = usize::try_from(u).ok()?;
+    let size = u.checked_add(8 + 7)? & !7;
+    Some(size.max(16))
+}
+
+/// Header address and size of the allocated block whose data starts at `addr`
+unsafe fn heap_block(memory: *mut u8, addr: i64) -> Option<(usize, usize)> {
+    unsafe {
+        let addr = usize::try_from(addr).ok()?;
+        let top = heap_top(memory);
+        if addr < HEAP_BASE + 8 || addr >= top || addr % 8 != 0 {
+            return None;
+        }
+        let header = addr - 8;
+        let cell = read_heap_cell(memory, header);
+        let size = cell & !BLOCK_USED;
+        (cell & BLOCK_USED != 0 && size >= 16 && header + size <= top).then_some((header, size))
+    }
+}
+
+/// First-fit allocation of a block of `size` bytes, merging free neighbours on
+/// the way and growing the heap when no free block is big enough
+unsafe fn heap_allocate(memory: *mut u8, size: usize) -> Option<usize> {
+    unsafe {
+        let mut top = heap_top(memory);
+        let mut header = HEAP_BASE;
+        while header < top {
+            let cell = read_heap_cell(memory, header);
+            let mut block = cell & !BLOCK_USED;
+            if cell & BLOCK_USED == 0 {
+                while header + block < top && read_heap_cell(memory, header + block) & BLOCK_USED == 0 {
+                    block += read_heap_cell(memory, header + block);
+                }
+                if header + block == top {
+                    // Free space at the end goes back to the unused part of the heap
+                    top = header;
+                    break;
+                }
+                write_heap_cell(memory, header, block);
+                if block >= size {
+                    if block - size >= 16 {
+                        write_heap_cell(memory, header + size, block - size);
+                        block = size;
+                    }
+                    write_heap_cell(memory, header, block | BLOCK_USED);
+                    return Some(header + 8);
+                }
+            }
+            header += block;
+        }
+        if size > HEAP_END - top {
+            write_heap_cell(memory, HEAP_TOP_ADDR, top);
+            return None;
+        }
+        write_heap_cell(memory, top, size | BLOCK_USED);
+        write_heap_cell(memory, HEAP_TOP_ADDR, top + size);
+        Some(top + 8)
+    }
+}
+
+/// Mark a block free, shrinking the heap if it was the last one
+unsafe fn heap_free(memory: *mut u8, header: usize, size: usize) {
+    unsafe {
+        if header + size == heap_top(memory) {
+            write_heap_cell(memory, HEAP_TOP_ADDR, header);
+        } else {
+            write_heap_cell(memory, header, size);
+        }
+    }
+}
+
+/// ALLOCATE ( u -- a-addr ior )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_allocate(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_cell_args(sp_val, 1, 2) {
+            return;
+        }
+        let addr = block_size(stack_cell(memory, sp_val, 1)).and_then(|size| heap_allocate(memory, size));
+        match addr {
+            Some(addr) => replace_cells(memory, sp, 1, &[addr as i64, 0]),
+            None => replace_cells(memory, sp, 1, &[0, IOR_ALLOCATE]),
+        }
+    }
+}
+
+/// FREE ( a-addr -- ior )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_free(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_cell_args(sp_val, 1, 1) {
+            return;
+        }
+        match heap_block(memory, stack_cell(memory, sp_val, 1)) {
+            Some((header, size)) => {
+                heap_free(memory, header, size);
+                replace_cells(memory, sp, 1, &[0]);
+            }
+            None => replace_cells(memory, sp, 1, &[IOR_FREE]),
+        }
+    }
+}
+
+/// RESIZE ( a-addr1 u -- a-addr2 ior )
+/// On failure a-addr1 is returned and the block is left as it was
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_resize(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_cell_args(sp_val, 2, 2) {
+            return;
+        }
+        let addr = stack_cell(memory, sp_val, 2);
+        let (Some((header, size)), Some(new_size)) =
+            (heap_block(memory, addr), block_size(stack_cell(memory, sp_val, 1)))
+        else {
+            replace_cells(memory, sp, 2, &[addr, IOR_RESIZE]);
+            return;
+        };
+        if new_size <= size {
+            replace_cells(memory, sp, 2, &[addr, 0]);
+            return;
+        }
+        if header + size == heap_top(memory) && new_size <= HEAP_END - header {
+            // The last block grows in place
+            write_heap_cell(memory, header, new_size | BLOCK_USED);
+            write_heap_cell(memory, HEAP_TOP_ADDR, header + new_size);
+            replace_cells(memory, sp, 2, &[addr, 0]);
+            return;
+        }
+        match heap_allocate(memory, new_size) {
+            Some(new_addr) => {
+                std::ptr::copy_nonoverlapping(memory.add(header + 8), memory.add(new_addr), size - 8);
+                heap_free(memory, header, size);
+                replace_cells(memory, sp, 2, &[new_addr as i64, 0]);
+            }
+            None => replace_cells(memory, sp, 2, &[addr, IOR_RESIZE]),
+        }
+    }
+}
+
+// ============================================================================
+// EXCEPTIONS
+// ============================================================================
+//
+// Compiled code unwinds a THROW by returning: THROW records its code at
+// THROW_ADDR and every compiled call site returns as soon as it sees a code
+// pending there. Whoever called the outermost compiled word (the interpreter's
+// CATCH, or main() of an AOT executable) picks the code up.
+
+/// THROW ( k*x n -- k*x | i*x n )
+/// Record a non-zero n as the pending throw code
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_throw(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 8) {
+            return;
+        }
+        let code = (memory.add(sp_val - 8) as *const i64).read_unaligned();
+        *sp = sp_val - 8;
+        if code != 0 {
+            raise(memory, code);
+        }
+    }
+}
+
+/// CATCH for AOT executables ( i*x xt -- j*x 0 | i*x n )
+/// Run the xt's compiled code from the xt table (-13 if it has none). If it
+/// throws, take the pending code and put the stacks back as CATCH found them
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_catch_compiled(memory: *mut u8, sp: *mut usize, rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_sp_read(sp_val, 8) {
+            return;
+        }
+        let xt = (memory.add(sp_val - 8) as *const i64).read_unaligned();
+        *sp = sp_val - 8;
+        let (saved_sp, saved_rp) = (*sp, *rp);
+
+        let entry = if (0..XT_TABLE_CELLS).contains(&xt) {
+            (memory.add(XT_TABLE_ADDR + xt as usize * 8) as *const usize).read_unaligned()
+        } else {
+            0
+        };
+        if entry != 0 {
+            let word: unsafe extern "C" fn(*mut u8, *mut usize, *mut usize) = std::mem::transmute(entry);
+            word(memory, sp, rp);
+        } else {
+            raise(memory, -13);
+        }
+
+        let pending = memory.add(THROW_ADDR) as *mut i64;
+        let code = pending.read_unaligned();
+        if code != 0 {
+            pending.write_unaligned(0);
+            *sp = saved_sp;
+            *rp = saved_rp;
+        }
+        if check_sp_write(*sp, 8) {
+            (memory.add(*sp) as *mut i64).write_unaligned(code);
+            *sp += 8;
+        }
+    }
+}
+
+// ============================================================================
+// NATIVE WORDS
+// Words the embedding program writes in Rust are closures in the interpreter's
+// dictionary, so compiled code calls them by xt through this trampoline. The
+// interpreter stores the function that runs an xt in NATIVE_HANDLER_ADDR; an AOT
+// executable has no dictionary, and unless the program linking it stores a
+// handler of its own the call is -13 THROW, as for EXECUTE.
+// ============================================================================
+
+/// Native-word trampoline ( i*x xt -- j*x )
+/// Run the word through the handler, which leaves its throw code pending like THROW
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_call_native(memory: *mut u8, sp: *mut usize, rp: *mut usize) {
+    unsafe {
+        let handler = (memory.add(NATIVE_HANDLER_ADDR) as *const usize).read_unaligned();
+        if handler != 0 {
+            let handler: unsafe extern "C" fn(*mut u8, *mut usize, *mut usize) = std::mem::transmute(handler);
+            handler(memory, sp, rp);
+            return;
+        }
+        let sp_val = *sp;
+        if check_sp_read(sp_val, 8) {
+            *sp = sp_val - 8;
+        }
+        raise(memory, -13);
+    }
+}
+
+// ============================================================================
+// INPUT SOURCE
+// The interpreter describes the text it is reading in system cells: SOURCE
+// is the line being interpreted, >IN how much of it has been parsed. The line
+// lies within a whole text (a file, the user's input, an EVALUATE string),
+// so REFILL moves on to the next line without help from the interpreter.
+// Compiled code run outside the interpreter sees an empty input buffer
+// ============================================================================
+
+#[inline]
+unsafe fn read_input_cell(memory: *mut u8, addr: usize) -> i64 {
+    unsafe { (memory.add(addr) as *const i64).read_unaligned() }
+}
+
+#[inline]
+unsafe fn write_input_cell(memory: *mut u8, addr: usize, value: i64) {
+    unsafe { (memory.add(addr) as *mut i64).write_unaligned(value) }
+}
+
+/// The parse area: address just past the parsed part of the input buffer and the bytes left
+#[inline]
+unsafe fn parse_area(memory: *mut u8) -> (usize, usize) {
+    unsafe {
+        let line = read_input_cell(memory, SOURCE_ADDR) as usize;
+        let len = read_input_cell(memory, SOURCE_LEN_ADDR).max(0) as usize;
+        let to_in = read_input_cell(memory, TO_IN_ADDR).clamp(0, len as i64) as usize;
+        (line + to_in, len - to_in)
+    }
+}
+
+/// Consume `n` bytes of the parse area
+#[inline]
+unsafe fn advance_input(memory: *mut u8, n: usize) {
+    unsafe {
+        let len = read_input_cell(memory, SOURCE_LEN_ADDR).max(0);
+        let to_in = read_input_cell(memory, TO_IN_ADDR).clamp(0, len);
+        write_input_cell(memory, TO_IN_ADDR, (to_in + n as i64).min(len));
+    }
+}
+
+/// Whether `c` delimits text parsed up to `delimiter`; a space delimiter also
+/// matches tabs and other control characters
+#[inline]
+fn is_delimiter(c: u8, delimiter: u8) -> bool {
+    c == delimiter || (delimiter == b' ' && c < b' ')
+}
+
+/// Parse text up to `delimiter`, optionally skipping leading delimiters first
+/// Returns the text's address and length; >IN moves past the delimiter found
+unsafe fn parse_input(memory: *mut u8, delimiter: u8, skip_leading: bool) -> (usize, usize) {
+    unsafe {
+        let (addr, len) = parse_area(memory);
+        let area = std::slice::from_raw_parts(memory.add(addr), len);
+        let start = if skip_leading {
+            area.iter().position(|&c| !is_delimiter(c, delimiter)).unwrap_or(len)
+        } else {
+            0
+        };
+        let end = area[start..].iter().position(|&c| is_delimiter(c, delimiter)).map_or(len, |n| start + n);
+        advance_input(memory, (end + 1).min(len));
+        (addr + start, end - start)
+    }
+}
+
+/// SOURCE ( -- c-addr u )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_source(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        if !check_cell_args(*sp, 0, 2) {
+            return;
+        }
+        let line = read_input_cell(memory, SOURCE_ADDR);
+        let len = read_input_cell(memory, SOURCE_LEN_ADDR);
+        replace_cells(memory, sp, 0, &[line, len]);
+    }
+}
+
+/// >IN ( -- a-addr )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_to_in(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        if !check_cell_args(*sp, 0, 1) {
+            return;
+        }
+        replace_cells(memory, sp, 0, &[TO_IN_ADDR as i64]);
+    }
+}
+
+/// SOURCE-ID ( -- 0 | -1 | n )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_source_id(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        if !check_cell_args(*sp, 0, 1) {
+            return;
+        }
+        replace_cells(memory, sp, 0, &[read_input_cell(memory, SOURCE_ID_ADDR)]);
+    }
+}
+
+/// PARSE ( char "ccc<char>" -- c-addr u )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_parse(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_cell_args(sp_val, 1, 2) {
+            return;
+        }
+        let delimiter = stack_cell(memory, sp_val, 1) as u8;
+        let (addr, len) = parse_input(memory, delimiter, false);
+        replace_cells(memory, sp, 1, &[addr as i64, len as i64]);
+    }
+}
+
+/// PARSE-NAME ( "<spaces>name<space>" -- c-addr u )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_parse_name(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        if !check_cell_args(*sp, 0, 2) {
+            return;
+        }
+        let (addr, len) = parse_input(memory, b' ', true);
+        replace_cells(memory, sp, 0, &[addr as i64, len as i64]);
+    }
+}
+
+/// WORD ( char "<chars>ccc<char>" -- c-addr )
+/// Leaves the text as a counted string in a buffer the next WORD overwrites
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_word(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_cell_args(sp_val, 1, 1) {
+            return;
+        }
+        let delimiter = stack_cell(memory, sp_val, 1) as u8;
+        let (addr, len) = parse_input(memory, delimiter, true);
+        let len = len.min(255);
+        std::ptr::copy(memory.add(addr), memory.add(WORD_BUFFER + 1), len);
+        *memory.add(WORD_BUFFER) = len as u8;
+        // A space follows the text, as Forth-94 required
+        *memory.add(WORD_BUFFER + 1 + len) = b' ';
+        replace_cells(memory, sp, 1, &[WORD_BUFFER as i64]);
+    }
+}
+
+/// REFILL ( -- flag )
+/// Make the next line of the text the input buffer; false at the end of the
+/// text and for EVALUATE strings, which are a single line
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_refill(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        if !check_cell_args(*sp, 0, 1) {
+            return;
+        }
+        let next = read_input_cell(memory, SOURCE_ADDR) + read_input_cell(memory, SOURCE_LEN_ADDR) + 1;
+        let end = read_input_cell(memory, INPUT_END_ADDR);
+        if read_input_cell(memory, SOURCE_ID_ADDR) == -1 || next > end {
+            replace_cells(memory, sp, 0, &[0]);
+            return;
+        }
+        let rest = std::slice::from_raw_parts(memory.add(next as usize), (end - next) as usize);
+        let len = rest.iter().position(|&c| c == b'\n').unwrap_or(rest.len());
+        write_input_cell(memory, SOURCE_ADDR, next);
+        write_input_cell(memory, SOURCE_LEN_ADDR, len as i64);
+        write_input_cell(memory, TO_IN_ADDR, 0);
+        replace_cells(memory, sp, 0, &[-1]);
+    }
+}
+
+/// SAVE-INPUT ( -- source-id c-addr u >in 4 )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_save_input(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        if !check_cell_args(*sp, 0, 5) {
+            return;
+        }
+        replace_cells(memory, sp, 0, &[
+            read_input_cell(memory, SOURCE_ID_ADDR),
+            read_input_cell(memory, SOURCE_ADDR),
+            read_input_cell(memory, SOURCE_LEN_ADDR),
+            read_input_cell(memory, TO_IN_ADDR),
+            4,
+        ]);
+    }
+}
+
+/// RESTORE-INPUT ( source-id c-addr u >in 4 -- flag )
+/// Go back to input saved from the current text; flag is true if it was not
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_restore_input(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_cell_args(sp_val, 1, 1) {
+            return;
+        }
+        let n = stack_cell(memory, sp_val, 1);
+        if n < 0 || !check_cell_args(sp_val, n as usize + 1, 1) {
+            return;
+        }
+        let restored = n == 4 && {
+            let (id, line, len, to_in) = (
+                stack_cell(memory, sp_val, 5),
+                stack_cell(memory, sp_val, 4),
+                stack_cell(memory, sp_val, 3),
+                stack_cell(memory, sp_val, 2),
+            );
+            let same_text = id == read_input_cell(memory, SOURCE_ID_ADDR)
+                && line >= read_input_cell(memory, INPUT_START_ADDR)
+                && len >= 0
+                && line + len <= read_input_cell(memory, INPUT_END_ADDR);
+            if same_text {
+                write_input_cell(memory, SOURCE_ADDR, line);
+                write_input_cell(memory, SOURCE_LEN_ADDR, len);
+                write_input_cell(memory, TO_IN_ADDR, to_in.clamp(0, len));
+            }
+            same_text
+        };
+        replace_cells(memory, sp, n as usize + 1, &[if restored { 0 } else { -1 }]);
+    }
+}
+
+// ============================================================================
+// ENVIRONMENT QUERIES
+// ENVIRONMENT? answers from the constants above, so compiled code and the
+// interpreter agree on what the system is
+// ============================================================================
+
+/// What ENVIRONMENT? leaves for a query it knows, besides the true flag
+enum EnvironmentAnswer {
+    /// Data stack cells, deepest first
+    Cells(Vec<i64>),
+    /// A float, left on the float stack
+    Float(f64),
+}
+
+/// The version as one number: major * 10000 + minor * 100 + patch
+fn version_number() -> i64 {
+    VERSION.split('.')
+        .map(|part| part.parse::<i64>().unwrap_or(0))
+        .chain(std::iter::repeat(0))
+        .take(3)
+        .fold(0, |number, part| number * 100 + part)
+}
+
+/// The answer to the ENVIRONMENT? query `name`, if it is one we know
+unsafe fn environment_answer(memory: *mut u8, name: &[u8]) -> Option<EnvironmentAnswer> {
+    use EnvironmentAnswer::{Cells, Float};
+
+    let mode = unsafe { (memory.add(EXECUTION_MODE_ADDR) as *const i64).read_unaligned() };
+    let name = std::str::from_utf8(name).ok()?.to_ascii_uppercase();
+    let answer = match name.as_str() {
+        // Forth 2012 implementation limits
+        "/COUNTED-STRING" => Cells(vec![u8::MAX as i64]),
+        "/HOLD" => Cells(vec![HOLD_BUFFER_SIZE as i64]),
+        "ADDRESS-UNIT-BITS" => Cells(vec![8]),
+        "FLOORED" => Cells(vec![0]),
+        "MAX-CHAR" => Cells(vec![u8::MAX as i64]),
+        "MAX-D" => Cells(vec![-1, i64::MAX]),
+        "MAX-N" => Cells(vec![i64::MAX]),
+        "MAX-U" => Cells(vec![-1]),
+        "MAX-UD" => Cells(vec![-1, -1]),
+        "RETURN-STACK-CELLS" => Cells(vec![((RETURN_STACK_END - RETURN_STACK_BASE) / CELL_SIZE) as i64]),
+        "STACK-CELLS" => Cells(vec![(RETURN_STACK_BASE / CELL_SIZE) as i64]),
+        "FLOATING-STACK" => Cells(vec![(FLOAT_STACK_SIZE / CELL_SIZE) as i64]),
+        "MAX-FLOAT" => Float(f64::MAX),
+
+        // Word sets that are present
+        "CORE" | "DOUBLE" | "EXCEPTION" | "FILE" | "FLOATING" | "LOCALS"
+        | "MEMORY-ALLOCATION" | "SEARCH-ORDER" | "STRING" => Cells(vec![-1]),
+
+        // Quarter
+        "QUARTER-VERSION" => Cells(vec![version_number()]),
+        "QUARTER-MODE" => Cells(vec![mode]),
+        "QUARTER-JIT" => Cells(vec![if mode == MODE_JIT { -1 } else { 0 }]),
+        "QUARTER-CELL-SIZE" => Cells(vec![CELL_SIZE as i64]),
+        "QUARTER-MEMORY-SIZE" => Cells(vec![MEMORY_SIZE as i64]),
+        _ => return None,
+    };
+    Some(answer)
+}
+
+/// ENVIRONMENT? ( c-addr u -- false | i*x true )
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_environment_query(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
+    unsafe {
+        let sp_val = *sp;
+        if !check_cell_args(sp_val, 2, 1) {
+            return;
+        }
+        let (addr, len) = (stack_cell(memory, sp_val, 2), stack_cell(memory, sp_val, 1));
+        let answer = memory_slice(memory, addr, len).and_then(|name| environment_answer(memory, name));
+        match answer {
+            Some(EnvironmentAnswer::Cells(mut values)) if check_cell_args(sp_val, 2, values.len() + 1) => {
+                values.push(-1);
+                replace_cells(memory, sp, 2, &values);
+            }
+            Some(EnvironmentAnswer::Float(value)) if float_depth(memory) + 8 <= FLOAT_STACK_SIZE => {
+                float_push(memory, value);
+                replace_cells(memory, sp, 2, &[-1]);
+            }
+            _ => replace_cells(memory, sp, 2, &[0]),
+        }
+    }
+}
+
+// ============================================================================
+// RUNTIME INITIALIZATION
+// ============================================================================
+
+unsafe extern "C" {
+    fn calloc(nmemb: usize, size: usize) -> *mut u8;
+    fn free(ptr: *mut u8);
+}
+
+static mut RUNTIME_MEMORY: *mut u8 = 0 as *mut u8;
+static mut RUNTIME_SP: usize = 0;
+static mut RUNTIME_RP: usize = 0x010000;  // Return stack starts at 64KB
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_runtime_init() {
+    unsafe {
+        // Allocate 8MB of zeroed memory using calloc
+        RUNTIME_MEMORY = calloc(8 * 1024 * 1024, 1);
+        if !RUNTIME_MEMORY.is_null() {
+            (RUNTIME_MEMORY.add(EXECUTION_MODE_ADDR) as *mut i64).write_unaligned(MODE_AOT);
+        }
+
+        // Initialize stack pointers
+        RUNTIME_SP = 0;  // Data stack starts at 0
+        RUNTIME_RP = 0x010000;  // Return stack starts at 64KB
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_runtime_cleanup() {
+    unsafe {
+        if !RUNTIME_MEMORY.is_null() {
+            free(RUNTIME_MEMORY);
+            RUNTIME_MEMORY = 0 as *mut u8;
+        }
+    }
+}
+
+/// Throw code left pending when the main word returned, 0 if none
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_runtime_throw_code() -> i64 {
+    unsafe {
+        if RUNTIME_MEMORY.is_null() {
+            return 0;
         }
+        (RUNTIME_MEMORY.add(THROW_ADDR) as *const i64).read_unaligned()
     }
 }
 
@@ -0,0 +1,217 @@
+// Interpreter state kept outside Forth memory
+// The self-hosting compiler's AST and LLVM handles, the REPL's line editor, where input
+// comes from and output goes, and what words that re-enter the interpreter run on all
+// belong to one Quarter VM. Memory owns the session, so every primitive can reach it,
+// and compiled code finds it through the system cell at 0x7FFE80
+
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+
+use rustyline::DefaultEditor;
+
+use crate::ast_forth::AstRegistry;
+use crate::input::Input;
+use crate::llvm_forth::LLVMRegistry;
+use crate::output::Output;
+use crate::{CompilerConfig, Dictionary, LoopStack, Memory, ReturnStack, RuntimeContext};
+
+/// State of one interpreter that doesn't live in Forth memory
+#[derive(Default)]
+pub struct Session {
+    /// AST nodes the compiler has handles to
+    pub ast: AstRegistry,
+    /// LLVM contexts, modules, builders and values the compiler has handles to
+    pub llvm: LLVMRegistry,
+    editor: Option<DefaultEditor>,
+    /// Where KEY, ACCEPT, READLINE and REFILL read from
+    input: Input,
+    /// Input read but not yet taken, such as the rest of the line KEY is in
+    pending: VecDeque<u8>,
+    /// Where output goes when no >STRING capture is open
+    pub output: Output,
+    /// Text written since each open >STRING, innermost last
+    captures: Vec<Vec<u8>>,
+    /// Words CREATE and DOES> made while a word ran, oldest first
+    created: Vec<Created>,
+    pub(crate) reentry: Option<Reentry>,
+}
+
+/// A change CREATE or DOES> makes to the dictionary. The word that runs them only has
+/// the dictionary to read, so the outer interpreter makes the change once it returns
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Created {
+    /// CREATE: a word `name` that pushes the data-field address `addr`
+    Word { name: String, addr: i64 },
+    /// DOES>: the word created last also runs the word with this xt
+    Does(i64),
+}
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Session").finish_non_exhaustive()
+    }
+}
+
+impl Session {
+    /// The line editor READLINE reads with, created the first time it's needed
+    pub(crate) fn line_editor(&mut self) -> Option<&mut DefaultEditor> {
+        if self.editor.is_none() {
+            self.editor = DefaultEditor::new().ok();
+        }
+        self.editor.as_mut()
+    }
+
+    /// The line editor, if READLINE or HISTORY-LOAD has created it
+    pub(crate) fn existing_line_editor(&mut self) -> Option<&mut DefaultEditor> {
+        self.editor.as_mut()
+    }
+
+    /// Read input from `input` from now on, returning where it came from before. What
+    /// was read from the old input and not yet taken is dropped
+    pub fn set_input(&mut self, input: Input) -> Input {
+        self.pending.clear();
+        std::mem::replace(&mut self.input, input)
+    }
+
+    /// The next byte of input, as KEY reads it; None at the end of the input
+    pub(crate) fn read_key(&mut self) -> Option<u8> {
+        if self.pending.is_empty() {
+            let line = self.input.read_line()?;
+            self.pending.extend(line);
+        }
+        self.pending.pop_front()
+    }
+
+    /// Whether KEY would return a character without waiting for input
+    pub(crate) fn key_ready(&self) -> bool {
+        !self.pending.is_empty() || self.input.ready()
+    }
+
+    /// Up to `max` bytes of the current line of input, as ACCEPT reads it. The line's
+    /// end is taken with it; a longer line's rest is left for the next read
+    pub(crate) fn accept(&mut self, max: usize) -> Vec<u8> {
+        let mut line = Vec::new();
+        while line.len() < max {
+            match self.read_key() {
+                Some(b'\n') | None => break,
+                Some(c) => line.push(c),
+            }
+        }
+        if line.len() == max {
+            // A line that just fits still has its end taken
+            if self.pending.front() == Some(&b'\r') {
+                self.pending.pop_front();
+            }
+            if self.pending.front() == Some(&b'\n') {
+                self.pending.pop_front();
+            }
+        }
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        line
+    }
+
+    /// Whether the input is the terminal READLINE edits lines on
+    pub(crate) fn reads_terminal(&self) -> bool {
+        matches!(self.input, Input::Stdin) && self.pending.is_empty()
+    }
+
+    /// The next line of input without its end, as REFILL reads it; None at the end of the input
+    pub(crate) fn next_line(&mut self) -> Option<Vec<u8>> {
+        if self.pending.is_empty() {
+            let line = self.input.read_line()?;
+            self.pending.extend(line);
+        }
+        let end = self.pending.iter().position(|&c| c == b'\n').map_or(self.pending.len(), |newline| newline + 1);
+        let mut line: Vec<u8> = self.pending.drain(..end).collect();
+        if line.last() == Some(&b'\n') {
+            line.pop();
+        }
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        Some(line)
+    }
+
+    /// Write output: into the innermost >STRING capture, or with none open to the output
+    pub fn write_output(&mut self, bytes: &[u8]) {
+        match self.captures.last_mut() {
+            Some(capture) => capture.extend_from_slice(bytes),
+            None => self.output.write(bytes),
+        }
+    }
+
+    /// Start capturing output, as >STRING does
+    pub(crate) fn begin_capture(&mut self) {
+        self.captures.push(Vec::new());
+    }
+
+    /// What was written since the innermost capture began, ending it (None if none is open)
+    pub(crate) fn end_capture(&mut self) -> Option<Vec<u8>> {
+        self.captures.pop()
+    }
+
+    /// Number of captures open
+    pub(crate) fn capture_depth(&self) -> usize {
+        self.captures.len()
+    }
+
+    /// Drop the captures opened since there were `depth`, when an error unwinds past
+    /// their STRING>
+    pub(crate) fn truncate_captures(&mut self, depth: usize) {
+        self.captures.truncate(depth);
+    }
+
+    /// Leave a word CREATE or DOES> made for the outer interpreter to define
+    pub(crate) fn push_created(&mut self, created: Created) {
+        self.created.push(created);
+    }
+
+    /// The words made since this was last called, oldest first
+    pub(crate) fn take_created(&mut self) -> Vec<Created> {
+        std::mem::take(&mut self.created)
+    }
+}
+
+/// What EVALUATE, NATIVE-XT and compiled code calling back into the dictionary
+/// (EXECUTE, CATCH, DEFER@, DEFER!) run on: the state of the source being interpreted
+#[derive(Clone, Copy)]
+pub(crate) struct Reentry {
+    pub dict: *mut Dictionary,
+    pub loop_stack: *mut LoopStack,
+    pub return_stack: *mut ReturnStack,
+    pub memory: *mut Memory,
+    pub included_files: *mut HashSet<String>,
+    pub config: CompilerConfig,
+}
+
+/// Points the session's re-entry at a runtime context until dropped, then puts back
+/// what it pointed at before
+pub(crate) struct Reentered {
+    memory: *mut Memory,
+    outer: Option<Reentry>,
+}
+
+impl Reentered {
+    pub(crate) fn new(ctx: &mut RuntimeContext, config: CompilerConfig, included_files: &mut HashSet<String>) -> Self {
+        let memory: *mut Memory = &mut *ctx.memory;
+        let reentry = Reentry {
+            dict: &mut *ctx.dict,
+            loop_stack: &mut *ctx.loop_stack,
+            return_stack: &mut *ctx.return_stack,
+            memory,
+            included_files,
+            config,
+        };
+        let outer = ctx.memory.session().reentry.replace(reentry);
+        Reentered { memory, outer }
+    }
+}
+
+impl Drop for Reentered {
+    fn drop(&mut self) {
+        // SAFETY: the context the guard was made from outlives it
+        unsafe { (*self.memory).session().reentry = self.outer };
+    }
+}
@@ -0,0 +1,203 @@
+// Source text and the place each token came from
+// The outer interpreter sees a file as one stream of whitespace-separated tokens
+// with comments removed; Source keeps, for every byte of that stream, the byte of
+// the original text it was taken from. Errors are reported from it as file:line:column,
+// and SOURCE and >IN are set from it so parsing words can read the text itself
+
+use std::fmt;
+
+use crate::QuarterError;
+
+/// Where a token starts: 1-based line and column in a file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+/// Words whose text up to a closing delimiter is a string, and that delimiter
+const STRING_WORDS: &[(&str, char)] = &[("S\"", '"'), (".\"", '"'), ("C\"", '"'), ("ABORT\"", '"'), (".(", ')')];
+
+/// Words followed by a name, which is never the start of a string
+const NAME_WORDS: &[&str] = &["POSTPONE", "[']", "'", "[COMPILE]", "CHAR", "[CHAR]"];
+
+/// Text ready for the interpreter: a file, a line typed at the terminal or an EVALUATE string
+pub struct Source {
+    // None for text that is not in a file
+    file: Option<String>,
+    contents: String,
+    // Byte offset in contents of the start of each line
+    line_starts: Vec<usize>,
+    // Comment-free text, one line after another separated by a newline
+    text: String,
+    // Byte offset in contents of each byte of text
+    origins: Vec<usize>,
+}
+
+impl Source {
+    pub fn new(file: &str, contents: &str) -> Self {
+        Self::build(Some(file.to_string()), contents)
+    }
+
+    /// Text with no file to report errors in
+    pub fn unnamed(contents: &str) -> Self {
+        Self::build(None, contents)
+    }
+
+    fn build(file: Option<String>, contents: &str) -> Self {
+        let mut line_starts = Vec::new();
+        let mut text = String::new();
+        let mut origins = Vec::new();
+        let mut start = 0;
+
+        for line in contents.split('\n') {
+            line_starts.push(start);
+            let mask = crate::comment_mask(line);
+            for ((offset, ch), keep) in line.char_indices().zip(mask) {
+                if keep {
+                    text.push(ch);
+                    origins.extend(start + offset..start + offset + ch.len_utf8());
+                }
+            }
+            // The newline, or the end of the last line
+            text.push('\n');
+            origins.push(start + line.len());
+            start += line.len() + 1;
+        }
+
+        Source { file, contents: contents.to_string(), line_starts, text, origins }
+    }
+
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// The text as it was given, comments and all
+    pub fn contents(&self) -> &str {
+        &self.contents
+    }
+
+    /// The tokens of the text, each a slice of the source text
+    pub fn tokens(&self) -> Vec<&str> {
+        self.tokens_from(0)
+    }
+
+    /// The tokens from byte `offset` of the comment-free text on. The text of a string
+    /// literal, from after the space that ends S" ." C" ABORT" or .( to the closing
+    /// delimiter on the same line, is a single token so the string keeps its spacing
+    pub(crate) fn tokens_from(&self, offset: usize) -> Vec<&str> {
+        let text = &self.text;
+        let mut tokens = Vec::new();
+        let mut i = offset.min(text.len());
+        let mut name_follows = false;
+
+        while let Some(start) = text[i..].find(|ch: char| !ch.is_whitespace()).map(|n| i + n) {
+            let end = text[start..].find(char::is_whitespace).map_or(text.len(), |n| start + n);
+            let token = &text[start..end];
+            tokens.push(token);
+            i = end;
+
+            let close = STRING_WORDS.iter()
+                .find(|(word, _)| !name_follows && token.eq_ignore_ascii_case(word))
+                .map(|&(_, close)| close);
+            name_follows = NAME_WORDS.iter().any(|word| token.eq_ignore_ascii_case(word));
+
+            if let Some(close) = close {
+                // One space ends the word; the string runs to the delimiter or the end of the line
+                if text[i..].starts_with([' ', '\t']) {
+                    i += 1;
+                    let line_end = text[i..].find('\n').map_or(text.len(), |n| i + n);
+                    let string_end = text[i..line_end].find(close).map_or(line_end, |n| i + n + 1);
+                    if string_end > i {
+                        tokens.push(&text[i..string_end]);
+                    }
+                    i = string_end;
+                }
+            }
+        }
+
+        tokens
+    }
+
+    /// Offset in the comment-free text of `token`, if it is one of the slices handed out by tokens()
+    pub(crate) fn text_offset(&self, token: &str) -> Option<usize> {
+        let offset = (token.as_ptr() as usize).checked_sub(self.text.as_ptr() as usize)?;
+        (offset < self.text.len()).then_some(offset)
+    }
+
+    /// Offset in contents just past `token` and the space after it, where the
+    /// interpreter leaves >IN once it has read the token
+    pub(crate) fn end_of(&self, token: &str) -> Option<usize> {
+        let last = self.text_offset(token)? + token.len().max(1) - 1;
+        let end = self.origins.get(last)? + 1;
+        match self.contents.as_bytes().get(end) {
+            Some(b' ' | b'\t') => Some(end + 1),
+            _ => Some(end),
+        }
+    }
+
+    /// Start and end in contents of the line holding byte `offset`
+    pub(crate) fn line_bounds(&self, offset: usize) -> (usize, usize) {
+        let index = self.line_starts.partition_point(|&start| start <= offset).max(1) - 1;
+        let end = self.line_starts.get(index + 1).map_or(self.contents.len(), |&next| next - 1);
+        (self.line_starts[index], end)
+    }
+
+    /// Offset in the comment-free text where interpretation carries on from byte
+    /// `offset` of contents, after a parsing word moved >IN there
+    pub(crate) fn resume_at(&self, offset: usize) -> usize {
+        let mut resume = self.origins.partition_point(|&origin| origin < offset);
+        while !self.text.is_char_boundary(resume) {
+            resume += 1;
+        }
+        resume
+    }
+
+    /// Where `token` starts, if it is one of the slices handed out by tokens() of a file
+    pub fn span(&self, token: &str) -> Option<Span> {
+        let file = self.file.as_ref()?;
+        let origin = *self.origins.get(self.text_offset(token)?)?;
+        let (start, _) = self.line_bounds(origin);
+        Some(Span {
+            file: file.clone(),
+            line: self.line_starts.partition_point(|&line_start| line_start <= origin),
+            column: self.contents[start..origin].chars().count() + 1,
+        })
+    }
+
+    /// Report `error`, raised while interpreting `tokens` (the rest of the file from
+    /// the construct that failed), at its place in the file with the line echoed.
+    /// An error already located in an INCLUDEd file is wrapped as included from here
+    pub fn locate(&self, error: QuarterError, tokens: &[&str]) -> QuarterError {
+        if matches!(error, QuarterError::Located { .. } | QuarterError::Included { .. }) {
+            return match tokens.first().and_then(|token| self.span(token)) {
+                Some(span) => QuarterError::Included { span, error: Box::new(error) },
+                None => error,
+            };
+        }
+
+        // An undefined word is usually further on than the construct that found it
+        let token = match &error {
+            QuarterError::UndefinedWord(name) => tokens.iter().find(|token| token.eq_ignore_ascii_case(name)),
+            _ => None,
+        }.or(tokens.first());
+        let Some((token, span)) = token.and_then(|token| Some((token, self.span(token)?))) else {
+            return error;
+        };
+
+        let (start, end) = self.line_bounds(self.line_starts[span.line - 1]);
+        QuarterError::Located {
+            line: self.contents[start..end].trim_end_matches('\r').to_string(),
+            width: token.chars().count(),
+            span,
+            error: Box::new(error),
+        }
+    }
+}
@@ -27,7 +27,9 @@ impl Stack {
 
     pub fn pop(&mut self, memory: &mut crate::Memory) -> Option<i64> {
         if self.sp == 0x000000 {
-            return None;  // Stack underflow
+            // Stack underflow: a primitive seeing None just returns, so also raise -4
+            memory.raise(-4);
+            return None;
         }
         // Move SP back one cell
         self.sp -= 8;
@@ -51,19 +53,17 @@ impl Stack {
         self.sp / 8
     }
 
-    pub fn print_stack(&self, memory: &crate::Memory) {
+    /// The stack as .S shows it: the depth, then each value from the bottom
+    pub fn format_stack(&self, memory: &crate::Memory) -> String {
         let depth = self.sp / 8;
-        if depth == 0 {
-            print!("<0> ");
-        } else {
-            print!("<{}> ", depth);
-            for i in 0..depth {
-                let addr = i * 8;
-                if let Ok(value) = memory.fetch(addr) {
-                    print!("{} ", value);
-                }
+        let mut text = format!("<{}> ", depth);
+        for i in 0..depth {
+            let addr = i * 8;
+            if let Ok(value) = memory.fetch(addr) {
+                text.push_str(&format!("{} ", value));
             }
         }
+        text
     }
 
     // New methods for stack pointer access
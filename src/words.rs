@@ -2,15 +2,8 @@
 
 use crate::LoopStack;
 use crate::stack::Stack;
-use std::cell::RefCell;
-use rustyline::DefaultEditor;
 use rustyline::error::ReadlineError;
 
-// Thread-local rustyline editor for REPL primitives
-thread_local! {
-    static READLINE_EDITOR: RefCell<Option<DefaultEditor>> = const { RefCell::new(None) };
-}
-
 // ============================================================================
 // External primitives from runtime.rs
 // These are linked in from libquarter_runtime.a via build.rs
@@ -61,6 +54,7 @@ unsafe extern "C" {
     pub fn quarter_c_store(memory: *mut u8, sp: *mut usize, rp: *mut usize);
     pub fn quarter_c_fetch(memory: *mut u8, sp: *mut usize, rp: *mut usize);
     pub fn quarter_base(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_state(memory: *mut u8, sp: *mut usize, rp: *mut usize);
 
     // Return stack
     pub fn quarter_to_r(memory: *mut u8, sp: *mut usize, rp: *mut usize);
@@ -74,198 +68,1128 @@ unsafe extern "C" {
     pub fn quarter_compare(memory: *mut u8, sp: *mut usize, rp: *mut usize);
     pub fn quarter_minus_trailing(memory: *mut u8, sp: *mut usize, rp: *mut usize);
     pub fn quarter_search(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+
+    // Double-cell arithmetic
+    pub fn quarter_um_star(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_m_star(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_um_slash_mod(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_sm_slash_rem(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_fm_slash_mod(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_d_plus(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_d_minus(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_dnegate(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_d_dot(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_2fetch(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_2store(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+
+    // Pictured numeric output
+    pub fn quarter_less_number_sign(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_number_sign(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_number_sign_s(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_hold(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_holds(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_sign(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_number_sign_greater(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+
+    // Floating point
+    pub fn quarter_f_plus(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_f_minus(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_f_star(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_f_slash(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_fnegate(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_fabs(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_fsqrt(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_fsin(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_fcos(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_floor(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_fround(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_fdup(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_fdrop(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_fswap(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_fover(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_fdepth(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_f_fetch(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_f_store(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_s_to_f(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_f_to_s(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_f_less(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_f_zero_less(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_f_zero_equal(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_f_dot(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+
+    // File access
+    pub fn quarter_open_file(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_create_file(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_close_file(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_delete_file(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_read_file(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_read_line(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_write_file(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_write_line(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_file_position(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_file_size(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_reposition_file(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+
+    // Memory allocation
+    pub fn quarter_allocate(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_free(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_resize(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+
+    // Exceptions
+    pub fn quarter_throw(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_catch_compiled(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+
+    // Input source
+    pub fn quarter_source(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_to_in(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_source_id(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_parse(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_parse_name(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_word(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_refill(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_save_input(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+    pub fn quarter_restore_input(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+
+    // Environment queries
+    pub fn quarter_environment_query(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+
+    // Native words
+    pub fn quarter_call_native(memory: *mut u8, sp: *mut usize, rp: *mut usize);
+}
+
+// Built-in word definitions
+pub fn dot(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    if let Some(value) = stack.pop(memory) {
+        memory.session().write_output(format!("{} ", value).as_bytes());
+    } else {
+        memory.raise(-4);
+    }
+}
+
+pub fn dot_s(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    let text = stack.format_stack(memory);
+    memory.session().write_output(text.as_bytes());
+}
+
+pub fn u_dot(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // U. ( u -- )
+    // Print unsigned number
+    if let Some(value) = stack.pop(memory) {
+        // Treat as unsigned by converting to u64
+        let unsigned_value = value as u64;
+        memory.session().write_output(format!("{} ", unsigned_value).as_bytes());
+    } else {
+        memory.raise(-4);
+    }
+}
+
+pub fn dot_r(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // .R ( n width -- )
+    // Print number right-justified in field of width
+    if let (Some(width), Some(value)) = (stack.pop(memory), stack.pop(memory)) {
+        let num_str = value.to_string();
+        let width = width as usize;
+        if num_str.len() < width {
+            // Pad with spaces on the left
+            memory.session().write_output(format!("{:>width$} ", num_str, width = width).as_bytes());
+        } else {
+            memory.session().write_output(format!("{} ", num_str).as_bytes());
+        }
+    } else {
+        memory.raise(-4);
+    }
+}
+
+pub fn u_dot_r(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // U.R ( u width -- )
+    // Print unsigned number right-justified in field of width
+    if let (Some(width), Some(value)) = (stack.pop(memory), stack.pop(memory)) {
+        let unsigned_value = value as u64;
+        let num_str = unsigned_value.to_string();
+        let width = width as usize;
+        if num_str.len() < width {
+            memory.session().write_output(format!("{:>width$} ", num_str, width = width).as_bytes());
+        } else {
+            memory.session().write_output(format!("{} ", num_str).as_bytes());
+        }
+    } else {
+        memory.raise(-4);
+    }
+}
+
+// Arithmetic Operations
+pub fn add(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
+        stack.push(a + b, memory);
+    } else {
+        memory.raise(-4);
+    }
+}
+
+pub fn subtract(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+
+    memory: &mut crate::Memory,
+) {
+    if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
+        stack.push(a - b, memory);
+    } else {
+        memory.raise(-4);
+    }
+}
+
+pub fn multiply(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+
+    memory: &mut crate::Memory,
+) {
+    if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
+        stack.push(a * b, memory);
+    } else {
+        memory.raise(-4);
+    }
+}
+
+pub fn divide(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
+        if b == 0 || (a == i64::MIN && b == -1) {
+            memory.raise(if b == 0 { -10 } else { -11 });
+            stack.push(a, memory);
+            stack.push(b, memory);
+        } else {
+            stack.push(a / b, memory);
+        }
+    } else {
+        memory.raise(-4);
+    }
+}
+
+pub fn slash_modulo(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+
+    memory: &mut crate::Memory,
+) {
+    if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
+        if b == 0 || (a == i64::MIN && b == -1) {
+            memory.raise(if b == 0 { -10 } else { -11 });
+            stack.push(a, memory);
+            stack.push(b, memory);
+        } else {
+            stack.push(a % b, memory);
+            stack.push(a / b, memory);
+        }
+    } else {
+        memory.raise(-4);
+    }
+}
+
+// */: Multiply-divide with intermediate product
+pub fn star_slash(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // */ ( n1 n2 n3 -- n1*n2/n3 )
+    // Multiply n1 by n2, then divide by n3
+    // Uses i128 intermediate to prevent overflow
+    if let (Some(n3), Some(n2), Some(n1)) = (stack.pop(memory), stack.pop(memory), stack.pop(memory)) {
+        if n3 == 0 {
+            memory.raise(-10);
+            stack.push(n1, memory);
+            stack.push(n2, memory);
+            stack.push(n3, memory);
+        } else {
+            let product = (n1 as i128) * (n2 as i128);
+            match i64::try_from(product / n3 as i128) {
+                Ok(result) => stack.push(result, memory),
+                Err(_) => {
+                    memory.raise(-11);
+                    stack.push(n1, memory);
+                    stack.push(n2, memory);
+                    stack.push(n3, memory);
+                }
+            }
+        }
+    } else {
+        memory.raise(-4);
+    }
+}
+
+// ============================================================================
+// Double-Cell Arithmetic
+// The arithmetic itself lives in runtime.rs; these wrappers check the stack
+// and hand the interpreter's memory and stack pointers to the quarter_* symbol
+// ============================================================================
+
+type RuntimePrimitive = unsafe extern "C" fn(*mut u8, *mut usize, *mut usize);
+
+/// Run a runtime.rs primitive on the interpreter stacks once `cells` values are present
+fn run_runtime_primitive(
+    primitive: RuntimePrimitive,
+    cells: usize,
+    stack: &mut Stack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    if stack.depth() < cells {
+        memory.raise(-4);
+        return;
+    }
+    unsafe {
+        primitive(memory.as_mut_ptr(), stack.sp_mut_ptr(), return_stack.rp_mut_ptr());
+    }
+}
+
+pub fn um_star(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // UM* ( u1 u2 -- ud )
+    run_runtime_primitive(quarter_um_star, 2, stack, return_stack, memory);
+}
+
+pub fn m_star(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // M* ( n1 n2 -- d )
+    run_runtime_primitive(quarter_m_star, 2, stack, return_stack, memory);
+}
+
+pub fn um_slash_mod(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // UM/MOD ( ud u1 -- u2 u3 )
+    run_runtime_primitive(quarter_um_slash_mod, 3, stack, return_stack, memory);
+}
+
+pub fn sm_slash_rem(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // SM/REM ( d n1 -- n2 n3 )
+    run_runtime_primitive(quarter_sm_slash_rem, 3, stack, return_stack, memory);
+}
+
+pub fn fm_slash_mod(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // FM/MOD ( d n1 -- n2 n3 )
+    run_runtime_primitive(quarter_fm_slash_mod, 3, stack, return_stack, memory);
+}
+
+pub fn d_plus(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // D+ ( d1 d2 -- d3 )
+    run_runtime_primitive(quarter_d_plus, 4, stack, return_stack, memory);
+}
+
+pub fn d_minus(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // D- ( d1 d2 -- d3 )
+    run_runtime_primitive(quarter_d_minus, 4, stack, return_stack, memory);
+}
+
+pub fn dnegate(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // DNEGATE ( d -- -d )
+    run_runtime_primitive(quarter_dnegate, 2, stack, return_stack, memory);
+}
+
+pub fn d_dot(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // D. ( d -- )
+    if let (Some(hi), Some(lo)) = (stack.pop(memory), stack.pop(memory)) {
+        let value = ((hi as i128) << 64) | (lo as u64 as i128);
+        memory.session().write_output(format!("{} ", value).as_bytes());
+    } else {
+        memory.raise(-4);
+    }
+}
+
+pub fn two_fetch(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // 2@ ( addr -- x1 x2 )
+    if let Some(addr) = stack.pop(memory) {
+        let addr = addr as usize;
+        match (memory.fetch(addr + 8), memory.fetch(addr)) {
+            (Ok(x1), Ok(x2)) => {
+                stack.push(x1, memory);
+                stack.push(x2, memory);
+            }
+            (Err(e), _) | (_, Err(e)) => memory.raise(e.code()),
+        }
+    } else {
+        memory.raise(-4);
+    }
+}
+
+pub fn two_store(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // 2! ( x1 x2 addr -- )
+    if let (Some(addr), Some(x2), Some(x1)) = (stack.pop(memory), stack.pop(memory), stack.pop(memory)) {
+        let addr = addr as usize;
+        if let Err(e) = memory.store(addr, x2).and_then(|_| memory.store(addr + 8, x1)) {
+            memory.raise(e.code());
+        }
+    } else {
+        memory.raise(-4);
+    }
+}
+
+// ============================================================================
+// Pictured Numeric Output
+// The hold buffer and its pointer live at fixed addresses managed by runtime.rs
+// ============================================================================
+
+pub fn less_number_sign(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // <# ( -- )
+    run_runtime_primitive(quarter_less_number_sign, 0, stack, return_stack, memory);
+}
+
+pub fn number_sign(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // # ( ud1 -- ud2 )
+    run_runtime_primitive(quarter_number_sign, 2, stack, return_stack, memory);
+}
+
+pub fn number_sign_s(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // #S ( ud1 -- 0 0 )
+    run_runtime_primitive(quarter_number_sign_s, 2, stack, return_stack, memory);
+}
+
+pub fn hold(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // HOLD ( char -- )
+    run_runtime_primitive(quarter_hold, 1, stack, return_stack, memory);
+}
+
+pub fn holds(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // HOLDS ( c-addr u -- )
+    run_runtime_primitive(quarter_holds, 2, stack, return_stack, memory);
+}
+
+pub fn sign(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // SIGN ( n -- )
+    run_runtime_primitive(quarter_sign, 1, stack, return_stack, memory);
+}
+
+pub fn number_sign_greater(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // #> ( xd -- c-addr u )
+    run_runtime_primitive(quarter_number_sign_greater, 2, stack, return_stack, memory);
+}
+
+/// Like run_runtime_primitive, but also checks the float stack depth
+fn run_float_primitive(
+    primitive: RuntimePrimitive,
+    cells: usize,
+    floats: usize,
+    stack: &mut Stack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    if memory.fdepth() < floats {
+        memory.raise(-45);
+        return;
+    }
+    run_runtime_primitive(primitive, cells, stack, return_stack, memory);
+}
+
+pub fn f_plus(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // F+ ( F: r1 r2 -- r3 )
+    run_float_primitive(quarter_f_plus, 0, 2, stack, return_stack, memory);
+}
+
+pub fn f_minus(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // F- ( F: r1 r2 -- r3 )
+    run_float_primitive(quarter_f_minus, 0, 2, stack, return_stack, memory);
+}
+
+pub fn f_star(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // F* ( F: r1 r2 -- r3 )
+    run_float_primitive(quarter_f_star, 0, 2, stack, return_stack, memory);
+}
+
+pub fn f_slash(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // F/ ( F: r1 r2 -- r3 )
+    run_float_primitive(quarter_f_slash, 0, 2, stack, return_stack, memory);
+}
+
+pub fn fnegate(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // FNEGATE ( F: r1 -- r2 )
+    run_float_primitive(quarter_fnegate, 0, 1, stack, return_stack, memory);
+}
+
+pub fn fabs(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // FABS ( F: r1 -- r2 )
+    run_float_primitive(quarter_fabs, 0, 1, stack, return_stack, memory);
+}
+
+pub fn fsqrt(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // FSQRT ( F: r1 -- r2 )
+    run_float_primitive(quarter_fsqrt, 0, 1, stack, return_stack, memory);
+}
+
+pub fn fsin(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // FSIN ( F: r1 -- r2 )
+    run_float_primitive(quarter_fsin, 0, 1, stack, return_stack, memory);
+}
+
+pub fn fcos(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // FCOS ( F: r1 -- r2 )
+    run_float_primitive(quarter_fcos, 0, 1, stack, return_stack, memory);
+}
+
+pub fn floor(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // FLOOR ( F: r1 -- r2 )
+    run_float_primitive(quarter_floor, 0, 1, stack, return_stack, memory);
+}
+
+pub fn fround(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // FROUND ( F: r1 -- r2 )
+    run_float_primitive(quarter_fround, 0, 1, stack, return_stack, memory);
+}
+
+pub fn fdup(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // FDUP ( F: r -- r r )
+    run_float_primitive(quarter_fdup, 0, 1, stack, return_stack, memory);
+}
+
+pub fn fdrop(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // FDROP ( F: r -- )
+    run_float_primitive(quarter_fdrop, 0, 1, stack, return_stack, memory);
+}
+
+pub fn fswap(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // FSWAP ( F: r1 r2 -- r2 r1 )
+    run_float_primitive(quarter_fswap, 0, 2, stack, return_stack, memory);
+}
+
+pub fn fover(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // FOVER ( F: r1 r2 -- r1 r2 r1 )
+    run_float_primitive(quarter_fover, 0, 2, stack, return_stack, memory);
+}
+
+pub fn fdepth(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // FDEPTH ( -- +n )
+    run_float_primitive(quarter_fdepth, 0, 0, stack, return_stack, memory);
+}
+
+pub fn f_fetch(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // F@ ( f-addr -- ) ( F: -- r )
+    run_float_primitive(quarter_f_fetch, 1, 0, stack, return_stack, memory);
+}
+
+pub fn f_store(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // F! ( f-addr -- ) ( F: r -- )
+    run_float_primitive(quarter_f_store, 1, 1, stack, return_stack, memory);
+}
+
+pub fn s_to_f(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // S>F ( n -- ) ( F: -- r )
+    run_float_primitive(quarter_s_to_f, 1, 0, stack, return_stack, memory);
+}
+
+pub fn f_to_s(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // F>S ( -- n ) ( F: r -- )
+    run_float_primitive(quarter_f_to_s, 0, 1, stack, return_stack, memory);
+}
+
+pub fn f_less(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // F< ( -- flag ) ( F: r1 r2 -- )
+    run_float_primitive(quarter_f_less, 0, 2, stack, return_stack, memory);
+}
+
+pub fn f_zero_less(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // F0< ( -- flag ) ( F: r -- )
+    run_float_primitive(quarter_f_zero_less, 0, 1, stack, return_stack, memory);
+}
+
+pub fn f_zero_equal(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // F0= ( -- flag ) ( F: r -- )
+    run_float_primitive(quarter_f_zero_equal, 0, 1, stack, return_stack, memory);
+}
+
+/// Format a float the way F. prints it: plain decimal for everyday
+/// magnitudes, exponent form otherwise, always with a '.' or 'e'
+pub fn format_float(r: f64) -> String {
+    let mut text = if r != 0.0 && (r.abs() >= 1e15 || r.abs() < 1e-4) {
+        format!("{:e}", r)
+    } else {
+        format!("{}", r)
+    };
+    if r.is_finite() && !text.contains(['.', 'e']) {
+        text.push('.');
+    }
+    text
+}
+
+pub fn f_dot(
+    _stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // F. ( F: r -- )
+    if let Some(r) = memory.fpop() {
+        memory.session().write_output(format!("{} ", format_float(r)).as_bytes());
+    } else {
+        memory.raise(-45);
+    }
+}
+
+pub fn open_file(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // OPEN-FILE ( c-addr u fam -- fileid ior )
+    run_runtime_primitive(quarter_open_file, 3, stack, return_stack, memory);
+}
+
+pub fn create_file(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // CREATE-FILE ( c-addr u fam -- fileid ior )
+    run_runtime_primitive(quarter_create_file, 3, stack, return_stack, memory);
+}
+
+pub fn close_file(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // CLOSE-FILE ( fileid -- ior )
+    run_runtime_primitive(quarter_close_file, 1, stack, return_stack, memory);
+}
+
+pub fn delete_file(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // DELETE-FILE ( c-addr u -- ior )
+    run_runtime_primitive(quarter_delete_file, 2, stack, return_stack, memory);
+}
+
+pub fn read_file(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // READ-FILE ( c-addr u1 fileid -- u2 ior )
+    run_runtime_primitive(quarter_read_file, 3, stack, return_stack, memory);
+}
+
+pub fn read_line(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // READ-LINE ( c-addr u1 fileid -- u2 flag ior )
+    run_runtime_primitive(quarter_read_line, 3, stack, return_stack, memory);
+}
+
+pub fn write_file(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // WRITE-FILE ( c-addr u fileid -- ior )
+    run_runtime_primitive(quarter_write_file, 3, stack, return_stack, memory);
+}
+
+pub fn write_line(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // WRITE-LINE ( c-addr u fileid -- ior )
+    run_runtime_primitive(quarter_write_line, 3, stack, return_stack, memory);
+}
+
+pub fn file_position(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // FILE-POSITION ( fileid -- ud ior )
+    run_runtime_primitive(quarter_file_position, 1, stack, return_stack, memory);
+}
+
+pub fn file_size(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // FILE-SIZE ( fileid -- ud ior )
+    run_runtime_primitive(quarter_file_size, 1, stack, return_stack, memory);
+}
+
+pub fn reposition_file(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // REPOSITION-FILE ( ud fileid -- ior )
+    run_runtime_primitive(quarter_reposition_file, 3, stack, return_stack, memory);
+}
+
+pub fn allocate(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // ALLOCATE ( u -- a-addr ior )
+    run_runtime_primitive(quarter_allocate, 1, stack, return_stack, memory);
+}
+
+pub fn free(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // FREE ( a-addr -- ior )
+    run_runtime_primitive(quarter_free, 1, stack, return_stack, memory);
+}
+
+pub fn resize(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // RESIZE ( a-addr1 u -- a-addr2 ior )
+    run_runtime_primitive(quarter_resize, 2, stack, return_stack, memory);
+}
+
+pub fn source(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // SOURCE ( -- c-addr u )
+    run_runtime_primitive(quarter_source, 0, stack, return_stack, memory);
+}
+
+pub fn to_in(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // >IN ( -- a-addr )
+    run_runtime_primitive(quarter_to_in, 0, stack, return_stack, memory);
+}
+
+pub fn source_id(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // SOURCE-ID ( -- 0 | -1 | fileid )
+    run_runtime_primitive(quarter_source_id, 0, stack, return_stack, memory);
 }
 
-// Built-in word definitions
-pub fn dot(
+pub fn parse(
     stack: &mut Stack,
     _loop_stack: &LoopStack,
-    _return_stack: &mut crate::ReturnStack,
+    return_stack: &mut crate::ReturnStack,
     memory: &mut crate::Memory,
 ) {
-    if let Some(value) = stack.pop(memory) {
-        print!("{} ", value);
-    } else {
-        println!("Stack underflow!");
-    }
+    // PARSE ( char "ccc<char>" -- c-addr u )
+    run_runtime_primitive(quarter_parse, 1, stack, return_stack, memory);
 }
 
-pub fn dot_s(
+pub fn parse_name(
     stack: &mut Stack,
     _loop_stack: &LoopStack,
-    _return_stack: &mut crate::ReturnStack,
+    return_stack: &mut crate::ReturnStack,
     memory: &mut crate::Memory,
 ) {
-    stack.print_stack(memory);
+    // PARSE-NAME ( "<spaces>name<space>" -- c-addr u )
+    run_runtime_primitive(quarter_parse_name, 0, stack, return_stack, memory);
 }
 
-pub fn u_dot(
+pub fn word(
     stack: &mut Stack,
     _loop_stack: &LoopStack,
-    _return_stack: &mut crate::ReturnStack,
+    return_stack: &mut crate::ReturnStack,
     memory: &mut crate::Memory,
 ) {
-    // U. ( u -- )
-    // Print unsigned number
-    if let Some(value) = stack.pop(memory) {
-        // Treat as unsigned by converting to u64
-        let unsigned_value = value as u64;
-        print!("{} ", unsigned_value);
-    } else {
-        println!("Stack underflow!");
-    }
+    // WORD ( char "<chars>ccc<char>" -- c-addr )
+    run_runtime_primitive(quarter_word, 1, stack, return_stack, memory);
 }
 
-pub fn dot_r(
+pub fn refill(
     stack: &mut Stack,
     _loop_stack: &LoopStack,
-    _return_stack: &mut crate::ReturnStack,
+    return_stack: &mut crate::ReturnStack,
     memory: &mut crate::Memory,
 ) {
-    // .R ( n width -- )
-    // Print number right-justified in field of width
-    if let (Some(width), Some(value)) = (stack.pop(memory), stack.pop(memory)) {
-        let num_str = value.to_string();
-        let width = width as usize;
-        if num_str.len() < width {
-            // Pad with spaces on the left
-            print!("{:>width$} ", num_str, width = width);
-        } else {
-            print!("{} ", num_str);
-        }
-    } else {
-        println!("Stack underflow!");
+    // REFILL ( -- flag )
+    refill_input(stack, return_stack, memory);
+}
+
+/// REFILL, reading the next line of the session's input once user input runs out
+fn refill_input(stack: &mut Stack, return_stack: &mut crate::ReturnStack, memory: &mut crate::Memory) {
+    run_runtime_primitive(quarter_refill, 0, stack, return_stack, memory);
+    if stack.peek(memory) == Some(0) && memory.input_source().id == 0 {
+        stack.pop(memory);
+        let flag = if memory.refill_terminal() { -1 } else { 0 };
+        stack.push(flag, memory);
     }
 }
 
-pub fn u_dot_r(
+pub fn save_input(
     stack: &mut Stack,
     _loop_stack: &LoopStack,
-    _return_stack: &mut crate::ReturnStack,
+    return_stack: &mut crate::ReturnStack,
     memory: &mut crate::Memory,
 ) {
-    // U.R ( u width -- )
-    // Print unsigned number right-justified in field of width
-    if let (Some(width), Some(value)) = (stack.pop(memory), stack.pop(memory)) {
-        let unsigned_value = value as u64;
-        let num_str = unsigned_value.to_string();
-        let width = width as usize;
-        if num_str.len() < width {
-            print!("{:>width$} ", num_str, width = width);
-        } else {
-            print!("{} ", num_str);
-        }
-    } else {
-        println!("Stack underflow!");
-    }
+    // SAVE-INPUT ( -- x1 ... xn n )
+    run_runtime_primitive(quarter_save_input, 0, stack, return_stack, memory);
 }
 
-// Arithmetic Operations
-pub fn add(
+pub fn restore_input(
     stack: &mut Stack,
     _loop_stack: &LoopStack,
-    _return_stack: &mut crate::ReturnStack,
+    return_stack: &mut crate::ReturnStack,
     memory: &mut crate::Memory,
 ) {
-    if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
-        stack.push(a + b, memory);
-    } else {
-        println!("Stack underflow!");
-    }
+    // RESTORE-INPUT ( x1 ... xn n -- flag )
+    let n = stack.peek(memory).unwrap_or(0).max(0) as usize;
+    run_runtime_primitive(quarter_restore_input, n + 1, stack, return_stack, memory);
 }
 
-pub fn subtract(
-    stack: &mut Stack,
-    _loop_stack: &LoopStack,
-    _return_stack: &mut crate::ReturnStack,
+// Conditional compilation
+// [IF] and [ELSE] skip source text by parsing names, reading on to the next
+// line as needed, so a skipped branch may span lines and hold words that are
+// not defined. Nested [IF] ... [THEN] pairs are skipped whole
 
+/// Skip names up to the [THEN] (or, if `stop_at_else`, the [ELSE]) that closes
+/// the current branch, or to the end of the text
+fn skip_conditional(
+    stop_at_else: bool,
+    stack: &mut Stack,
+    return_stack: &mut crate::ReturnStack,
     memory: &mut crate::Memory,
 ) {
-    if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
-        stack.push(a - b, memory);
-    } else {
-        println!("Stack underflow!");
+    let mut depth = 0;
+    loop {
+        run_runtime_primitive(quarter_parse_name, 0, stack, return_stack, memory);
+        let (Some(len), Some(addr)) = (stack.pop(memory), stack.pop(memory)) else {
+            return;
+        };
+        if len == 0 {
+            refill_input(stack, return_stack, memory);
+            if stack.pop(memory).unwrap_or(0) == 0 {
+                return;
+            }
+            continue;
+        }
+
+        let name = extract_string(memory, addr as usize, len as usize).unwrap_or_default();
+        if name.eq_ignore_ascii_case("[IF]") {
+            depth += 1;
+        } else if name.eq_ignore_ascii_case("[ELSE]") && stop_at_else && depth == 0 {
+            return;
+        } else if name.eq_ignore_ascii_case("[THEN]") {
+            if depth == 0 {
+                return;
+            }
+            depth -= 1;
+        }
     }
 }
 
-pub fn multiply(
+pub fn bracket_if(
     stack: &mut Stack,
     _loop_stack: &LoopStack,
-    _return_stack: &mut crate::ReturnStack,
-
+    return_stack: &mut crate::ReturnStack,
     memory: &mut crate::Memory,
 ) {
-    if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
-        stack.push(a * b, memory);
-    } else {
-        println!("Stack underflow!");
+    // [IF] ( flag -- ): carry on if flag is true, else skip to the matching [ELSE] or [THEN]
+    match stack.pop(memory) {
+        Some(0) => skip_conditional(true, stack, return_stack, memory),
+        Some(_) => {}
+        None => {
+            memory.raise(-4);
+        }
     }
 }
 
-pub fn divide(
+pub fn bracket_else(
     stack: &mut Stack,
     _loop_stack: &LoopStack,
-    _return_stack: &mut crate::ReturnStack,
+    return_stack: &mut crate::ReturnStack,
     memory: &mut crate::Memory,
 ) {
-    if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
-        if b == 0 {
-            print!("Division by zero!");
-            stack.push(a, memory);
-            stack.push(b, memory);
-        } else {
-            stack.push(a / b, memory);
-        }
-    } else {
-        print!("Stack underflow!");
-    }
+    // [ELSE] ( -- ): reached at the end of a true branch, so skip to the matching [THEN]
+    skip_conditional(false, stack, return_stack, memory);
 }
 
-pub fn slash_modulo(
-    stack: &mut Stack,
+pub fn bracket_then(
+    _stack: &mut Stack,
     _loop_stack: &LoopStack,
     _return_stack: &mut crate::ReturnStack,
-
-    memory: &mut crate::Memory,
+    _memory: &mut crate::Memory,
 ) {
-    if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
-        if b == 0 {
-            print!("Division by zero!");
-            stack.push(a, memory);
-            stack.push(b, memory);
-        } else {
-            stack.push(a % b, memory);
-            stack.push(a / b, memory);
-        }
-    } else {
-        print!("Stack underflow!");
-    }
+    // [THEN] ( -- ): only marks where a skipped branch ends
 }
 
-// */: Multiply-divide with intermediate product
-pub fn star_slash(
+// Environment queries
+pub fn environment_query(
     stack: &mut Stack,
     _loop_stack: &LoopStack,
-    _return_stack: &mut crate::ReturnStack,
+    return_stack: &mut crate::ReturnStack,
     memory: &mut crate::Memory,
 ) {
-    // */ ( n1 n2 n3 -- n1*n2/n3 )
-    // Multiply n1 by n2, then divide by n3
-    // Uses i128 intermediate to prevent overflow
-    if let (Some(n3), Some(n2), Some(n1)) = (stack.pop(memory), stack.pop(memory), stack.pop(memory)) {
-        if n3 == 0 {
-            println!("Division by zero!");
-            stack.push(n1, memory);
-            stack.push(n2, memory);
-            stack.push(n3, memory);
-        } else {
-            let product = (n1 as i128) * (n2 as i128);
-            let result = (product / n3 as i128) as i64;
-            stack.push(result, memory);
-        }
-    } else {
-        println!("Stack underflow!");
-    }
+    // ENVIRONMENT? ( c-addr u -- false | i*x true )
+    run_runtime_primitive(quarter_environment_query, 2, stack, return_stack, memory);
 }
 
 // Stack manipulation
@@ -278,7 +1202,7 @@ pub fn dup(
     if let Some(value) = stack.peek(memory) {
         stack.push(value, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -295,7 +1219,7 @@ pub fn question_dup(
             stack.push(value, memory);
         }
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -309,7 +1233,7 @@ pub fn swap(
         stack.push(a, memory);
         stack.push(b, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -326,7 +1250,7 @@ pub fn over(
         stack.push(a, memory);
         stack.push(b, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -343,7 +1267,7 @@ pub fn rot(
         stack.push(x3, memory);
         stack.push(x1, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -366,16 +1290,15 @@ pub fn pick(
             let sp = stack.get_sp();
             let addr = sp - (index + 1) * 8;
 
-            if let Ok(value) = memory.fetch(addr) {
-                stack.push(value, memory);
-            } else {
-                println!("Memory fetch error in PICK!");
+            match memory.fetch(addr) {
+                Ok(value) => stack.push(value, memory),
+                Err(e) => memory.raise(e.code()),
             }
         } else {
-            println!("Stack underflow in PICK!");
+            memory.raise(-4);
         }
     } else {
-        println!("Stack underflow in PICK!");
+        memory.raise(-4);
     }
 }
 
@@ -390,7 +1313,7 @@ pub fn less_than(
     if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
         stack.push(if a < b { -1 } else { 0 }, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -404,7 +1327,7 @@ pub fn greater_than(
     if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
         stack.push(if a > b { -1 } else { 0 }, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -417,7 +1340,7 @@ pub fn equal(
     if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
         stack.push(if a == b { -1 } else { 0 }, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -430,7 +1353,7 @@ pub fn not_equal(
     if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
         stack.push(if a != b { -1 } else { 0 }, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -443,7 +1366,7 @@ pub fn less_equal(
     if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
         stack.push(if a <= b { -1 } else { 0 }, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -456,7 +1379,7 @@ pub fn greater_equal(
     if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
         stack.push(if a >= b { -1 } else { 0 }, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -473,7 +1396,7 @@ pub fn u_less_than(
         let ub = b as u64;
         stack.push(if ua < ub { -1 } else { 0 }, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -486,7 +1409,7 @@ pub fn zero_equal(
     if let Some(a) = stack.pop(memory) {
         stack.push(if a == 0 { -1 } else { 0 }, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -499,7 +1422,7 @@ pub fn zero_less(
     if let Some(a) = stack.pop(memory) {
         stack.push(if a < 0 { -1 } else { 0 }, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -512,7 +1435,7 @@ pub fn zero_greater(
     if let Some(a) = stack.pop(memory) {
         stack.push(if a > 0 { -1 } else { 0 }, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -524,14 +1447,15 @@ pub fn mod_word(
 ) {
     if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
         if b == 0 {
-            print!("Division by zero!");
+            memory.raise(-10);
             stack.push(a, memory);
             stack.push(b, memory);
         } else {
-            stack.push(a % b, memory);
+            // MIN-INT MOD -1 is 0, though MIN-INT / -1 is out of range
+            stack.push(a.wrapping_rem(b), memory);
         }
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -544,7 +1468,7 @@ pub fn abs(
     if let Some(value) = stack.pop(memory) {
         stack.push(value.abs(), memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -557,7 +1481,7 @@ pub fn negate(
     if let Some(value) = stack.pop(memory) {
         stack.push(-value, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -570,7 +1494,7 @@ pub fn min(
     if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
         stack.push(if a < b { a } else { b }, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -583,7 +1507,7 @@ pub fn max(
     if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
         stack.push(if a > b { a } else { b }, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -596,7 +1520,7 @@ pub fn one_plus(
     if let Some(value) = stack.pop(memory) {
         stack.push(value + 1, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -609,7 +1533,7 @@ pub fn one_minus(
     if let Some(value) = stack.pop(memory) {
         stack.push(value - 1, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -622,7 +1546,7 @@ pub fn two_star(
     if let Some(value) = stack.pop(memory) {
         stack.push(value * 2, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -635,7 +1559,7 @@ pub fn two_slash(
     if let Some(value) = stack.pop(memory) {
         stack.push(value / 2, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -643,9 +1567,9 @@ pub fn cr(
     _stack: &mut Stack,
     _loop_stack: &LoopStack,
     _return_stack: &mut crate::ReturnStack,
-    _memory: &mut crate::Memory,
+    memory: &mut crate::Memory,
 ) {
-    println!();
+    memory.session().write_output(b"\n");
 }
 
 pub fn drop(
@@ -655,7 +1579,7 @@ pub fn drop(
     memory: &mut crate::Memory,
 ) {
     if stack.pop(memory).is_none() {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -681,7 +1605,7 @@ pub fn loop_i(
     if let Some(index) = loop_stack.get_index() {
         stack.push(index, memory);
     } else {
-        println!("Not in a loop!");
+        memory.raise(-26);
     }
 }
 
@@ -694,7 +1618,7 @@ pub fn loop_j(
     if let Some(index) = loop_stack.get_outer_index() {
         stack.push(index, memory);
     } else {
-        println!("Not in a nested loop!");
+        memory.raise(-26);
     }
 }
 
@@ -706,12 +1630,12 @@ pub fn emit(
 ) {
     if let Some(value) = stack.pop(memory) {
         if let Some(ch) = char::from_u32(value as u32) {
-            print!("{}", ch);
+            memory.session().write_output(ch.to_string().as_bytes());
         } else {
-            println!("Invalid character code: {}", value);
+            memory.raise(-24);
         }
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -721,9 +1645,9 @@ pub fn space(
     _stack: &mut Stack,
     _loop_stack: &LoopStack,
     _return_stack: &mut crate::ReturnStack,
-    _memory: &mut crate::Memory,
+    memory: &mut crate::Memory,
 ) {
-    print!(" ");
+    memory.session().write_output(b" ");
 }
 
 /// TYPE: Print string from memory
@@ -736,31 +1660,69 @@ pub fn type_word(
 ) {
     if let (Some(len), Some(addr)) = (stack.pop(memory), stack.pop(memory)) {
         if len < 0 {
-            eprintln!("TYPE: negative length");
+            memory.raise(-24);
             return;
         }
         let addr = addr as usize;
         let len = len as usize;
 
-        // Print each character from memory
+        // Each byte is a Latin-1 character
+        let mut text = String::with_capacity(len);
         for i in 0..len {
             match memory.fetch_byte(addr + i) {
-                Ok(byte) => {
-                    if let Some(ch) = char::from_u32(byte as u32) {
-                        print!("{}", ch);
-                    } else {
-                        print!("?");
-                    }
-                }
+                Ok(byte) => text.push(byte as u8 as char),
                 Err(e) => {
-                    eprintln!("\nTYPE: {}", e);
+                    memory.raise(e.code());
                     return;
                 }
             }
         }
+        memory.session().write_output(text.as_bytes());
     } else {
-        eprintln!("TYPE: stack underflow");
+        memory.raise(-4);
+    }
+}
+
+/// `>STRING`: Capture output instead of printing it, until the matching STRING>
+/// Stack: ( -- )
+pub fn to_string_word(
+    _stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    memory.session().begin_capture();
+}
+
+/// STRING>: End the innermost >STRING capture, leaving what was printed since in
+/// ALLOCATEd memory, which the caller FREEs
+/// Stack: ( -- c-addr u )
+pub fn string_from_word(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    let Some(text) = memory.session().end_capture() else {
+        // No >STRING to match
+        memory.raise(-22);
+        return;
+    };
+    stack.push(text.len() as i64, memory);
+    run_runtime_primitive(quarter_allocate, 1, stack, return_stack, memory);
+    let (Some(ior), Some(addr)) = (stack.pop(memory), stack.pop(memory)) else {
+        return;
+    };
+    if ior != 0 {
+        memory.raise(-59);
+        return;
     }
+    if memory.store_bytes(addr as usize, &text).is_err() {
+        memory.raise(-9);
+        return;
+    }
+    stack.push(addr, memory);
+    stack.push(text.len() as i64, memory);
 }
 
 pub fn compare(
@@ -778,7 +1740,7 @@ pub fn compare(
         stack.pop(memory),
     ) {
         if u1 < 0 || u2 < 0 {
-            eprintln!("COMPARE: negative length");
+            memory.raise(-24);
             return;
         }
 
@@ -802,7 +1764,7 @@ pub fn compare(
                     // If equal, continue to next byte
                 }
                 (Err(e), _) | (_, Err(e)) => {
-                    eprintln!("COMPARE: {}", e);
+                    memory.raise(e.code());
                     return;
                 }
             }
@@ -817,7 +1779,7 @@ pub fn compare(
             stack.push(0, memory);
         }
     } else {
-        eprintln!("COMPARE: stack underflow");
+        memory.raise(-4);
     }
 }
 
@@ -831,7 +1793,7 @@ pub fn minus_trailing(
     // Remove trailing spaces from string
     if let (Some(u), Some(addr)) = (stack.pop(memory), stack.pop(memory)) {
         if u < 0 {
-            eprintln!("-TRAILING: negative length");
+            memory.raise(-24);
             return;
         }
 
@@ -849,7 +1811,7 @@ pub fn minus_trailing(
                     len -= 1;
                 }
                 Err(e) => {
-                    eprintln!("-TRAILING: {}", e);
+                    memory.raise(e.code());
                     return;
                 }
             }
@@ -858,7 +1820,7 @@ pub fn minus_trailing(
         stack.push(addr, memory);
         stack.push(len as i64, memory);
     } else {
-        eprintln!("-TRAILING: stack underflow");
+        memory.raise(-4);
     }
 }
 
@@ -877,7 +1839,7 @@ pub fn search(
         stack.pop(memory),
     ) {
         if haystack_len < 0 || needle_len < 0 {
-            eprintln!("SEARCH: negative length");
+            memory.raise(-24);
             return;
         }
 
@@ -912,7 +1874,7 @@ pub fn search(
                             }
                         }
                         (Err(e), _) | (_, Err(e)) => {
-                            eprintln!("SEARCH: {}", e);
+                            memory.raise(e.code());
                             return;
                         }
                     }
@@ -933,7 +1895,7 @@ pub fn search(
         stack.push(haystack_len, memory);
         stack.push(0, memory); // FALSE
     } else {
-        eprintln!("SEARCH: stack underflow");
+        memory.raise(-4);
     }
 }
 
@@ -943,16 +1905,77 @@ pub fn key(
     _return_stack: &mut crate::ReturnStack,
     memory: &mut crate::Memory,
 ) {
-    use std::io::Read;
+    // KEY ( -- char )
+    // Read a byte of input; 0 at the end of the input
+    let c = memory.session().read_key().unwrap_or(0);
+    stack.push(c as i64, memory);
+}
 
-    let mut buffer = [0; 1];
-    match std::io::stdin().read_exact(&mut buffer) {
-        Ok(_) => stack.push(buffer[0] as i64, memory),
-        Err(_) => {
-            // EOF or error - push 0
-            stack.push(0, memory);
+/// KEY?: Whether KEY would return without waiting
+/// Stack: ( -- flag )
+pub fn key_question(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    let ready = memory.session().key_ready();
+    stack.push(if ready { -1 } else { 0 }, memory);
+}
+
+/// EKEY: Read a keyboard event, which is a whole character: the code point of the
+/// UTF-8 sequence next in the input (a byte that doesn't start one is its own event)
+/// Stack: ( -- x )
+pub fn ekey(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    let session = memory.session();
+    let Some(first) = session.read_key() else {
+        stack.push(0, memory);
+        return;
+    };
+    let len = match first {
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF7 => 4,
+        _ => 1,
+    };
+    let mut bytes = vec![first];
+    while bytes.len() < len {
+        match session.read_key() {
+            Some(c) => bytes.push(c),
+            None => break,
         }
     }
+    let code = match std::str::from_utf8(&bytes) {
+        Ok(text) => text.chars().next().map_or(0, |c| c as i64),
+        Err(_) => first as i64,
+    };
+    stack.push(code, memory);
+}
+
+/// ACCEPT: Read a line of input into the buffer at c-addr, at most +n1 characters;
+/// +n2 is how many were read. The line's end is not stored
+/// Stack: ( c-addr +n1 -- +n2 )
+pub fn accept(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    let (Some(max), Some(addr)) = (stack.pop(memory), stack.pop(memory)) else {
+        memory.raise(-4);
+        return;
+    };
+    let line = memory.session().accept(max.max(0) as usize);
+    if memory.store_bytes(addr as usize, &line).is_err() {
+        memory.raise(-9);
+        return;
+    }
+    stack.push(line.len() as i64, memory);
 }
 
 pub fn and(
@@ -964,7 +1987,7 @@ pub fn and(
     if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
         stack.push(a & b, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -977,7 +2000,7 @@ pub fn or(
     if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
         stack.push(a | b, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -990,7 +2013,7 @@ pub fn xor(
     if let (Some(b), Some(a)) = (stack.pop(memory), stack.pop(memory)) {
         stack.push(a ^ b, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -1003,7 +2026,7 @@ pub fn invert(
     if let Some(n) = stack.pop(memory) {
         stack.push(!n, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -1016,7 +2039,7 @@ pub fn lshift(
     if let (Some(u), Some(n)) = (stack.pop(memory), stack.pop(memory)) {
         stack.push(n << u, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -1029,7 +2052,7 @@ pub fn rshift(
     if let (Some(u), Some(n)) = (stack.pop(memory), stack.pop(memory)) {
         stack.push(n >> u, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -1043,7 +2066,7 @@ pub fn to_r(
     if let Some(n) = stack.pop(memory) {
         return_stack.push(n, memory);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -1056,7 +2079,7 @@ pub fn r_from(
     if let Some(n) = return_stack.pop(memory) {
         stack.push(n, memory);
     } else {
-        println!("Return stack underflow!");
+        memory.raise(-6);
     }
 }
 
@@ -1069,7 +2092,7 @@ pub fn r_fetch(
     if let Some(n) = return_stack.peek(memory) {
         stack.push(n, memory);
     } else {
-        println!("Return stack underflow!");
+        memory.raise(-6);
     }
 }
 
@@ -1105,10 +2128,10 @@ pub fn store(
     if let (Some(addr), Some(value)) = (stack.pop(memory), stack.pop(memory)) {
         match memory.store(addr as usize, value) {
             Ok(_) => {}
-            Err(e) => println!("{}", e),
+            Err(e) => memory.raise(e.code()),
         }
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -1122,10 +2145,10 @@ pub fn fetch(
     if let Some(addr) = stack.pop(memory) {
         match memory.fetch(addr as usize) {
             Ok(value) => stack.push(value, memory),
-            Err(e) => println!("{}", e),
+            Err(e) => memory.raise(e.code()),
         }
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -1139,10 +2162,10 @@ pub fn c_store(
     if let (Some(addr), Some(value)) = (stack.pop(memory), stack.pop(memory)) {
         match memory.store_byte(addr as usize, value) {
             Ok(_) => {}
-            Err(e) => println!("{}", e),
+            Err(e) => memory.raise(e.code()),
         }
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -1156,10 +2179,10 @@ pub fn c_fetch(
     if let Some(addr) = stack.pop(memory) {
         match memory.fetch_byte(addr as usize) {
             Ok(value) => stack.push(value, memory),
-            Err(e) => println!("{}", e),
+            Err(e) => memory.raise(e.code()),
         }
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -1187,7 +2210,7 @@ pub fn sp_store(
     if let Some(addr) = stack.pop(memory) {
         stack.set_sp(addr as usize);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -1214,7 +2237,7 @@ pub fn rp_store(
     if let Some(addr) = stack.pop(memory) {
         return_stack.set_rp(addr as usize);
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -1242,10 +2265,10 @@ pub fn allot(
     if let Some(n) = stack.pop(memory) {
         match memory.allot(n) {
             Ok(_) => {}
-            Err(e) => println!("{}", e),
+            Err(e) => memory.raise(e.code()),
         }
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -1264,13 +2287,13 @@ pub fn comma(
                 // Advance dictionary pointer by 8 bytes (one cell)
                 match memory.allot(8) {
                     Ok(_) => {}
-                    Err(e) => println!("{}", e),
+                    Err(e) => memory.raise(e.code()),
                 }
             }
-            Err(e) => println!("{}", e),
+            Err(e) => memory.raise(e.code()),
         }
     } else {
-        println!("Stack underflow!");
+        memory.raise(-4);
     }
 }
 
@@ -1285,6 +2308,45 @@ pub fn base(
     stack.push(memory.base(), memory);
 }
 
+pub fn state(
+    stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // STATE ( -- a-addr )
+    // Push address of compilation state (true while compiling)
+    stack.push(memory.state(), memory);
+}
+
+pub fn left_bracket(
+    _stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // [ ( -- ) - immediate
+    // Enter interpretation state
+    let state_addr = memory.state() as usize;
+    if let Err(e) = memory.store(state_addr, 0) {
+        memory.raise(e.code());
+    }
+}
+
+pub fn right_bracket(
+    _stack: &mut Stack,
+    _loop_stack: &LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    // ] ( -- )
+    // Enter compilation state
+    let state_addr = memory.state() as usize;
+    if let Err(e) = memory.store(state_addr, -1) {
+        memory.raise(e.code());
+    }
+}
+
 /// >NUMBER: ( ud1-lo ud1-hi c-addr u -- ud2-lo ud2-hi c-addr u )
 ///
 /// Convert string to number with accumulation using double-cell unsigned arithmetic.
@@ -1299,30 +2361,33 @@ pub fn to_number(
     // Pop arguments: u1, c-addr1, ud1-hi, ud1-lo
     let u1 = match stack.pop(memory) {
         Some(n) => n as usize,
-        None => { println!("Stack underflow in >NUMBER"); return; }
+        None => return,
     };
     let c_addr1 = match stack.pop(memory) {
         Some(n) => n as usize,
-        None => { println!("Stack underflow in >NUMBER"); return; }
+        None => return,
     };
     let ud1_hi = match stack.pop(memory) {
         Some(n) => n as u64,
-        None => { println!("Stack underflow in >NUMBER"); return; }
+        None => return,
     };
     let ud1_lo = match stack.pop(memory) {
         Some(n) => n as u64,
-        None => { println!("Stack underflow in >NUMBER"); return; }
+        None => return,
     };
 
     // Get BASE value
     let base_addr = memory.base() as usize;
     let base_val = match memory.fetch(base_addr) {
         Ok(n) => n as u32,
-        Err(e) => { println!("Error fetching BASE: {}", e); return; }
+        Err(e) => {
+            memory.raise(e.code());
+            return;
+        }
     };
 
     if !(2..=36).contains(&base_val) {
-        println!("Invalid BASE value: {}", base_val);
+        memory.raise(-24);
         return;
     }
 
@@ -1610,9 +2675,9 @@ pub unsafe extern "C" fn quarter_depth(memory: *mut u8, sp: *mut usize, _rp: *mu
 // ============================================================================
 
 // /MOD operation using two-result macro
-two_result_binary_op!(quarter_slash_mod, |a, b| {
+two_result_binary_op!(quarter_slash_mod, |a: i64, b| {
     if b != 0 {
-        (a % b, a / b)  // (remainder, quotient)
+        (a.wrapping_rem(b), a.wrapping_div(b))  // (remainder, quotient)
     } else {
         (0, 0)  // Division by zero protection
     }
@@ -1635,8 +2700,9 @@ pub unsafe extern "C" fn quarter_i(memory: *mut u8, sp: *mut usize, _rp: *mut us
     unsafe {
         let sp_val = *sp;
         debug_assert!(sp_val % 8 == 0, "Misaligned stack pointer: 0x{:x}", sp_val);
+        let session = (memory.add(crate::SESSION_ADDR) as *const i64).read_unaligned() as *mut crate::Session;
         let dest = memory.add(sp_val) as *mut i64;
-        dest.write_unaligned(0);  // Placeholder
+        dest.write_unaligned((*session).read_key().unwrap_or(0) as i64);
         let new_sp = sp_val + 8;
         debug_assert!(new_sp % 8 == 0, "Stack pointer misalignment after I");
         debug_assert!(new_sp <= DATA_STACK_END, "Stack overflow after I");
@@ -1656,8 +2722,9 @@ pub unsafe extern "C" fn quarter_j(memory: *mut u8, sp: *mut usize, _rp: *mut us
     unsafe {
         let sp_val = *sp;
         debug_assert!(sp_val % 8 == 0, "Misaligned stack pointer: 0x{:x}", sp_val);
+        let session = (memory.add(crate::SESSION_ADDR) as *const i64).read_unaligned() as *mut crate::Session;
         let dest = memory.add(sp_val) as *mut i64;
-        dest.write_unaligned(0);  // Placeholder
+        dest.write_unaligned((*session).read_key().unwrap_or(0) as i64);
         let new_sp = sp_val + 8;
         debug_assert!(new_sp % 8 == 0, "Stack pointer misalignment after J");
         debug_assert!(new_sp <= DATA_STACK_END, "Stack overflow after J");
@@ -1669,6 +2736,23 @@ pub unsafe extern "C" fn quarter_j(memory: *mut u8, sp: *mut usize, _rp: *mut us
 // I/O Operations
 // ============================================================================
 
+/// Write compiled code's output to the session of the interpreter whose memory it runs
+/// on. The runtime library's output words call this through the output handler cell
+/// # Safety
+/// The caller must ensure:
+/// - `memory` points to the memory of an interpreter, whose session is in its system cell
+/// - `bytes` points to `len` readable bytes
+pub unsafe extern "C" fn quarter_write_output(memory: *mut u8, bytes: *const u8, len: usize) {
+    unsafe { write_jit_output(memory, std::slice::from_raw_parts(bytes, len)) };
+}
+
+unsafe fn write_jit_output(memory: *mut u8, bytes: &[u8]) {
+    unsafe {
+        let session = (memory.add(crate::SESSION_ADDR) as *const i64).read_unaligned() as *mut crate::Session;
+        (*session).write_output(bytes);
+    }
+}
+
 /// JIT-callable emit: ( c -- )
 /// Outputs character
 /// # Safety
@@ -1686,7 +2770,7 @@ pub unsafe extern "C" fn quarter_emit(memory: *mut u8, sp: *mut usize, _rp: *mut
         let addr = memory.add(sp_val - 8) as *const i64;
         let code = addr.read_unaligned();
         if let Some(ch) = char::from_u32(code as u32) {
-            print!("{}", ch);
+            write_jit_output(memory, ch.to_string().as_bytes());
         }
         let new_sp = sp_val - 8;
         debug_assert!(new_sp % 8 == 0, "Stack pointer misalignment after EMIT");
@@ -1698,20 +2782,20 @@ pub unsafe extern "C" fn quarter_emit(memory: *mut u8, sp: *mut usize, _rp: *mut
 /// Outputs a space character
 /// # Safety
 /// The caller must ensure:
-/// - All pointers are valid (though this function doesn't use them)
+/// - `memory` points to the memory of an interpreter, whose session is in its system cell
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn quarter_space(_memory: *mut u8, _sp: *mut usize, _rp: *mut usize) {
-    print!(" ");
+pub unsafe extern "C" fn quarter_space(memory: *mut u8, _sp: *mut usize, _rp: *mut usize) {
+    unsafe { write_jit_output(memory, b" ") };
 }
 
 /// JIT-callable cr: ( -- )
 /// Outputs newline
 /// # Safety
 /// The caller must ensure:
-/// - All pointers are valid (though this function doesn't use them)
+/// - `memory` points to the memory of an interpreter, whose session is in its system cell
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn quarter_cr(_memory: *mut u8, _sp: *mut usize, _rp: *mut usize) {
-    println!();
+pub unsafe extern "C" fn quarter_cr(memory: *mut u8, _sp: *mut usize, _rp: *mut usize) {
+    unsafe { write_jit_output(memory, b"\n") };
 }
 
 /// JIT-callable dot: ( n -- )
@@ -1730,7 +2814,7 @@ pub unsafe extern "C" fn quarter_dot(memory: *mut u8, sp: *mut usize, _rp: *mut
         }
         let addr = memory.add(sp_val - 8) as *const i64;
         let value = addr.read_unaligned();
-        print!("{} ", value);
+        write_jit_output(memory, format!("{} ", value).as_bytes());
         let new_sp = sp_val - 8;
         debug_assert!(new_sp % 8 == 0, "Stack pointer misalignment after .");
         *sp = new_sp;
@@ -1738,18 +2822,19 @@ pub unsafe extern "C" fn quarter_dot(memory: *mut u8, sp: *mut usize, _rp: *mut
 }
 
 /// JIT-callable key: ( -- c )
-/// Reads character (placeholder - needs proper I/O handling)
+/// Reads a byte from the session's input; 0 at the end of the input
 /// # Safety
 /// The caller must ensure:
-/// - `memory` points to a valid memory buffer of at least 8MB
+/// - `memory` points to the memory of an interpreter, whose session is in its system cell
 /// - `sp` points to a valid stack pointer within data stack bounds (0-65535)
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn quarter_key(memory: *mut u8, sp: *mut usize, _rp: *mut usize) {
     unsafe {
         let sp_val = *sp;
         debug_assert!(sp_val % 8 == 0, "Misaligned stack pointer: 0x{:x}", sp_val);
+        let session = (memory.add(crate::SESSION_ADDR) as *const i64).read_unaligned() as *mut crate::Session;
         let dest = memory.add(sp_val) as *mut i64;
-        dest.write_unaligned(0);  // Placeholder
+        dest.write_unaligned((*session).read_key().unwrap_or(0) as i64);
         let new_sp = sp_val + 8;
         debug_assert!(new_sp % 8 == 0, "Stack pointer misalignment after KEY");
         debug_assert!(new_sp <= DATA_STACK_END, "Stack overflow after KEY");
@@ -1773,7 +2858,7 @@ pub unsafe extern "C" fn quarter_u_dot(memory: *mut u8, sp: *mut usize, _rp: *mu
         let addr = memory.add(sp_val - 8) as *const i64;
         let value = addr.read_unaligned();
         let unsigned_value = value as u64;
-        print!("{} ", unsigned_value);
+        write_jit_output(memory, format!("{} ", unsigned_value).as_bytes());
         let new_sp = sp_val - 8;
         debug_assert!(new_sp % 8 == 0, "Stack pointer misalignment after U.");
         *sp = new_sp;
@@ -1801,9 +2886,9 @@ pub unsafe extern "C" fn quarter_dot_r(memory: *mut u8, sp: *mut usize, _rp: *mu
 
         let num_str = value.to_string();
         if num_str.len() < width {
-            print!("{:>width$} ", num_str, width = width);
+            write_jit_output(memory, format!("{:>width$} ", num_str, width = width).as_bytes());
         } else {
-            print!("{} ", num_str);
+            write_jit_output(memory, format!("{} ", num_str).as_bytes());
         }
         let new_sp = sp_val - 16;
         debug_assert!(new_sp % 8 == 0, "Stack pointer misalignment after .R");
@@ -1833,9 +2918,9 @@ pub unsafe extern "C" fn quarter_u_dot_r(memory: *mut u8, sp: *mut usize, _rp: *
         let unsigned_value = value as u64;
         let num_str = unsigned_value.to_string();
         if num_str.len() < width {
-            print!("{:>width$} ", num_str, width = width);
+            write_jit_output(memory, format!("{:>width$} ", num_str, width = width).as_bytes());
         } else {
-            print!("{} ", num_str);
+            write_jit_output(memory, format!("{} ", num_str).as_bytes());
         }
         let new_sp = sp_val - 16;
         debug_assert!(new_sp % 8 == 0, "Stack pointer misalignment after U.R");
@@ -1913,8 +2998,8 @@ pub unsafe extern "C" fn quarter_allot(memory: *mut u8, sp: *mut usize, _rp: *mu
         // Calculate new dp
         let new_dp = dp_val + n;
 
-        // Check for overflow (8MB limit)
-        if new_dp >= 8 * 1024 * 1024 {
+        // Check for overflow into the ALLOCATE heap (0x400000)
+        if new_dp >= 0x400000 {
             eprintln!("Dictionary overflow");
             return;
         }
@@ -1956,11 +3041,79 @@ pub unsafe extern "C" fn quarter_comma(memory: *mut u8, sp: *mut usize, _rp: *mu
             let dest = memory.add(dp_val as usize) as *mut i64;
             dest.write_unaligned(n);
 
-            // Advance dp by 8 bytes
-            let new_dp = dp_val + 8;
-            dp_ptr.write_unaligned(new_dp);
-        }
-    }
+            // Advance dp by 8 bytes
+            let new_dp = dp_val + 8;
+            dp_ptr.write_unaligned(new_dp);
+        }
+    }
+}
+
+// ============================================================================
+// Execution Token Primitives (JIT callbacks into the dictionary)
+// ============================================================================
+
+/// Run an xt-consuming AST node (EXECUTE, DEFER@, DEFER!, CATCH) on behalf of JIT code
+/// The xt is resolved through the dictionary at call time, so deferred words
+/// pick up the target most recently set with IS or DEFER!
+/// An error becomes a pending throw code, which the JIT code unwinds with
+/// # Safety
+/// The caller must ensure:
+/// - `memory` is the byte buffer of a `Memory`, whose session cell points at its session
+/// - `sp` and `rp` point to the stack pointers of the current execution context's memory
+unsafe fn run_xt_node(node: crate::ast::AstNode, memory: *mut u8, sp: *mut usize, rp: *mut usize) {
+    unsafe {
+        let session = (memory.add(crate::SESSION_ADDR) as *const i64).read_unaligned() as *mut crate::Session;
+        match (*session).reentry {
+            Some(reentry) => {
+                let return_stack = &mut *reentry.return_stack;
+                let mut stack = Stack::new();
+                stack.set_sp(*sp);
+                return_stack.set_rp(*rp);
+
+                if let Err(e) = node.execute(&mut stack, &*reentry.dict, &mut *reentry.loop_stack, return_stack, &mut *reentry.memory) {
+                    (*reentry.memory).raise(e.code());
+                }
+
+                *sp = stack.get_sp();
+                *rp = return_stack.get_rp();
+            }
+            None => {
+                eprintln!("No execution context available");
+            }
+        }
+    }
+}
+
+/// JIT-callable execute: ( xt -- )
+/// # Safety
+/// See `run_xt_node`
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_execute(memory: *mut u8, sp: *mut usize, rp: *mut usize) {
+    unsafe { run_xt_node(crate::ast::AstNode::Execute, memory, sp, rp) }
+}
+
+/// JIT-callable CATCH: ( i*x xt -- j*x 0 | i*x n )
+/// # Safety
+/// See `run_xt_node`
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_catch(memory: *mut u8, sp: *mut usize, rp: *mut usize) {
+    unsafe { run_xt_node(crate::ast::AstNode::Catch, memory, sp, rp) }
+}
+
+/// JIT-callable DEFER@: ( xt1 -- xt2 )
+/// # Safety
+/// See `run_xt_node`
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_defer_fetch(memory: *mut u8, sp: *mut usize, rp: *mut usize) {
+    unsafe { run_xt_node(crate::ast::AstNode::DeferFetch, memory, sp, rp) }
+}
+
+/// JIT-callable DEFER!: ( xt2 xt1 -- )
+/// # Safety
+/// See `run_xt_node`
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn quarter_defer_store(memory: *mut u8, sp: *mut usize, rp: *mut usize) {
+    unsafe { run_xt_node(crate::ast::AstNode::DeferStore, memory, sp, rp) }
 }
 
 // ============================================================================
@@ -1984,7 +3137,7 @@ pub fn llvm_create_context_word(
     _return_stack: &mut crate::ReturnStack,
     memory: &mut crate::Memory,
 ) {
-    match crate::llvm_forth::llvm_create_context() {
+    match crate::llvm_forth::llvm_create_context(&mut memory.session().llvm) {
         Ok(handle) => stack.push(handle, memory),
         Err(e) => eprintln!("LLVM-CREATE-CONTEXT error: {}", e),
     }
@@ -2005,7 +3158,7 @@ pub fn llvm_create_module_word(
     ) {
         match extract_string(memory, name_addr as usize, name_len as usize) {
             Ok(name) => {
-                match crate::llvm_forth::llvm_create_module(ctx_handle, &name) {
+                match crate::llvm_forth::llvm_create_module(&mut memory.session().llvm, ctx_handle, &name) {
                     Ok(handle) => stack.push(handle, memory),
                     Err(e) => eprintln!("LLVM-CREATE-MODULE error: {}", e),
                 }
@@ -2033,7 +3186,7 @@ pub fn llvm_declare_external_word(
     ) {
         match extract_string(memory, name_addr as usize, name_len as usize) {
             Ok(name) => {
-                match crate::llvm_forth::llvm_declare_external(module_handle, ctx_handle, &name) {
+                match crate::llvm_forth::llvm_declare_external(&mut memory.session().llvm, module_handle, ctx_handle, &name) {
                     Ok(_) => {},
                     Err(e) => eprintln!("LLVM-DECLARE-EXTERNAL error: {}", e),
                 }
@@ -2054,7 +3207,7 @@ pub fn llvm_create_builder_word(
     memory: &mut crate::Memory,
 ) {
     if let Some(ctx_handle) = stack.pop(memory) {
-        match crate::llvm_forth::llvm_create_builder(ctx_handle) {
+        match crate::llvm_forth::llvm_create_builder(&mut memory.session().llvm, ctx_handle) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-CREATE-BUILDER error: {}", e),
         }
@@ -2079,7 +3232,7 @@ pub fn llvm_create_function_word(
     ) {
         match extract_string(memory, name_addr as usize, name_len as usize) {
             Ok(name) => {
-                match crate::llvm_forth::llvm_create_function(module_handle, ctx_handle, &name) {
+                match crate::llvm_forth::llvm_create_function(&mut memory.session().llvm, module_handle, ctx_handle, &name) {
                     Ok(handle) => stack.push(handle, memory),
                     Err(e) => eprintln!("LLVM-CREATE-FUNCTION error: {}", e),
                 }
@@ -2106,7 +3259,7 @@ pub fn llvm_module_get_function_word(
     ) {
         match extract_string(memory, name_addr as usize, name_len as usize) {
             Ok(name) => {
-                match crate::llvm_forth::llvm_get_function(module_handle, &name) {
+                match crate::llvm_forth::llvm_get_function(&mut memory.session().llvm, module_handle, &name) {
                     Ok(handle) => stack.push(handle, memory),
                     Err(_) => {
                         // Function not found - this is expected when looking up words
@@ -2141,7 +3294,7 @@ pub fn llvm_create_block_word(
     ) {
         match extract_string(memory, name_addr as usize, name_len as usize) {
             Ok(name) => {
-                match crate::llvm_forth::llvm_create_block(ctx_handle, fn_handle, &name) {
+                match crate::llvm_forth::llvm_create_block(&mut memory.session().llvm, ctx_handle, fn_handle, &name) {
                     Ok(handle) => stack.push(handle, memory),
                     Err(e) => eprintln!("LLVM-CREATE-BLOCK error: {}", e),
                 }
@@ -2165,7 +3318,7 @@ pub fn llvm_position_at_end_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        if let Err(e) = crate::llvm_forth::llvm_position_at_end(builder_handle, block_handle) {
+        if let Err(e) = crate::llvm_forth::llvm_position_at_end(&mut memory.session().llvm, builder_handle, block_handle) {
             eprintln!("LLVM-POSITION-AT-END error: {}", e);
         }
     } else {
@@ -2182,7 +3335,7 @@ pub fn llvm_build_ret_void_word(
     memory: &mut crate::Memory,
 ) {
     if let Some(builder_handle) = stack.pop(memory) {
-        if let Err(e) = crate::llvm_forth::llvm_build_ret_void(builder_handle) {
+        if let Err(e) = crate::llvm_forth::llvm_build_ret_void(&mut memory.session().llvm, builder_handle) {
             eprintln!("LLVM-BUILD-RET-VOID error: {}", e);
         }
     } else {
@@ -2202,7 +3355,7 @@ pub fn llvm_build_ret_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        if let Err(e) = crate::llvm_forth::llvm_build_ret(builder_handle, value_handle) {
+        if let Err(e) = crate::llvm_forth::llvm_build_ret(&mut memory.session().llvm, builder_handle, value_handle) {
             eprintln!("LLVM-BUILD-RET error: {}", e);
         }
     } else {
@@ -2219,7 +3372,7 @@ pub fn llvm_dump_module_word(
     memory: &mut crate::Memory,
 ) {
     if let Some(module_handle) = stack.pop(memory) {
-        if let Err(e) = crate::llvm_forth::llvm_dump_module(module_handle) {
+        if let Err(e) = crate::llvm_forth::llvm_dump_module(&memory.session().llvm, module_handle) {
             eprintln!("LLVM-DUMP-MODULE error: {}", e);
         }
     } else {
@@ -2236,7 +3389,7 @@ pub fn llvm_create_jit_word(
     memory: &mut crate::Memory,
 ) {
     if let Some(module_handle) = stack.pop(memory) {
-        match crate::llvm_forth::llvm_create_jit_engine(module_handle) {
+        match crate::llvm_forth::llvm_create_jit_engine(&mut memory.session().llvm, module_handle) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-CREATE-JIT error: {}", e),
         }
@@ -2261,7 +3414,7 @@ pub fn llvm_get_function_word(
     ) {
         match extract_string(memory, name_addr as usize, name_len as usize) {
             Ok(name) => {
-                match crate::llvm_forth::llvm_get_jit_function(engine_handle, &name) {
+                match crate::llvm_forth::llvm_get_jit_function(&memory.session().llvm, engine_handle, &name) {
                     Ok(fn_ptr) => {
                         // Split 64-bit pointer into two 32-bit values
                         let low = (fn_ptr & 0xFFFFFFFF) as i64;
@@ -2301,7 +3454,7 @@ pub fn llvm_build_const_int_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::llvm_forth::llvm_build_const_int(ctx_handle, value, bit_width) {
+        match crate::llvm_forth::llvm_build_const_int(&mut memory.session().llvm, ctx_handle, value, bit_width) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-BUILD-CONST-INT error: {}", e),
         }
@@ -2324,7 +3477,7 @@ pub fn llvm_build_load_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::llvm_forth::llvm_build_load(builder_handle, ctx_handle, ptr_handle, bit_width) {
+        match crate::llvm_forth::llvm_build_load(&mut memory.session().llvm, builder_handle, ctx_handle, ptr_handle, bit_width) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-BUILD-LOAD error: {}", e),
         }
@@ -2346,7 +3499,7 @@ pub fn llvm_build_store_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        if let Err(e) = crate::llvm_forth::llvm_build_store(builder_handle, value_handle, ptr_handle) {
+        if let Err(e) = crate::llvm_forth::llvm_build_store(&mut memory.session().llvm, builder_handle, value_handle, ptr_handle) {
             eprintln!("LLVM-BUILD-STORE error: {}", e);
         }
     } else {
@@ -2354,6 +3507,24 @@ pub fn llvm_build_store_word(
     }
 }
 
+/// LLVM-BUILD-ALLOCA: Allocate an i64 stack slot in the function's entry block
+/// Stack: ( builder-handle ctx-handle -- ptr-handle )
+pub fn llvm_build_alloca_word(
+    stack: &mut crate::Stack,
+    _loop_stack: &crate::LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    if let (Some(ctx_handle), Some(builder_handle)) = (stack.pop(memory), stack.pop(memory)) {
+        match crate::llvm_forth::llvm_build_alloca(&mut memory.session().llvm, builder_handle, ctx_handle) {
+            Ok(handle) => stack.push(handle, memory),
+            Err(e) => eprintln!("LLVM-BUILD-ALLOCA error: {}", e),
+        }
+    } else {
+        eprintln!("LLVM-BUILD-ALLOCA: Stack underflow");
+    }
+}
+
 /// LLVM-BUILD-GEP: Get element pointer (pointer arithmetic)
 /// Stack: ( builder-handle ctx-handle ptr-handle offset-handle -- ptr-handle )
 pub fn llvm_build_gep_word(
@@ -2368,7 +3539,7 @@ pub fn llvm_build_gep_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::llvm_forth::llvm_build_gep(builder_handle, ctx_handle, ptr_handle, offset_handle) {
+        match crate::llvm_forth::llvm_build_gep(&mut memory.session().llvm, builder_handle, ctx_handle, ptr_handle, offset_handle) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-BUILD-GEP error: {}", e),
         }
@@ -2390,7 +3561,7 @@ pub fn llvm_build_add_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::llvm_forth::llvm_build_add(builder_handle, lhs_handle, rhs_handle) {
+        match crate::llvm_forth::llvm_build_add(&mut memory.session().llvm, builder_handle, lhs_handle, rhs_handle) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-BUILD-ADD error: {}", e),
         }
@@ -2412,7 +3583,7 @@ pub fn llvm_build_sub_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::llvm_forth::llvm_build_sub(builder_handle, lhs_handle, rhs_handle) {
+        match crate::llvm_forth::llvm_build_sub(&mut memory.session().llvm, builder_handle, lhs_handle, rhs_handle) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-BUILD-SUB error: {}", e),
         }
@@ -2434,7 +3605,7 @@ pub fn llvm_build_mul_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::llvm_forth::llvm_build_mul(builder_handle, lhs_handle, rhs_handle) {
+        match crate::llvm_forth::llvm_build_mul(&mut memory.session().llvm, builder_handle, lhs_handle, rhs_handle) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-BUILD-MUL error: {}", e),
         }
@@ -2456,7 +3627,7 @@ pub fn llvm_build_sdiv_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::llvm_forth::llvm_build_sdiv(builder_handle, lhs_handle, rhs_handle) {
+        match crate::llvm_forth::llvm_build_sdiv(&mut memory.session().llvm, builder_handle, lhs_handle, rhs_handle) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-BUILD-SDIV error: {}", e),
         }
@@ -2478,7 +3649,7 @@ pub fn llvm_build_srem_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::llvm_forth::llvm_build_srem(builder_handle, lhs_handle, rhs_handle) {
+        match crate::llvm_forth::llvm_build_srem(&mut memory.session().llvm, builder_handle, lhs_handle, rhs_handle) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-BUILD-SREM error: {}", e),
         }
@@ -2487,6 +3658,30 @@ pub fn llvm_build_srem_word(
     }
 }
 
+/// LLVM-BUILD-FLOAT-OP: Double-precision arithmetic on cells holding float bits
+/// Stack: ( builder-handle lhs-handle rhs-handle op -- result-handle )
+/// op: 0=F+, 1=F-, 2=F*, 3=F/
+pub fn llvm_build_float_op_word(
+    stack: &mut crate::Stack,
+    _loop_stack: &crate::LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    if let (Some(op), Some(rhs_handle), Some(lhs_handle), Some(builder_handle)) = (
+        stack.pop(memory),
+        stack.pop(memory),
+        stack.pop(memory),
+        stack.pop(memory),
+    ) {
+        match crate::llvm_forth::llvm_build_float_op(&mut memory.session().llvm, builder_handle, lhs_handle, rhs_handle, op) {
+            Ok(handle) => stack.push(handle, memory),
+            Err(e) => eprintln!("LLVM-BUILD-FLOAT-OP error: {}", e),
+        }
+    } else {
+        eprintln!("LLVM-BUILD-FLOAT-OP: Stack underflow");
+    }
+}
+
 /// LLVM-BUILD-AND: Bitwise AND
 /// Stack: ( builder-handle lhs-handle rhs-handle -- result-handle )
 pub fn llvm_build_and_word(
@@ -2500,7 +3695,7 @@ pub fn llvm_build_and_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::llvm_forth::llvm_build_and(builder_handle, lhs_handle, rhs_handle) {
+        match crate::llvm_forth::llvm_build_and(&mut memory.session().llvm, builder_handle, lhs_handle, rhs_handle) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-BUILD-AND error: {}", e),
         }
@@ -2522,7 +3717,7 @@ pub fn llvm_build_or_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::llvm_forth::llvm_build_or(builder_handle, lhs_handle, rhs_handle) {
+        match crate::llvm_forth::llvm_build_or(&mut memory.session().llvm, builder_handle, lhs_handle, rhs_handle) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-BUILD-OR error: {}", e),
         }
@@ -2544,7 +3739,7 @@ pub fn llvm_build_xor_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::llvm_forth::llvm_build_xor(builder_handle, lhs_handle, rhs_handle) {
+        match crate::llvm_forth::llvm_build_xor(&mut memory.session().llvm, builder_handle, lhs_handle, rhs_handle) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-BUILD-XOR error: {}", e),
         }
@@ -2566,7 +3761,7 @@ pub fn llvm_build_shl_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::llvm_forth::llvm_build_shl(builder_handle, lhs_handle, rhs_handle) {
+        match crate::llvm_forth::llvm_build_shl(&mut memory.session().llvm, builder_handle, lhs_handle, rhs_handle) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-BUILD-SHL error: {}", e),
         }
@@ -2588,7 +3783,7 @@ pub fn llvm_build_ashr_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::llvm_forth::llvm_build_ashr(builder_handle, lhs_handle, rhs_handle) {
+        match crate::llvm_forth::llvm_build_ashr(&mut memory.session().llvm, builder_handle, lhs_handle, rhs_handle) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-BUILD-ASHR error: {}", e),
         }
@@ -2609,7 +3804,7 @@ pub fn llvm_build_br_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        if let Err(e) = crate::llvm_forth::llvm_build_br(builder_handle, block_handle) {
+        if let Err(e) = crate::llvm_forth::llvm_build_br(&mut memory.session().llvm, builder_handle, block_handle) {
             eprintln!("LLVM-BUILD-BR error: {}", e);
         }
     } else {
@@ -2631,7 +3826,7 @@ pub fn llvm_build_cond_br_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        if let Err(e) = crate::llvm_forth::llvm_build_cond_br(builder_handle, cond_handle, then_block, else_block) {
+        if let Err(e) = crate::llvm_forth::llvm_build_cond_br(&mut memory.session().llvm, builder_handle, cond_handle, then_block, else_block) {
             eprintln!("LLVM-BUILD-COND-BR error: {}", e);
         }
     } else {
@@ -2654,7 +3849,7 @@ pub fn llvm_build_icmp_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::llvm_forth::llvm_build_icmp(builder_handle, predicate, lhs_handle, rhs_handle) {
+        match crate::llvm_forth::llvm_build_icmp(&mut memory.session().llvm, builder_handle, predicate, lhs_handle, rhs_handle) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-BUILD-ICMP error: {}", e),
         }
@@ -2676,7 +3871,7 @@ pub fn llvm_build_sext_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::llvm_forth::llvm_build_sext(builder_handle, ctx_handle, value_handle) {
+        match crate::llvm_forth::llvm_build_sext(&mut memory.session().llvm, builder_handle, ctx_handle, value_handle) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-BUILD-SEXT error: {}", e),
         }
@@ -2699,7 +3894,7 @@ pub fn llvm_build_select_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::llvm_forth::llvm_build_select(builder_handle, cond_handle, true_handle, false_handle) {
+        match crate::llvm_forth::llvm_build_select(&mut memory.session().llvm, builder_handle, cond_handle, true_handle, false_handle) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-BUILD-SELECT error: {}", e),
         }
@@ -2722,7 +3917,7 @@ pub fn llvm_build_trunc_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::llvm_forth::llvm_build_trunc(builder_handle, ctx_handle, value_handle, bit_width) {
+        match crate::llvm_forth::llvm_build_trunc(&mut memory.session().llvm, builder_handle, ctx_handle, value_handle, bit_width) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-BUILD-TRUNC error: {}", e),
         }
@@ -2744,7 +3939,7 @@ pub fn llvm_build_ptrtoint_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::llvm_forth::llvm_build_ptrtoint(builder_handle, ctx_handle, ptr_handle) {
+        match crate::llvm_forth::llvm_build_ptrtoint(&mut memory.session().llvm, builder_handle, ctx_handle, ptr_handle) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-BUILD-PTRTOINT error: {}", e),
         }
@@ -2832,7 +4027,7 @@ pub fn llvm_create_global_string_word(
         }
     };
 
-    match crate::llvm_forth::llvm_create_global_string(module_handle, ctx_handle, &string_bytes, name) {
+    match crate::llvm_forth::llvm_create_global_string(&mut memory.session().llvm, module_handle, ctx_handle, &string_bytes, name) {
         Ok(handle) => stack.push(handle, memory),
         Err(e) => eprintln!("LLVM-CREATE-GLOBAL-STRING error: {}", e),
     }
@@ -2855,7 +4050,7 @@ pub fn llvm_build_call_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        if let Err(e) = crate::llvm_forth::llvm_build_call(builder_handle, fn_handle, arg1, arg2, arg3, nargs, is_tail_call) {
+        if let Err(e) = crate::llvm_forth::llvm_build_call(&mut memory.session().llvm, builder_handle, fn_handle, arg1, arg2, arg3, nargs, is_tail_call) {
             // Debug: Show more details when QUARTER_DEBUG is set
             if std::env::var("QUARTER_DEBUG").is_ok() {
                 eprintln!("LLVM-BUILD-CALL error: {}", e);
@@ -2870,6 +4065,48 @@ pub fn llvm_build_call_word(
     }
 }
 
+/// LLVM-BUILD-INDIRECT-CALL: Call a native entry point held as an integer
+/// Stack: ( builder-handle ctx-handle addr-handle arg1 arg2 arg3 -- )
+pub fn llvm_build_indirect_call_word(
+    stack: &mut crate::Stack,
+    _loop_stack: &crate::LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    if let (Some(arg3), Some(arg2), Some(arg1), Some(addr_handle), Some(ctx_handle), Some(builder_handle)) = (
+        stack.pop(memory),
+        stack.pop(memory),
+        stack.pop(memory),
+        stack.pop(memory),
+        stack.pop(memory),
+        stack.pop(memory),
+    ) {
+        if let Err(e) = crate::llvm_forth::llvm_build_indirect_call(&mut memory.session().llvm, builder_handle, ctx_handle, addr_handle, arg1, arg2, arg3) {
+            eprintln!("LLVM-BUILD-INDIRECT-CALL error: {}", e);
+        }
+    } else {
+        eprintln!("LLVM-BUILD-INDIRECT-CALL: Stack underflow");
+    }
+}
+
+/// LLVM-FUNCTION-ADDRESS: Get the address of a function
+/// Stack: ( fn-handle -- value-handle )
+pub fn llvm_function_address_word(
+    stack: &mut crate::Stack,
+    _loop_stack: &crate::LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    if let Some(fn_handle) = stack.pop(memory) {
+        match crate::llvm_forth::llvm_function_address(&mut memory.session().llvm, fn_handle) {
+            Ok(handle) => stack.push(handle, memory),
+            Err(e) => eprintln!("LLVM-FUNCTION-ADDRESS error: {}", e),
+        }
+    } else {
+        eprintln!("LLVM-FUNCTION-ADDRESS: Stack underflow");
+    }
+}
+
 /// LLVM-GET-PARAM: Get function parameter
 /// Stack: ( fn-handle index -- value-handle )
 pub fn llvm_get_param_word(
@@ -2882,7 +4119,7 @@ pub fn llvm_get_param_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::llvm_forth::llvm_get_param(fn_handle, index) {
+        match crate::llvm_forth::llvm_get_param(&mut memory.session().llvm, fn_handle, index) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-GET-PARAM error: {}", e),
         }
@@ -2907,7 +4144,7 @@ pub fn llvm_build_phi_word(
     ) {
         match extract_string(memory, name_addr as usize, name_len as usize) {
             Ok(name) => {
-                match crate::llvm_forth::llvm_build_phi(builder_handle, ctx_handle, &name) {
+                match crate::llvm_forth::llvm_build_phi(&mut memory.session().llvm, builder_handle, ctx_handle, &name) {
                     Ok(handle) => stack.push(handle, memory),
                     Err(e) => eprintln!("LLVM-BUILD-PHI error: {}", e),
                 }
@@ -2932,7 +4169,7 @@ pub fn llvm_phi_add_incoming_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        if let Err(e) = crate::llvm_forth::llvm_phi_add_incoming(phi_handle, value_handle, block_handle) {
+        if let Err(e) = crate::llvm_forth::llvm_phi_add_incoming(&mut memory.session().llvm, phi_handle, value_handle, block_handle) {
             eprintln!("LLVM-PHI-ADD-INCOMING error: {}", e);
         }
     } else {
@@ -2949,7 +4186,7 @@ pub fn llvm_get_insert_block_word(
     memory: &mut crate::Memory,
 ) {
     if let Some(builder_handle) = stack.pop(memory) {
-        match crate::llvm_forth::llvm_get_insert_block(builder_handle) {
+        match crate::llvm_forth::llvm_get_insert_block(&mut memory.session().llvm, builder_handle) {
             Ok(handle) => stack.push(handle, memory),
             Err(e) => eprintln!("LLVM-GET-INSERT-BLOCK error: {}", e),
         }
@@ -3034,7 +4271,7 @@ pub fn llvm_write_object_file_word(
         }
     };
 
-    match crate::llvm_forth::llvm_write_object_file(module_handle, &path, opt_level) {
+    match crate::llvm_forth::llvm_write_object_file(&memory.session().llvm, module_handle, &path, opt_level) {
         Ok(()) => {}, // Success
         Err(e) => eprintln!("LLVM-WRITE-OBJECT-FILE error: {}", e),
     }
@@ -3047,7 +4284,9 @@ pub fn llvm_write_object_file_word(
 /// AST-TYPE: Get AST node type
 /// Stack: ( ast-handle -- type )
 /// Types: 1=PushNumber, 2=CallWord, 3=Sequence, 4=IfThenElse, 5=BeginUntil,
-///        6=BeginWhileRepeat, 7=DoLoop, 8=PrintString, 9=StackString, 10=Leave, 11=Exit
+///        6=BeginWhileRepeat, 7=DoLoop, 8=PrintString, 9=StackString, 10=Leave, 11=Exit,
+///        14=Execute, 22=Case, 23=DeferFetch, 24=DeferStore, 25=Locals, 26=LocalFetch,
+///        27=LocalStore, 28=PushFloat
 pub fn ast_get_type_word(
     stack: &mut crate::Stack,
     _loop_stack: &crate::LoopStack,
@@ -3059,7 +4298,7 @@ pub fn ast_get_type_word(
         //     handle,
         //     if handle >= 0 && handle <= 127 { (handle as u8) as char } else { '?' },
         //     handle as u8);
-        match crate::ast_forth::ast_get_type(handle) {
+        match crate::ast_forth::ast_get_type(&memory.session().ast, handle) {
             Ok(node_type) => stack.push(node_type, memory),
             Err(e) => eprintln!("AST-TYPE error: {}", e),
         }
@@ -3068,7 +4307,7 @@ pub fn ast_get_type_word(
     }
 }
 
-/// AST-GET-NUMBER: Get number from PushNumber node
+/// AST-GET-NUMBER: Get number from PushNumber node (or the bits of a PushFloat)
 /// Stack: ( ast-handle -- number )
 pub fn ast_get_number_word(
     stack: &mut crate::Stack,
@@ -3077,7 +4316,7 @@ pub fn ast_get_number_word(
     memory: &mut crate::Memory,
 ) {
     if let Some(handle) = stack.pop(memory) {
-        match crate::ast_forth::ast_get_number(handle) {
+        match crate::ast_forth::ast_get_number(&memory.session().ast, handle) {
             Ok(number) => stack.push(number, memory),
             Err(e) => eprintln!("AST-GET-NUMBER error: {}", e),
         }
@@ -3107,6 +4346,31 @@ pub fn ast_get_word_word(
     }
 }
 
+/// NATIVE-XT: Get the xt of a native word, which compiled code calls by xt
+/// Stack: ( name-addr name-len -- xt | 0 )
+pub fn native_xt_word(
+    stack: &mut crate::Stack,
+    _loop_stack: &crate::LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    if let (Some(len), Some(addr)) = (
+        stack.pop(memory),
+        stack.pop(memory),
+    ) {
+        let name: String = (0..len as usize)
+            .map(|i| memory.fetch_byte(addr as usize + i).unwrap_or(0) as u8 as char)
+            .collect();
+        // The compiler runs with the re-entry pointed at the dictionary being compiled
+        let xt = memory.session().reentry
+            .and_then(|reentry| unsafe { (*reentry.dict).native_xt(&name) })
+            .unwrap_or(0);
+        stack.push(xt, memory);
+    } else {
+        eprintln!("NATIVE-XT: Stack underflow");
+    }
+}
+
 /// AST-GET-STRING: Get string from PrintString or StackString
 /// Stack: ( ast-handle addr -- length )
 pub fn ast_get_string_word(
@@ -3137,7 +4401,7 @@ pub fn ast_seq_length_word(
     memory: &mut crate::Memory,
 ) {
     if let Some(handle) = stack.pop(memory) {
-        match crate::ast_forth::ast_get_sequence_length(handle) {
+        match crate::ast_forth::ast_get_sequence_length(&memory.session().ast, handle) {
             Ok(length) => stack.push(length, memory),
             Err(e) => eprintln!("AST-SEQ-LENGTH error: {}", e),
         }
@@ -3158,7 +4422,7 @@ pub fn ast_seq_child_word(
         stack.pop(memory),
         stack.pop(memory),
     ) {
-        match crate::ast_forth::ast_get_sequence_child(handle, index) {
+        match crate::ast_forth::ast_get_sequence_child(&mut memory.session().ast, handle, index) {
             Ok(child) => stack.push(child, memory),
             Err(e) => eprintln!("AST-SEQ-CHILD error: {}", e),
         }
@@ -3176,7 +4440,7 @@ pub fn ast_if_then_word(
     memory: &mut crate::Memory,
 ) {
     if let Some(handle) = stack.pop(memory) {
-        match crate::ast_forth::ast_get_if_then(handle) {
+        match crate::ast_forth::ast_get_if_then(&mut memory.session().ast, handle) {
             Ok(then_handle) => stack.push(then_handle, memory),
             Err(e) => eprintln!("AST-IF-THEN error: {}", e),
         }
@@ -3194,7 +4458,7 @@ pub fn ast_if_else_word(
     memory: &mut crate::Memory,
 ) {
     if let Some(handle) = stack.pop(memory) {
-        match crate::ast_forth::ast_get_if_else(handle) {
+        match crate::ast_forth::ast_get_if_else(&mut memory.session().ast, handle) {
             Ok(else_handle) => stack.push(else_handle, memory),
             Err(e) => eprintln!("AST-IF-ELSE error: {}", e),
         }
@@ -3212,7 +4476,7 @@ pub fn ast_loop_body_word(
     memory: &mut crate::Memory,
 ) {
     if let Some(handle) = stack.pop(memory) {
-        match crate::ast_forth::ast_get_loop_body(handle) {
+        match crate::ast_forth::ast_get_loop_body(&mut memory.session().ast, handle) {
             Ok(body_handle) => stack.push(body_handle, memory),
             Err(e) => eprintln!("AST-LOOP-BODY error: {}", e),
         }
@@ -3230,7 +4494,7 @@ pub fn ast_loop_condition_word(
     memory: &mut crate::Memory,
 ) {
     if let Some(handle) = stack.pop(memory) {
-        match crate::ast_forth::ast_get_loop_condition(handle) {
+        match crate::ast_forth::ast_get_loop_condition(&mut memory.session().ast, handle) {
             Ok(cond_handle) => stack.push(cond_handle, memory),
             Err(e) => eprintln!("AST-LOOP-CONDITION error: {}", e),
         }
@@ -3248,7 +4512,7 @@ pub fn ast_loop_increment_word(
     memory: &mut crate::Memory,
 ) {
     if let Some(handle) = stack.pop(memory) {
-        match crate::ast_forth::ast_get_loop_increment(handle) {
+        match crate::ast_forth::ast_get_loop_increment(&memory.session().ast, handle) {
             Ok(increment) => stack.push(increment, memory),
             Err(e) => eprintln!("AST-LOOP-INCREMENT error: {}", e),
         }
@@ -3257,6 +4521,156 @@ pub fn ast_loop_increment_word(
     }
 }
 
+/// AST-CASE-COUNT: Get number of OF clauses (Case only)
+/// Stack: ( ast-handle -- count )
+pub fn ast_case_count_word(
+    stack: &mut crate::Stack,
+    _loop_stack: &crate::LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    if let Some(handle) = stack.pop(memory) {
+        match crate::ast_forth::ast_get_case_count(&memory.session().ast, handle) {
+            Ok(count) => stack.push(count, memory),
+            Err(e) => eprintln!("AST-CASE-COUNT error: {}", e),
+        }
+    } else {
+        eprintln!("AST-CASE-COUNT: Stack underflow");
+    }
+}
+
+/// AST-CASE-TEST: Get test code of the nth clause (Case only)
+/// Stack: ( ast-handle index -- test-handle )
+pub fn ast_case_test_word(
+    stack: &mut crate::Stack,
+    _loop_stack: &crate::LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    if let (Some(index), Some(handle)) = (
+        stack.pop(memory),
+        stack.pop(memory),
+    ) {
+        match crate::ast_forth::ast_get_case_test(&mut memory.session().ast, handle, index) {
+            Ok(test_handle) => stack.push(test_handle, memory),
+            Err(e) => eprintln!("AST-CASE-TEST error: {}", e),
+        }
+    } else {
+        eprintln!("AST-CASE-TEST: Stack underflow");
+    }
+}
+
+/// AST-CASE-BODY: Get body of the nth clause (Case only)
+/// Stack: ( ast-handle index -- body-handle )
+pub fn ast_case_body_word(
+    stack: &mut crate::Stack,
+    _loop_stack: &crate::LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    if let (Some(index), Some(handle)) = (
+        stack.pop(memory),
+        stack.pop(memory),
+    ) {
+        match crate::ast_forth::ast_get_case_body(&mut memory.session().ast, handle, index) {
+            Ok(body_handle) => stack.push(body_handle, memory),
+            Err(e) => eprintln!("AST-CASE-BODY error: {}", e),
+        }
+    } else {
+        eprintln!("AST-CASE-BODY: Stack underflow");
+    }
+}
+
+/// AST-CASE-DEFAULT: Get default code (Case only)
+/// Stack: ( ast-handle -- default-handle )
+pub fn ast_case_default_word(
+    stack: &mut crate::Stack,
+    _loop_stack: &crate::LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    if let Some(handle) = stack.pop(memory) {
+        match crate::ast_forth::ast_get_case_default(&mut memory.session().ast, handle) {
+            Ok(default_handle) => stack.push(default_handle, memory),
+            Err(e) => eprintln!("AST-CASE-DEFAULT error: {}", e),
+        }
+    } else {
+        eprintln!("AST-CASE-DEFAULT: Stack underflow");
+    }
+}
+
+/// AST-LOCALS-COUNT: Get number of locals (Locals only)
+/// Stack: ( ast-handle -- count )
+pub fn ast_locals_count_word(
+    stack: &mut crate::Stack,
+    _loop_stack: &crate::LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    if let Some(handle) = stack.pop(memory) {
+        match crate::ast_forth::ast_get_locals_count(&memory.session().ast, handle) {
+            Ok(count) => stack.push(count, memory),
+            Err(e) => eprintln!("AST-LOCALS-COUNT error: {}", e),
+        }
+    } else {
+        eprintln!("AST-LOCALS-COUNT: Stack underflow");
+    }
+}
+
+/// AST-LOCALS-ARGS: Get number of locals initialised from the stack (Locals only)
+/// Stack: ( ast-handle -- args )
+pub fn ast_locals_args_word(
+    stack: &mut crate::Stack,
+    _loop_stack: &crate::LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    if let Some(handle) = stack.pop(memory) {
+        match crate::ast_forth::ast_get_locals_args(&memory.session().ast, handle) {
+            Ok(args) => stack.push(args, memory),
+            Err(e) => eprintln!("AST-LOCALS-ARGS error: {}", e),
+        }
+    } else {
+        eprintln!("AST-LOCALS-ARGS: Stack underflow");
+    }
+}
+
+/// AST-LOCALS-BODY: Get code in the scope of the locals (Locals only)
+/// Stack: ( ast-handle -- body-handle )
+pub fn ast_locals_body_word(
+    stack: &mut crate::Stack,
+    _loop_stack: &crate::LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    if let Some(handle) = stack.pop(memory) {
+        match crate::ast_forth::ast_get_locals_body(&mut memory.session().ast, handle) {
+            Ok(body_handle) => stack.push(body_handle, memory),
+            Err(e) => eprintln!("AST-LOCALS-BODY error: {}", e),
+        }
+    } else {
+        eprintln!("AST-LOCALS-BODY: Stack underflow");
+    }
+}
+
+/// AST-LOCAL-INDEX: Get frame index of a local (LocalFetch/LocalStore only)
+/// Stack: ( ast-handle -- index )
+pub fn ast_local_index_word(
+    stack: &mut crate::Stack,
+    _loop_stack: &crate::LoopStack,
+    _return_stack: &mut crate::ReturnStack,
+    memory: &mut crate::Memory,
+) {
+    if let Some(handle) = stack.pop(memory) {
+        match crate::ast_forth::ast_get_local_index(&memory.session().ast, handle) {
+            Ok(index) => stack.push(index, memory),
+            Err(e) => eprintln!("AST-LOCAL-INDEX error: {}", e),
+        }
+    } else {
+        eprintln!("AST-LOCAL-INDEX: Stack underflow");
+    }
+}
+
 /// TEST-AST-CREATE: Create a test AST for compiler testing
 /// Creates AST for: 42 (just pushes number 42)
 /// Stack: ( -- ast-handle )
@@ -3271,7 +4685,7 @@ pub fn test_ast_create_word(
 
     // Create simple AST: PushNumber(42)
     let ast = AstNode::PushNumber(42);
-    let handle = ast_register_node(ast);
+    let handle = ast_register_node(&mut memory.session().ast, ast);
     stack.push(handle, memory);
 }
 
@@ -3330,55 +4744,49 @@ pub fn readline_word(
         // Extract prompt string from memory
         match extract_string(memory, prompt_addr as usize, prompt_len as usize) {
             Ok(prompt) => {
-                // Initialize editor if needed
-                READLINE_EDITOR.with(|editor_cell| {
-                    let mut editor_opt = editor_cell.borrow_mut();
-                    if editor_opt.is_none() {
-                        *editor_opt = Some(DefaultEditor::new().unwrap());
+                let session = memory.session();
+                let line = if session.reads_terminal() {
+                    match session.line_editor() {
+                        Some(editor) => editor.readline(&prompt),
+                        // Editor not available
+                        None => Err(ReadlineError::Eof),
                     }
-
-                    if let Some(editor) = editor_opt.as_mut() {
-                        match editor.readline(&prompt) {
-                            Ok(line) => {
-                                // Store the line in a temporary buffer at a fixed high address
-                                // Use address 0x300000 (3MB mark) to avoid conflicts with HERE
-                                // This gives us plenty of space before we hit this area
-                                let temp_buffer = 0x300000_usize;
-
-                                for (i, ch) in line.bytes().enumerate() {
-                                    if memory.store_byte(temp_buffer + i, ch as i64).is_err() {
-                                        stack.push(0, memory); // addr (dummy)
-                                        stack.push(0, memory); // len
-                                        stack.push(0, memory); // flag (false)
-                                        return;
-                                    }
-                                }
-
-                                // Push line addr, len, and success flag
-                                stack.push(temp_buffer as i64, memory);
-                                stack.push(line.len() as i64, memory);
-                                stack.push(-1, memory); // true flag
-                            }
-                            Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => {
-                                // EOF or interrupt - return false flag
-                                stack.push(0, memory); // addr (dummy)
-                                stack.push(0, memory); // len
-                                stack.push(0, memory); // flag (false)
-                            }
-                            Err(_) => {
-                                // Other error - return false flag
+                } else {
+                    // Input given by the program: show the prompt and take the next line
+                    session.write_output(prompt.as_bytes());
+                    session
+                        .next_line()
+                        .map(|line| String::from_utf8_lossy(&line).into_owned())
+                        .ok_or(ReadlineError::Eof)
+                };
+                match line {
+                    Ok(line) => {
+                        // Store the line in a temporary buffer at a fixed high address
+                        // Use address 0x300000 (3MB mark) to avoid conflicts with HERE
+                        // This gives us plenty of space before we hit this area
+                        let temp_buffer = 0x300000_usize;
+
+                        for (i, ch) in line.bytes().enumerate() {
+                            if memory.store_byte(temp_buffer + i, ch as i64).is_err() {
                                 stack.push(0, memory); // addr (dummy)
                                 stack.push(0, memory); // len
                                 stack.push(0, memory); // flag (false)
+                                return;
                             }
                         }
-                    } else {
-                        // Editor not available
+
+                        // Push line addr, len, and success flag
+                        stack.push(temp_buffer as i64, memory);
+                        stack.push(line.len() as i64, memory);
+                        stack.push(-1, memory); // true flag
+                    }
+                    Err(_) => {
+                        // EOF, interrupt or another error - return false flag
                         stack.push(0, memory); // addr (dummy)
                         stack.push(0, memory); // len
                         stack.push(0, memory); // flag (false)
                     }
-                });
+                }
             }
             Err(e) => {
                 eprintln!("READLINE prompt string error: {}", e);
@@ -3403,11 +4811,9 @@ pub fn history_add_word(
     if let (Some(len), Some(addr)) = (stack.pop(memory), stack.pop(memory)) {
         match extract_string(memory, addr as usize, len as usize) {
             Ok(line) => {
-                READLINE_EDITOR.with(|editor_cell| {
-                    if let Some(editor) = editor_cell.borrow_mut().as_mut() {
-                        let _ = editor.add_history_entry(line);
-                    }
-                });
+                if let Some(editor) = memory.session().existing_line_editor() {
+                    let _ = editor.add_history_entry(line);
+                }
             }
             Err(e) => eprintln!("HISTORY-ADD string error: {}", e),
         }
@@ -3427,18 +4833,8 @@ pub fn history_load_word(
     if let (Some(len), Some(addr)) = (stack.pop(memory), stack.pop(memory)) {
         match extract_string(memory, addr as usize, len as usize) {
             Ok(filename) => {
-                let success = READLINE_EDITOR.with(|editor_cell| {
-                    let mut editor_opt = editor_cell.borrow_mut();
-                    if editor_opt.is_none() {
-                        *editor_opt = Some(DefaultEditor::new().unwrap());
-                    }
-
-                    if let Some(editor) = editor_opt.as_mut() {
-                        editor.load_history(&filename).is_ok()
-                    } else {
-                        false
-                    }
-                });
+                let success = memory.session().line_editor()
+                    .is_some_and(|editor| editor.load_history(&filename).is_ok());
 
                 stack.push(if success { -1 } else { 0 }, memory);
             }
@@ -3464,13 +4860,8 @@ pub fn history_save_word(
     if let (Some(len), Some(addr)) = (stack.pop(memory), stack.pop(memory)) {
         match extract_string(memory, addr as usize, len as usize) {
             Ok(filename) => {
-                let success = READLINE_EDITOR.with(|editor_cell| {
-                    if let Some(editor) = editor_cell.borrow_mut().as_mut() {
-                        editor.save_history(&filename).is_ok()
-                    } else {
-                        false
-                    }
-                });
+                let success = memory.session().existing_line_editor()
+                    .is_some_and(|editor| editor.save_history(&filename).is_ok());
 
                 stack.push(if success { -1 } else { 0 }, memory);
             }
@@ -3487,7 +4878,7 @@ pub fn history_save_word(
 
 /// EVALUATE: ( addr len -- )
 /// Execute a string as Forth code
-/// Uses the global execution context to parse and execute the code
+/// Runs on the dictionary and stacks of the source being interpreted
 /// Supports word definitions (: and ;) and all other Forth constructs
 pub fn evaluate_word(
     stack: &mut Stack,
@@ -3497,50 +4888,48 @@ pub fn evaluate_word(
 ) {
     // Pop values first before any re-entrant calls
     if let (Some(len), Some(addr)) = (stack.pop(memory), stack.pop(memory)) {
-        // Extract the string from memory
-        match extract_string(memory, addr as usize, len as usize) {
-            Ok(code) => {
-                // Get raw pointers for re-entrant access (avoids RefCell borrow panic)
-                match crate::get_reentrant_pointers() {
-                    Some((dict_ptr, loop_stack_ptr, return_stack_ptr, _memory_ptr, included_files_ptr)) => {
-                        // Get config flags
-                        let (no_jit, dump_ir, verify_ir) = crate::get_reentrant_config();
-                        let config = crate::CompilerConfig::new(no_jit, dump_ir, verify_ir);
-                        let options = crate::ExecutionOptions::new(false, false);
-
-                        // SAFETY: Using raw pointers from execution context
-                        // These are valid for the lifetime of the execution context
-                        unsafe {
-                            let mut ctx = crate::RuntimeContext::new(
-                                stack,
-                                &mut *dict_ptr,
-                                &mut *loop_stack_ptr,
-                                &mut *return_stack_ptr,
-                                memory,
-                            );
-                            match crate::execute_line(
-                                &code,
-                                &mut ctx,
-                                config,
-                                options,
-                                &mut *included_files_ptr,
-                            ) {
-                                Ok(()) => {
-                                    // Success
-                                }
-                                Err(e) => {
-                                    eprintln!("EVALUATE error: {}", e);
-                                }
-                            }
+        // The dictionary and stacks of the source EVALUATE was called from
+        match memory.session().reentry {
+            Some(reentry) => {
+                let options = crate::ExecutionOptions::new(false, false);
+                let capture_depth = memory.session().capture_depth();
+
+                // SAFETY: the re-entry points at the context being interpreted, which
+                // outlives this call
+                unsafe {
+                    let mut ctx = crate::RuntimeContext::new(
+                        stack,
+                        &mut *reentry.dict,
+                        &mut *reentry.loop_stack,
+                        &mut *reentry.return_stack,
+                        memory,
+                    );
+                    // The string is interpreted where it lies, so SOURCE returns it
+                    match crate::evaluate(
+                        addr as usize,
+                        len as usize,
+                        &mut ctx,
+                        reentry.config,
+                        options,
+                        &mut *reentry.included_files,
+                    ) {
+                        Ok(()) => {
+                            // Success
+                        }
+                        Err(e) if (*reentry.dict).in_catch() => {
+                            // Let the enclosing CATCH see the error
+                            memory.raise(e.code());
+                        }
+                        Err(e) => {
+                            // Output captured since the error's >STRING goes back to the output
+                            memory.session().truncate_captures(capture_depth);
+                            eprintln!("EVALUATE error: {}", e);
                         }
-                    }
-                    None => {
-                        eprintln!("EVALUATE: No execution context available");
                     }
                 }
             }
-            Err(e) => {
-                eprintln!("EVALUATE string error: {}", e);
+            None => {
+                eprintln!("EVALUATE: No execution context available");
             }
         }
     } else {
@@ -3548,6 +4937,34 @@ pub fn evaluate_word(
     }
 }
 
+/// CREATE ( "name" -- )
+/// Make a word that pushes the address of the data field starting at HERE. The
+/// outer interpreter defines it once the word that ran CREATE returns
+pub(crate) fn create_word(ctx: &mut crate::NativeContext) -> Result<(), crate::QuarterError> {
+    run_runtime_primitive(quarter_parse_name, 0, ctx.stack, ctx.return_stack, ctx.memory);
+    let (Some(len), Some(addr)) = (ctx.stack.pop(ctx.memory), ctx.stack.pop(ctx.memory)) else {
+        return Err(crate::QuarterError::underflow("CREATE"));
+    };
+    if len == 0 {
+        return Err(crate::QuarterError::Compile("CREATE requires a name".to_string()));
+    }
+    let name = extract_string(ctx.memory, addr as usize, len as usize)
+        .map_err(crate::QuarterError::Other)?
+        .to_uppercase();
+    let here = ctx.memory.here();
+    ctx.memory.session().push_created(crate::session::Created::Word { name, addr: here });
+    Ok(())
+}
+
+/// (DOES>) ( xt -- )
+/// What DOES> compiles: the word created last pushes its data-field address, then
+/// runs xt, the code after DOES>
+pub(crate) fn does_word(ctx: &mut crate::NativeContext) -> Result<(), crate::QuarterError> {
+    let xt: i64 = ctx.pop()?;
+    ctx.memory.session().push_created(crate::session::Created::Does(xt));
+    Ok(())
+}
+
 /// CMOVE: ( src dest count -- )
 /// Copy count bytes from src to dest
 pub fn cmove_word(
@@ -3567,18 +4984,18 @@ pub fn cmove_word(
             match memory.fetch_byte(src_addr + i) {
                 Ok(byte) => {
                     if let Err(e) = memory.store_byte(dest_addr + i, byte) {
-                        eprintln!("CMOVE store error: {}", e);
+                        memory.raise(e.code());
                         return;
                     }
                 }
                 Err(e) => {
-                    eprintln!("CMOVE fetch error: {}", e);
+                    memory.raise(e.code());
                     return;
                 }
             }
         }
     } else {
-        eprintln!("CMOVE: Stack underflow");
+        memory.raise(-4);
     }
 }
 
@@ -3588,117 +5005,9 @@ pub fn bye_word(
     _stack: &mut Stack,
     _loop_stack: &LoopStack,
     _return_stack: &mut crate::ReturnStack,
-    _memory: &mut crate::Memory,
-) {
-    println!("\nGoodbye!");
-    std::process::exit(0);
-}
-
-/// ABORT: ( i*x -- ) ( R: j*x -- )
-/// Clear data and return stacks, then abort execution
-pub fn abort_word(
-    stack: &mut Stack,
-    _loop_stack: &LoopStack,
-    return_stack: &mut crate::ReturnStack,
-    memory: &mut crate::Memory,
-) {
-    // Clear data stack
-    while stack.pop(memory).is_some() {}
-
-    // Clear return stack
-    while return_stack.pop(memory).is_some() {}
-
-    eprintln!("ABORT");
-    std::process::exit(-1);
-}
-
-/// THROW: ( k*x n -- k*x | i*x n )
-/// If n is non-zero, unwind to nearest CATCH with error code n
-/// If n is zero, do nothing
-pub fn throw_word(
-    stack: &mut Stack,
-    _loop_stack: &LoopStack,
-    _return_stack: &mut crate::ReturnStack,
-    memory: &mut crate::Memory,
-) {
-    if let Some(n) = stack.pop(memory) {
-        if n != 0 {
-            // Signal throw by returning a special error with the throw code
-            // This will be caught by CATCH
-            eprintln!("THROW: Throwing error code {}", n);
-            // We can't actually throw from here, so we'll use a special marker
-            // The actual unwinding will happen in CATCH
-            stack.push(n, memory); // Put code back for CATCH to see
-        }
-        // If n==0, do nothing (don't throw)
-    } else {
-        eprintln!("THROW: Stack underflow");
-    }
-}
-
-/// CATCH: ( i*x addr len -- j*x 0 | i*x n )
-/// Execute code at addr/len (like EVALUATE) and catch any errors
-/// Returns 0 if successful, or error code n if THROW was called
-pub fn catch_word(
-    stack: &mut Stack,
-    _loop_stack: &LoopStack,
-    _return_stack: &mut crate::ReturnStack,
     memory: &mut crate::Memory,
 ) {
-    if let (Some(len), Some(addr)) = (stack.pop(memory), stack.pop(memory)) {
-        // Save stack depth for unwinding
-        let saved_depth = stack.depth();
-
-        // Extract the string from memory
-        match extract_string(memory, addr as usize, len as usize) {
-            Ok(code) => {
-                // Try to execute the code using reentrant pointers
-                let result = match crate::get_reentrant_pointers() {
-                    Some((dict_ptr, loop_stack_ptr, return_stack_ptr, _memory_ptr, _included_files_ptr)) => {
-                        let tokens: Vec<&str> = code.split_whitespace().collect();
-                        unsafe {
-                            let dict_ref = &*dict_ptr;
-                            match crate::parse_tokens(&tokens, dict_ref, None) {
-                                Ok(ast) => {
-                                    ast.execute(
-                                        stack,
-                                        dict_ref,
-                                        &mut *loop_stack_ptr,
-                                        &mut *return_stack_ptr,
-                                        memory,
-                                    )
-                                }
-                                Err(e) => Err(e),
-                            }
-                        }
-                    }
-                    None => Err("No execution context".to_string()),
-                };
-
-                // Check result
-                match result {
-                    Ok(()) => {
-                        // Success - push 0
-                        stack.push(0, memory);
-                    }
-                    Err(e) => {
-                        // Error - restore stack and push error code
-                        eprintln!("CATCH: Caught error: {}", e);
-                        // Restore stack depth
-                        while stack.depth() > saved_depth {
-                            stack.pop(memory);
-                        }
-                        // Push error code (-1 for generic error)
-                        stack.push(-1, memory);
-                    }
-                }
-            }
-            Err(e) => {
-                eprintln!("CATCH string error: {}", e);
-                stack.push(-3, memory); // String error code
-            }
-        }
-    } else {
-        eprintln!("CATCH: Stack underflow");
-    }
+    memory.session().write_output(b"\nGoodbye!\n");
+    memory.session().output.flush();
+    std::process::exit(0);
 }
@@ -0,0 +1,137 @@
+use std::collections::HashSet;
+use quarter::{execute_line, Dictionary, LoopStack, Memory, ReturnStack, Stack, RuntimeContext, CompilerConfig, ExecutionOptions};
+
+#[test]
+fn test_allocate_store_and_free() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    let here = memory.here();
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("24 ALLOCATE DROP VALUE BUF", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("10 BUF ! 20 BUF 8 + ! BUF @ BUF 8 + @ +", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("BUF FREE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(30));
+    assert!(stack.is_empty());
+    // The buffer does not take dictionary space; only the VALUE cell does
+    assert_eq!(memory.here(), here + 8);
+}
+
+#[test]
+fn test_freed_blocks_are_reused() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("100 ALLOCATE DROP CONSTANT A 100 ALLOCATE DROP CONSTANT B", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        // A smaller request fits in A's block once it is freed
+        execute_line("A FREE DROP 50 ALLOCATE DROP A =", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_resize_keeps_contents() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // FENCE sits after BUF, so BUF can't grow in place and is moved
+        execute_line("8 ALLOCATE DROP VALUE BUF 8 ALLOCATE DROP CONSTANT FENCE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("77 BUF ! BUF 1000 RESIZE SWAP TO BUF BUF @", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(77));
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_allocation_errors_return_ior() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("100000000 ALLOCATE SWAP DROP", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("HERE FREE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("HERE 10 RESIZE SWAP DROP", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(-61));
+    assert_eq!(stack.pop(&mut memory), Some(-60));
+    assert_eq!(stack.pop(&mut memory), Some(-59));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_allocate_up_to_the_heap_limit_during_evaluate() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("VARIABLE GRABBED", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        // Allocate blocks of u bytes until the heap is out of room, writing the last cell of each
+        execute_line(": GRAB ( u -- ) BEGIN DUP ALLOCATE 0= WHILE OVER + 8 - -1 SWAP ! GRABBED @ 1 + GRABBED ! REPEAT DROP DROP ;",
+            &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        // Filling the heap from EVALUATE leaves the text of the line alone, so
+        // parsing carries on after it
+        execute_line("S\" 65536 GRAB 4096 GRAB 64 GRAB 8 GRAB 7\" EVALUATE 8 ALLOCATE SWAP DROP 9",
+            &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("GRABBED @ 50 >", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert_eq!(stack.pop(&mut memory), Some(9));
+    assert_eq!(stack.pop(&mut memory), Some(-59));
+    assert_eq!(stack.pop(&mut memory), Some(7));
+    assert!(stack.is_empty());
+}
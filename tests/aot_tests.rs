@@ -0,0 +1,132 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+// Compile a Forth source file with --compile and run the executable
+fn compile_and_run(source: &Path, name: &str) -> Output {
+    // Build first to ensure binary is up to date
+    let build_status = Command::new("cargo")
+        .args(["build", "--quiet"])
+        .status()
+        .expect("Failed to build");
+
+    assert!(build_status.success(), "Build failed");
+
+    // Get the project root directory (where Cargo.toml is)
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    let project_root = PathBuf::from(manifest_dir);
+
+    let binary_path = project_root.join("target/debug/quarter");
+    let executable = env::temp_dir().join(format!("quarter-aot-{}-{}", std::process::id(), name));
+
+    let compile = Command::new(&binary_path)
+        .arg(source)
+        .arg("--compile")
+        .arg("-o")
+        .arg(&executable)
+        .current_dir(&project_root)
+        .output()
+        .expect("Failed to run quarter --compile");
+
+    assert!(
+        compile.status.success(),
+        "Compiling {} failed with exit code: {:?}\nstdout: {}\nstderr: {}",
+        source.display(),
+        compile.status.code(),
+        String::from_utf8_lossy(&compile.stdout),
+        String::from_utf8_lossy(&compile.stderr)
+    );
+
+    let output = Command::new(&executable)
+        .output()
+        .expect("Failed to run the compiled executable");
+    let _ = std::fs::remove_file(&executable);
+    output
+}
+
+// Write a Forth source file to the temp directory
+fn temp_source(name: &str, source: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("quarter-aot-{}-{}.fth", std::process::id(), name));
+    std::fs::write(&path, source).expect("Failed to write source file");
+    path
+}
+
+#[test]
+fn test_catch_and_throw_aot() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    let source = PathBuf::from(manifest_dir).join("tests/aot_exception_tests.fth");
+
+    let output = compile_and_run(&source, "exceptions");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(
+        output.status.success(),
+        "Exception executable failed with exit code: {:?}\nstdout: {}\nstderr: {}",
+        output.status.code(),
+        stdout,
+        stderr
+    );
+
+    // Each line holds the codes CATCH returned, with the stacks restored
+    let lines: Vec<&str> = stdout.lines().map(str::trim_end).collect();
+    assert_eq!(lines, ["4242", "3003 9", "7008", "0 1111", "-10", "-11 -11 0 0"], "stderr: {}", stderr);
+}
+
+#[test]
+fn test_uncaught_throw_aot() {
+    let source = temp_source("uncaught", ": MAIN 1 . 42 THROW 2 . ;\n");
+
+    let output = compile_and_run(&source, "uncaught");
+    let _ = std::fs::remove_file(&source);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // The THROW unwinds out of MAIN, and the program reports it and fails
+    assert_eq!(output.status.code(), Some(1), "stdout: {}\nstderr: {}", stdout, stderr);
+    assert_eq!(stdout, "1 ");
+    assert!(stderr.contains("Uncaught THROW 42"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_data_space_aot() {
+    let source = temp_source(
+        "data",
+        ": CONST CREATE , DOES> @ ;\n\
+         42 CONST X\n\
+         7 VALUE V\n\
+         VARIABLE COUNTER 5 COUNTER !\n\
+         : MAIN X . V . COUNTER @ . HERE 0 , HERE SWAP - . ;\n",
+    );
+
+    let output = compile_and_run(&source, "data");
+    let _ = std::fs::remove_file(&source);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // Cells stored while the source loaded are there when MAIN runs, and HERE is past them
+    assert!(output.status.success(), "stdout: {}\nstderr: {}", stdout, stderr);
+    assert_eq!(stdout, "42 7 5 8 ");
+}
+
+#[test]
+fn test_defining_words_aot() {
+    let source = temp_source(
+        "defining",
+        ": CONST CREATE , DOES> @ ;\n\
+         : MAYBE ( flag \"name\" -- ) IF CREATE 1 , ELSE 2 THEN ;\n\
+         : TALLY ( \"name\" -- ) 0 CONST DOES> DUP @ 1 + DUP ROT ! ;\n\
+         -1 MAYBE YES 0 MAYBE CONSTANT NO\n\
+         TALLY TICKS\n\
+         : MAIN YES @ . NO . TICKS . TICKS . ;\n",
+    );
+
+    let output = compile_and_run(&source, "defining");
+    let _ = std::fs::remove_file(&source);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // TICKS runs the code after TALLY's DOES>, compiled once for every word TALLY makes
+    assert!(output.status.success(), "stdout: {}\nstderr: {}", stdout, stderr);
+    assert_eq!(stdout, "1 2 1 2 ");
+}
@@ -0,0 +1,177 @@
+use std::collections::HashSet;
+use quarter::{execute_line, Dictionary, LoopStack, Memory, ReturnStack, Stack, RuntimeContext, CompilerConfig, ExecutionOptions, QuarterError};
+
+#[test]
+fn test_catch_returns_zero_or_thrown_code() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": SAFE 5 0 THROW ; : RISKY 1 2 3 99 THROW 4 ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("' SAFE CATCH 10 ' RISKY CATCH", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    // RISKY's pushes are discarded when the stack is restored to CATCH's depth
+    assert_eq!(stack.pop(&mut memory), Some(99));
+    assert_eq!(stack.pop(&mut memory), Some(10));
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(5));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_nested_catch_unwinds_loops_and_return_stack() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": INNER 10 0 DO 7 >R I 3 = IF -7 THROW THEN LOOP ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": MIDDLE ['] INNER CATCH 1 THROW ; : OUTER ['] MIDDLE CATCH ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("OUTER", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    // INNER's -7 is caught by MIDDLE, which then throws 1 to OUTER
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert!(stack.is_empty());
+    assert!(return_stack.pop(&mut memory).is_none());
+    assert_eq!(loop_stack.depth(), 0);
+}
+
+#[test]
+fn test_interpreter_errors_have_standard_codes() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": UNDERFLOW DROP DROP ; : DIVIDE 1 0 / ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("' UNDERFLOW CATCH ' DIVIDE CATCH", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(-10));
+    assert_eq!(stack.pop(&mut memory), Some(-4));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_abort_is_catchable() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": QUIT-NOW ABORT ; : COMPLAIN 1 ABORT\" failed\" ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("' QUIT-NOW CATCH ' COMPLAIN CATCH", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(-2));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_uncaught_abort_clears_stacks() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        assert!(execute_line("1 2 3 ABORT", &mut ctx, config, options, &mut HashSet::new()).is_err());
+    }
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_uncaught_errors_keep_their_kind() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+    execute_line(": EARLY 1 EXIT 2 ; : STRAY LEAVE ;", &mut ctx, config, options, &mut HashSet::new())
+        .unwrap();
+
+    let result = execute_line("DROP", &mut ctx, config, options, &mut HashSet::new());
+    assert_eq!(result, Err(QuarterError::StackUnderflow(String::new())));
+    let result = execute_line("1 0 /", &mut ctx, config, options, &mut HashSet::new());
+    assert_eq!(result, Err(QuarterError::DivisionByZero));
+    let result = execute_line("-7 THROW", &mut ctx, config, options, &mut HashSet::new());
+    assert_eq!(result.map_err(|e| e.code()), Err(-7));
+
+    // EXIT is not an error, but a LEAVE no loop takes is
+    assert_eq!(execute_line("EARLY", &mut ctx, config, options, &mut HashSet::new()), Ok(()));
+    let result = execute_line("STRAY", &mut ctx, config, options, &mut HashSet::new());
+    assert!(matches!(result, Err(QuarterError::Other(_))));
+}
+
+#[test]
+fn test_errors_while_compiling_keep_their_codes() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": REFUSE 42 THROW ; IMMEDIATE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+
+        // Immediate words and [ ... ] run while the definition compiles
+        let result = execute_line(": USES-REFUSE REFUSE ;", &mut ctx, config, options, &mut HashSet::new());
+        assert_eq!(result.map_err(|e| e.code()), Err(42));
+        let result = execute_line(": INTERPRETS [ DROP ] ;", &mut ctx, config, options, &mut HashSet::new());
+        assert_eq!(result.map_err(|e| e.kind().clone()), Err(QuarterError::StackUnderflow(String::new())));
+
+        // So CATCH sees their codes
+        execute_line(": COMPILE-IT S\" : LATER REFUSE ;\" EVALUATE ; ' COMPILE-IT CATCH", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(42));
+    assert!(stack.is_empty());
+}
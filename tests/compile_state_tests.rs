@@ -0,0 +1,147 @@
+use std::collections::HashSet;
+use quarter::{execute_line, Dictionary, LoopStack, Memory, ReturnStack, Stack, RuntimeContext, CompilerConfig, ExecutionOptions};
+
+#[test]
+fn test_state_is_false_when_interpreting() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("STATE @", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(0));
+}
+
+#[test]
+fn test_immediate_word_sees_compile_state() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+
+        // Immediate word runs while FOO is being compiled and leaves STATE on the stack
+        execute_line(": STATE? STATE @ ; IMMEDIATE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": FOO STATE? ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_brackets_and_literal() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+
+        // [ 6 7 * ] is computed at compile time and compiled as a literal
+        execute_line(": ANSWER [ 6 7 * ] LITERAL ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert!(stack.is_empty());
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("ANSWER ANSWER", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(42));
+    assert_eq!(stack.pop(&mut memory), Some(42));
+}
+
+#[test]
+fn test_postpone_control_flow() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+
+        // User-defined control structure words built with POSTPONE
+        execute_line(": UNLESS POSTPONE 0= POSTPONE IF ; IMMEDIATE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": END-UNLESS POSTPONE THEN ; IMMEDIATE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": CHECK ( flag -- n ) UNLESS 99 END-UNLESS ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("0 CHECK -1 CHECK", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(99));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_postpone_immediate_word_and_compile_comma() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+
+        // POSTPONE of an immediate word (LITERAL) runs it when the outer word runs
+        execute_line(": TEN 10 POSTPONE LITERAL ; IMMEDIATE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        // COMPILE, appends a call to the word named by the xt
+        execute_line(": DOUBLE, ['] DUP COMPILE, ['] + COMPILE, ; IMMEDIATE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": TWENTY TEN DOUBLE, ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("TWENTY", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(20));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_postpone_unknown_word() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+    let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+
+    let result = execute_line(": BAD POSTPONE NO-SUCH-WORD ; IMMEDIATE", &mut ctx, config, options, &mut HashSet::new());
+    assert!(result.is_err());
+}
@@ -0,0 +1,160 @@
+use std::collections::HashSet;
+use quarter::{execute_line, load_file, Dictionary, LoopStack, Memory, ReturnStack, Stack, RuntimeContext, CompilerConfig, ExecutionMode, ExecutionOptions};
+
+// A scratch file name unique to this test
+fn temp_path(name: &str) -> String {
+    std::env::temp_dir()
+        .join(format!("quarter-{}-{}", std::process::id(), name))
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[test]
+fn test_if_else_then_select_branch() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // The skipped branch may name words that do not exist
+        execute_line("-1 [IF] 1 [ELSE] NO-SUCH-WORD [THEN]", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("0 [if] NO-SUCH-WORD [else] 2 [then]", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("0 [IF] 3 [THEN] 4", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(4));
+    assert_eq!(stack.pop(&mut memory), Some(2));
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_nested_conditionals_are_skipped_whole() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("0 [IF] -1 [IF] 1 [ELSE] 2 [THEN] 3 [ELSE] 4 [THEN]", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("-1 [IF] 0 [IF] 5 [ELSE] 6 [THEN] [ELSE] 7 [THEN]", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(6));
+    assert_eq!(stack.pop(&mut memory), Some(4));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_conditionals_inside_definition() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": PICKED [ 0 ] [IF] 10 [ELSE] 20 [THEN] 1 + ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("PICKED", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(21));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_skipped_branch_spans_lines() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+    let path = temp_path("conditional.fth");
+    std::fs::write(&path, "0 [IF]\n  : BROKEN NO-SUCH-WORD ;\n  1\n[ELSE]\n  2\n[THEN]\n3\n").unwrap();
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        load_file(&path, &mut ctx, config, options, &mut HashSet::new()).unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(3));
+    assert_eq!(stack.pop(&mut memory), Some(2));
+    assert!(stack.is_empty());
+    assert!(!dict.has_word("BROKEN"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_defined_and_undefined() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        load_file("stdlib/core.fth", &mut ctx, config, options, &mut HashSet::new()).unwrap();
+        execute_line("[DEFINED] DUP [DEFINED] NO-SUCH-WORD [UNDEFINED] NO-SUCH-WORD", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        // Define a word only if it is missing
+        execute_line("[UNDEFINED] SQUARE [IF] : SQUARE DUP * ; [THEN] 7 SQUARE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(49));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_environment_reports_execution_mode() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("S\" QUARTER-MODE\" ENVIRONMENT?", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        ctx.memory.set_execution_mode(ExecutionMode::Aot);
+        execute_line("S\" quarter-mode\" ENVIRONMENT? S\" NO-SUCH-QUERY\" ENVIRONMENT?", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert_eq!(stack.pop(&mut memory), Some(2));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert!(stack.is_empty());
+}
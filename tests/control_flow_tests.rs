@@ -140,3 +140,78 @@ fn test_if_compile_only_error() {
     let ast = parse_tokens(&tokens, &dict, None);
     assert!(ast.is_ok()); // Parsing should succeed
 }
+
+#[test]
+fn test_case_of_match() {
+    let mut stack = Stack::new();
+    let mut loop_stack = LoopStack::new();
+    let mut dict = Dictionary::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    // Define: : TEST CASE 1 OF 10 ENDOF 2 OF 20 ENDOF 0 SWAP ENDCASE ;
+    let tokens = vec!["CASE", "1", "OF", "10", "ENDOF", "2", "OF", "20", "ENDOF", "0", "SWAP", "ENDCASE"];
+    let ast = parse_tokens(&tokens, &dict, None).unwrap();
+    dict.add_compiled("TEST".to_string(), ast);
+
+    stack.push(2, &mut memory);
+    dict.execute_word("TEST", &mut stack, &mut loop_stack, &mut return_stack, &mut memory).unwrap();
+    assert_eq!(stack.pop(&mut memory), Some(20));
+    // Selector was consumed by the matching OF
+    assert_eq!(stack.pop(&mut memory), None);
+}
+
+#[test]
+fn test_case_default() {
+    let mut stack = Stack::new();
+    let mut loop_stack = LoopStack::new();
+    let mut dict = Dictionary::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    // Define: : TEST CASE 1 OF 10 ENDOF 2 OF 20 ENDOF 0 SWAP ENDCASE ;
+    let tokens = vec!["CASE", "1", "OF", "10", "ENDOF", "2", "OF", "20", "ENDOF", "0", "SWAP", "ENDCASE"];
+    let ast = parse_tokens(&tokens, &dict, None).unwrap();
+    dict.add_compiled("TEST".to_string(), ast);
+
+    // Default code runs with the selector on the stack, ENDCASE drops it
+    stack.push(7, &mut memory);
+    dict.execute_word("TEST", &mut stack, &mut loop_stack, &mut return_stack, &mut memory).unwrap();
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), None);
+}
+
+#[test]
+fn test_nested_case() {
+    let mut stack = Stack::new();
+    let mut loop_stack = LoopStack::new();
+    let mut dict = Dictionary::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    // Define: : TEST ( a b -- n ) SWAP CASE 1 OF CASE 1 OF 11 ENDOF 12 SWAP ENDCASE ENDOF DROP 0 SWAP ENDCASE ;
+    let tokens = vec![
+        "SWAP", "CASE",
+        "1", "OF", "CASE", "1", "OF", "11", "ENDOF", "12", "SWAP", "ENDCASE", "ENDOF",
+        "DROP", "0", "SWAP",
+        "ENDCASE",
+    ];
+    let ast = parse_tokens(&tokens, &dict, None).unwrap();
+    dict.add_compiled("TEST".to_string(), ast);
+
+    stack.push(1, &mut memory);
+    stack.push(1, &mut memory);
+    dict.execute_word("TEST", &mut stack, &mut loop_stack, &mut return_stack, &mut memory).unwrap();
+    assert_eq!(stack.pop(&mut memory), Some(11));
+
+    stack.push(1, &mut memory);
+    stack.push(5, &mut memory);
+    dict.execute_word("TEST", &mut stack, &mut loop_stack, &mut return_stack, &mut memory).unwrap();
+    assert_eq!(stack.pop(&mut memory), Some(12));
+
+    stack.push(3, &mut memory);
+    stack.push(1, &mut memory);
+    dict.execute_word("TEST", &mut stack, &mut loop_stack, &mut return_stack, &mut memory).unwrap();
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), None);
+}
@@ -0,0 +1,211 @@
+use std::collections::HashSet;
+use quarter::{execute_line, Dictionary, LoopStack, Memory, ReturnStack, Stack, RuntimeContext, CompilerConfig, ExecutionOptions};
+
+#[test]
+fn test_double_literals() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // 2^64 does not fit in one cell, so it lands in the high cell
+        execute_line("42. -1. 18446744073709551616.", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(42));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_mixed_multiply_and_divide() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // The 128-bit product of two large cells survives the round trip through UM/MOD
+        execute_line("4611686018427387904 8 UM* 8 UM/MOD", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("-9223372036854775807 3 M* 3 SM/REM", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(-9223372036854775807));
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(4611686018427387904));
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_floored_and_symmetric_division() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("-10. 3 SM/REM -10. 3 FM/MOD", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    // FM/MOD: remainder takes the sign of the divisor
+    assert_eq!(stack.pop(&mut memory), Some(-4));
+    assert_eq!(stack.pop(&mut memory), Some(2));
+    // SM/REM: remainder takes the sign of the dividend
+    assert_eq!(stack.pop(&mut memory), Some(-3));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_division_by_zero_leaves_stack() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // Division by zero throws -10 without touching the operands
+        assert!(execute_line("7. 0 UM/MOD", &mut ctx, config, options, &mut HashSet::new()).is_err());
+    }
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(7));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_division_overflow_throws() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // The smallest double divided by -1 is out of range, as is a quotient wider than a cell
+        let result = execute_line("0 -9223372036854775808 -1 SM/REM", &mut ctx, config, options, &mut HashSet::new());
+        assert_eq!(result.map_err(|e| e.code()), Err(-11));
+        execute_line("DROP DROP DROP", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("0 -9223372036854775808 -1 ' FM/MOD CATCH >R DROP DROP DROP R>", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("0 1 1 ' SM/REM CATCH >R DROP DROP DROP R> 0 1 1 ' UM/MOD CATCH >R DROP DROP DROP R>", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(-11));
+    assert_eq!(stack.pop(&mut memory), Some(-11));
+    assert_eq!(stack.pop(&mut memory), Some(-11));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_double_add_subtract_negate() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // Carry out of the low cell
+        execute_line("-1 0 1. D+", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        // Borrow from the high cell, then negate
+        execute_line("0 1 1. D- DNEGATE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_2variable_and_2constant() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("2VARIABLE PAIR 10 20 2CONSTANT TENS", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": SAVE-TENS TENS PAIR 2! ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("SAVE-TENS PAIR 2@ PAIR @", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    // 2! stores the top cell at the lower address
+    assert_eq!(stack.pop(&mut memory), Some(20));
+    assert_eq!(stack.pop(&mut memory), Some(20));
+    assert_eq!(stack.pop(&mut memory), Some(10));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_only_2constants_are_inlined() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // A caller keeps the pair a 2CONSTANT had when the caller was compiled
+        execute_line("1 2 2CONSTANT CP : USE-CP CP ; 3 4 2CONSTANT CP USE-CP", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        // A colon definition pushing two numbers is called like any other word
+        execute_line(": PAIR 5 6 ; : USE-PAIR PAIR ; : PAIR 7 8 ; USE-PAIR", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(8));
+    assert_eq!(stack.pop(&mut memory), Some(7));
+    assert_eq!(stack.pop(&mut memory), Some(2));
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert!(stack.is_empty());
+}
@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+use quarter::{execute_line, Dictionary, LoopStack, Memory, ReturnStack, Stack, RuntimeContext, CompilerConfig, ExecutionMode, ExecutionOptions};
+
+#[test]
+fn test_implementation_limits() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("S\" /COUNTED-STRING\" ENVIRONMENT? DROP S\" ADDRESS-UNIT-BITS\" ENVIRONMENT? DROP", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("S\" STACK-CELLS\" ENVIRONMENT? DROP S\" RETURN-STACK-CELLS\" ENVIRONMENT? DROP", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("S\" MAX-N\" ENVIRONMENT? DROP S\" MAX-U\" ENVIRONMENT? DROP S\" FLOORED\" ENVIRONMENT? DROP", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert_eq!(stack.pop(&mut memory), Some(i64::MAX));
+    // The return stack shares its top cell with the dictionary pointer
+    assert_eq!(stack.pop(&mut memory), Some(8191));
+    assert_eq!(stack.pop(&mut memory), Some(8192));
+    assert_eq!(stack.pop(&mut memory), Some(8));
+    assert_eq!(stack.pop(&mut memory), Some(255));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_double_and_float_limits() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("S\" MAX-D\" ENVIRONMENT? S\" MAX-FLOAT\" ENVIRONMENT?", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    // MAX-FLOAT leaves its value on the float stack
+    assert_eq!(memory.fpop(), Some(f64::MAX));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert_eq!(stack.pop(&mut memory), Some(i64::MAX));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_word_sets_and_unknown_queries() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("S\" FILE\" ENVIRONMENT? S\" search-order\" ENVIRONMENT? S\" BLOCK\" ENVIRONMENT?", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    // Unknown queries leave only a false flag
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_quarter_queries() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+    let version = env!("CARGO_PKG_VERSION")
+        .split('.')
+        .fold(0, |number, part| number * 100 + part.parse::<i64>().unwrap());
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("S\" QUARTER-VERSION\" ENVIRONMENT? DROP S\" QUARTER-JIT\" ENVIRONMENT? DROP", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        ctx.memory.set_execution_mode(ExecutionMode::Jit);
+        execute_line("S\" QUARTER-JIT\" ENVIRONMENT? DROP S\" QUARTER-CELL-SIZE\" ENVIRONMENT? DROP", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(8));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(version));
+    assert!(stack.is_empty());
+}
@@ -41,7 +41,7 @@ fn test_evaluate_multiline_via_file() {
 fn test_catch_success_via_file() {
     // Test CATCH with successful code
     let test_code = r#"
-        S" 5 10 +" CATCH DROP
+        S" 5 10 +" ' EVALUATE CATCH DROP
     "#;
     std::fs::write("/tmp/test_catch_ok.fth", test_code).unwrap();
 
@@ -57,7 +57,7 @@ fn test_catch_success_via_file() {
 fn test_catch_error_via_file() {
     // Test CATCH with error
     let test_code = r#"
-        S" BADWORD" CATCH DROP
+        S" BADWORD" ' EVALUATE CATCH DROP
     "#;
     std::fs::write("/tmp/test_catch_err.fth", test_code).unwrap();
 
@@ -70,6 +70,23 @@ fn test_catch_error_via_file() {
     assert!(output.status.success(), "CATCH error test failed");
 }
 
+#[test]
+fn test_catch_undefined_word_code_via_file() {
+    // An undefined word inside EVALUATE throws -13
+    let test_code = r#"
+        S" BADWORD" ' EVALUATE CATCH .
+    "#;
+    std::fs::write("/tmp/test_catch_undefined.fth", test_code).unwrap();
+
+    let output = std::process::Command::new("target/debug/quarter")
+        .arg("/tmp/test_catch_undefined.fth")
+        .output()
+        .expect("Failed to execute");
+
+    assert!(output.status.success(), "CATCH undefined word test failed");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("-13"));
+}
+
 #[test]
 fn test_cmove_via_file() {
     // Test CMOVE through a Forth file
@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+use quarter::{execute_line, Dictionary, LoopStack, Memory, ReturnStack, Stack, RuntimeContext, CompilerConfig, ExecutionOptions, QuarterError};
+
+#[test]
+fn test_tick_gives_the_same_xt_without_using_data_space() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    let here = memory.here();
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": SQUARE DUP * ; : SQUARE-XT ['] SQUARE ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("' SQUARE SQUARE-XT ' SQUARE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    let xt = stack.pop(&mut memory).unwrap();
+    assert_eq!(stack.pop(&mut memory), Some(xt));
+    assert_eq!(stack.pop(&mut memory), Some(xt));
+    // The definitions themselves take no data space, and neither does '
+    assert_eq!(memory.here(), here);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(&format!("7 {} EXECUTE", xt), &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(49));
+}
+
+#[test]
+fn test_name_to_string_maps_xt_back_to_name() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("WORDLIST CONSTANT EXTRA EXTRA SET-CURRENT : HIDDEN 1 ; FORTH-WORDLIST SET-CURRENT", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("S\" HIDDEN\" EXTRA SEARCH-WORDLIST DROP >NAME NAME>STRING", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    // Words outside FORTH-WORDLIST are named without their wordlist
+    let len = stack.pop(&mut memory).unwrap();
+    let addr = stack.pop(&mut memory).unwrap();
+    let name: String = (0..len)
+        .map(|i| memory.fetch_byte((addr + i) as usize).unwrap() as u8 as char)
+        .collect();
+    assert_eq!(name, "HIDDEN");
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("HERE ' DUP >NAME NAME>STRING DROP ' DUP >NAME NAME>STRING >R = R> HERE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    // Names are laid down once, outside the data space
+    let here = stack.pop(&mut memory).unwrap();
+    assert_eq!(stack.pop(&mut memory), Some(3));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert_eq!(stack.pop(&mut memory), Some(here));
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("' SWAP >NAME ' SWAP = 123456 >NAME", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+}
+
+#[test]
+fn test_invalid_xt_is_undefined_word() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("MARKER GONE : TEMP 1 ; ' TEMP GONE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        // The xt of a forgotten word names nothing
+        execute_line("' EXECUTE CATCH", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(-13));
+
+    let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+    let result = execute_line("0 EXECUTE", &mut ctx, config, options, &mut HashSet::new());
+    assert_eq!(result, Err(QuarterError::InvalidXt(0)));
+}
+
+unsafe extern "C" fn native_noop(_memory: *mut u8, _sp: *mut usize, _rp: *mut usize) {}
+
+#[test]
+fn test_xt_table_holds_native_entry_points() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    dict.add_jit_compiled("NATIVE".to_string(), native_noop);
+    let xt = dict.xt("NATIVE").unwrap();
+    let entry = quarter::XT_TABLE_ADDR + xt as usize * 8;
+
+    // The table is brought up to date before native code runs
+    dict.execute_word("NATIVE", &mut stack, &mut loop_stack, &mut return_stack, &mut memory)
+        .unwrap();
+    assert_eq!(memory.fetch(entry), Ok(native_noop as *const () as usize as i64));
+
+    // An interpreted redefinition gets a new xt with no native entry; the old xt
+    // still runs the native definition
+    dict.add_compiled("NATIVE".to_string(), quarter::AstNode::PushNumber(1));
+    let new_xt = dict.xt("NATIVE").unwrap();
+    assert_ne!(new_xt, xt);
+    dict.add_jit_compiled("OTHER".to_string(), native_noop);
+    dict.execute_word("OTHER", &mut stack, &mut loop_stack, &mut return_stack, &mut memory)
+        .unwrap();
+    assert_eq!(memory.fetch(entry), Ok(native_noop as *const () as usize as i64));
+    assert_eq!(memory.fetch(quarter::XT_TABLE_ADDR + new_xt as usize * 8), Ok(0));
+}
+
+#[test]
+fn test_xt_keeps_running_the_definition_it_was_taken_from() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": FOO 1 ; ' FOO : FOO 2 ; EXECUTE ' FOO EXECUTE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        // Forgetting a redefinition brings back the word it hid, with its xt
+        execute_line("' FOO MARKER GONE : FOO 3 ; GONE ' FOO = FOO", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(2));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert_eq!(stack.pop(&mut memory), Some(2));
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert!(stack.is_empty());
+}
@@ -0,0 +1,183 @@
+use std::collections::HashSet;
+use quarter::{execute_line, load_file, Dictionary, LoopStack, Memory, ReturnStack, Stack, RuntimeContext, CompilerConfig, ExecutionOptions};
+
+// A scratch file name unique to this test
+fn temp_path(name: &str) -> String {
+    std::env::temp_dir()
+        .join(format!("quarter-{}-{}", std::process::id(), name))
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[test]
+fn test_write_then_read_lines() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+    let path = temp_path("lines.txt");
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // R/O, W/O, R/W and BIN are defined in the standard library
+        load_file("stdlib/core.fth", &mut ctx, config, options, &mut HashSet::new()).unwrap();
+        execute_line("VARIABLE FID CREATE BUF 80 ALLOT", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(&format!("S\" {}\" W/O CREATE-FILE DROP FID !", path), &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("S\" first\" FID @ WRITE-LINE DROP S\" second\" FID @ WRITE-LINE DROP", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("FID @ CLOSE-FILE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(&format!("S\" {}\" R/O OPEN-FILE DROP FID !", path), &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        // Second line: ( u2 flag ior ), then end of file
+        execute_line("BUF 80 FID @ READ-LINE DROP 2DROP BUF 80 FID @ READ-LINE BUF 80 FID @ READ-LINE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("FID @ CLOSE-FILE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert_eq!(stack.pop(&mut memory), Some(6));
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert!(stack.is_empty());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "first\nsecond\n");
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_read_line_strips_crlf() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+    let path = temp_path("crlf.txt");
+    std::fs::write(&path, "ab\r\ncd").unwrap();
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        load_file("stdlib/core.fth", &mut ctx, config, options, &mut HashSet::new()).unwrap();
+        execute_line("VARIABLE FID CREATE BUF 80 ALLOT", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(&format!("S\" {}\" R/O OPEN-FILE DROP FID !", path), &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        // The last line has no terminator but is still returned
+        execute_line("BUF 80 FID @ READ-LINE 2DROP BUF 80 FID @ READ-LINE 2DROP FID @ CLOSE-FILE DROP", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(2));
+    assert_eq!(stack.pop(&mut memory), Some(2));
+    assert!(stack.is_empty());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_size_position_and_reposition() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+    let path = temp_path("seek.bin");
+    std::fs::write(&path, "0123456789").unwrap();
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        load_file("stdlib/core.fth", &mut ctx, config, options, &mut HashSet::new()).unwrap();
+        execute_line("VARIABLE FID CREATE BUF 16 ALLOT", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(&format!("S\" {}\" R/W BIN OPEN-FILE DROP FID !", path), &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("FID @ FILE-SIZE DROP D>S", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("7. FID @ REPOSITION-FILE DROP BUF 16 FID @ READ-FILE DROP BUF C@", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("FID @ FILE-POSITION DROP D>S FID @ CLOSE-FILE DROP", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(10));
+    assert_eq!(stack.pop(&mut memory), Some(b'7' as i64));
+    // Only three bytes were left after position 7
+    assert_eq!(stack.pop(&mut memory), Some(3));
+    assert_eq!(stack.pop(&mut memory), Some(10));
+    assert!(stack.is_empty());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_missing_file_iors() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+    let path = temp_path("missing.txt");
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        load_file("stdlib/core.fth", &mut ctx, config, options, &mut HashSet::new()).unwrap();
+        execute_line(&format!("S\" {}\" R/O OPEN-FILE NIP", path), &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(&format!("S\" {}\" DELETE-FILE", path), &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(-38));
+    assert_eq!(stack.pop(&mut memory), Some(-38));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_unknown_and_closed_fileids() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+    let path = temp_path("closed.txt");
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        load_file("stdlib/core.fth", &mut ctx, config, options, &mut HashSet::new()).unwrap();
+        execute_line("VARIABLE FID CREATE BUF 80 ALLOT", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        // Fileids that were never opened are refused, not used as pointers
+        execute_line("42 CLOSE-FILE 0 CLOSE-FILE -1 FILE-SIZE NIP NIP BUF 80 12345 READ-LINE NIP NIP", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        // Closing a file twice fails the second time, and its fileid is refused afterwards
+        execute_line(&format!("S\" {}\" W/O CREATE-FILE DROP FID !", path), &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("FID @ CLOSE-FILE FID @ CLOSE-FILE S\" x\" FID @ WRITE-FILE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(-37));
+    assert_eq!(stack.pop(&mut memory), Some(-37));
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(-37));
+    assert_eq!(stack.pop(&mut memory), Some(-37));
+    assert_eq!(stack.pop(&mut memory), Some(-37));
+    assert_eq!(stack.pop(&mut memory), Some(-37));
+    assert!(stack.is_empty());
+    std::fs::remove_file(&path).unwrap();
+}
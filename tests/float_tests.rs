@@ -0,0 +1,115 @@
+use std::collections::HashSet;
+use quarter::{execute_line, Dictionary, LoopStack, Memory, ReturnStack, Stack, RuntimeContext, CompilerConfig, ExecutionOptions};
+
+#[test]
+fn test_float_literals_and_arithmetic() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // An empty exponent means zero, so 2e is 2.0
+        execute_line("1.5e0 2.5E0 F+ 2e F* 1e F- 3e F/", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(memory.fpop(), Some(2.3333333333333335));
+    assert_eq!(memory.fdepth(), 0);
+    // Floats never touch the data stack
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_float_stack_words() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("1e 2e FSWAP FDUP FDEPTH", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(3));
+    assert_eq!(memory.fpop(), Some(1.0));
+    assert_eq!(memory.fpop(), Some(1.0));
+    assert_eq!(memory.fpop(), Some(2.0));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_float_conversion_and_math() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // F>S truncates toward zero, FLOOR rounds toward negative infinity
+        execute_line("-3.7e F>S -3.7e FLOOR F>S 16 S>F FSQRT F>S", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("0e FSIN F0= 1e 2e F<", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert_eq!(stack.pop(&mut memory), Some(4));
+    assert_eq!(stack.pop(&mut memory), Some(-4));
+    assert_eq!(stack.pop(&mut memory), Some(-3));
+    assert!(stack.is_empty());
+    assert_eq!(memory.fdepth(), 0);
+}
+
+#[test]
+fn test_fvariable_and_fconstant() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("FVARIABLE RADIUS 1.5e FCONSTANT SCALE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": SCALED-SQUARE ( -- ) ( F: -- r ) RADIUS F@ FDUP F* SCALE F* ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("2e RADIUS F! SCALED-SQUARE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(memory.fpop(), Some(4.0 * 1.5));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_float_stack_underflow() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+    let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+
+    assert!(execute_line("FCONSTANT NOTHING", &mut ctx, config, options, &mut HashSet::new()).is_err());
+}
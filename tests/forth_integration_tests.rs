@@ -190,6 +190,103 @@ fn test_tco_jit() {
     );
 }
 
+#[test]
+fn test_exceptions_interpreted() {
+    // Build first to ensure binary is up to date
+    let build_status = Command::new("cargo")
+        .args(["build", "--quiet"])
+        .status()
+        .expect("Failed to build");
+
+    assert!(build_status.success(), "Build failed");
+
+    // Get the project root directory (where Cargo.toml is)
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    let project_root = PathBuf::from(manifest_dir);
+
+    // Run the exception tests in interpreted mode
+    let binary_path = project_root.join("target/debug/quarter");
+    let test_path = project_root.join("tests/exception_tests.fth");
+
+    let output = Command::new(&binary_path)
+        .arg(&test_path)
+        .current_dir(&project_root)
+        .output()
+        .expect("Failed to run exception_tests.fth");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // Check that the command succeeded
+    assert!(
+        output.status.success(),
+        "Exception tests failed with exit code: {:?}\nstdout: {}\nstderr: {}",
+        output.status.code(),
+        stdout,
+        stderr
+    );
+
+    // Each test prints the codes CATCH returned
+    for expected in ["4242", "7007", "3003 9", "7008", "0 1111", "-1 88 77", "-10 -10", "-11 -11 0 0 -11"] {
+        assert!(
+            stdout.contains(expected),
+            "Exception tests missing {}:\nstdout: {}\nstderr: {}",
+            expected,
+            stdout,
+            stderr
+        );
+    }
+}
+
+#[test]
+fn test_exceptions_jit() {
+    // Build first to ensure binary is up to date
+    let build_status = Command::new("cargo")
+        .args(["build", "--quiet"])
+        .status()
+        .expect("Failed to build");
+
+    assert!(build_status.success(), "Build failed");
+
+    // Get the project root directory (where Cargo.toml is)
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    let project_root = PathBuf::from(manifest_dir);
+
+    // Run the exception tests in JIT mode
+    let binary_path = project_root.join("target/debug/quarter");
+    let test_path = project_root.join("tests/exception_tests.fth");
+
+    let output = Command::new(&binary_path)
+        .arg(&test_path)
+        .arg("--jit")
+        .current_dir(&project_root)
+        .output()
+        .expect("Failed to run exception_tests.fth with --jit");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // Check that the command succeeded
+    assert!(
+        output.status.success(),
+        "Exception JIT tests failed with exit code: {:?}\nstdout: {}\nstderr: {}",
+        output.status.code(),
+        stdout,
+        stderr
+    );
+
+    // Each test prints the codes CATCH returned
+    for expected in ["4242", "7007", "3003 9", "7008", "0 1111", "-1 88 77", "-10 -10", "-11 -11 0 0 -11"] {
+        assert!(
+            stdout.contains(expected),
+            "Exception JIT tests missing {}:\nstdout: {}\nstderr: {}",
+            expected,
+            stdout,
+            stderr
+        );
+    }
+}
+
 #[test]
 fn test_recurse_interpreted() {
     // Build first to ensure binary is up to date
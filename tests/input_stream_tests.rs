@@ -0,0 +1,185 @@
+use std::collections::HashSet;
+use quarter::{execute_line, load_file, Dictionary, LoopStack, Memory, ReturnStack, Stack, RuntimeContext, CompilerConfig, ExecutionOptions};
+
+// The string ( addr len ) on top of the stack
+fn pop_string(stack: &mut Stack, memory: &mut Memory) -> String {
+    let len = stack.pop(memory).unwrap() as usize;
+    let addr = stack.pop(memory).unwrap() as usize;
+    (0..len).map(|i| memory.fetch_byte(addr + i).unwrap() as u8 as char).collect()
+}
+
+// A scratch file name unique to this test
+fn temp_path(name: &str) -> String {
+    std::env::temp_dir()
+        .join(format!("quarter-{}-{}", std::process::id(), name))
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[test]
+fn test_strings_keep_their_spacing() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": GREETING S\" a  b   c\" ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("GREETING S\"  leading\"", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(pop_string(&mut stack, &mut memory), " leading");
+    assert_eq!(pop_string(&mut stack, &mut memory), "a  b   c");
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_parse_and_parse_name() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // A parsing word of our own: the text up to the next |
+        execute_line(": UPTO| 124 PARSE ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("UPTO| one  two| PARSE-NAME   three 5", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(5));
+    assert_eq!(pop_string(&mut stack, &mut memory), "three");
+    assert_eq!(pop_string(&mut stack, &mut memory), "one  two");
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_to_in_skips_rest_of_line() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": SKIP-LINE SOURCE SWAP DROP >IN ! ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("1 SKIP-LINE NOT-A-WORD 2", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_word_returns_counted_string() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // Leading delimiters are skipped
+        execute_line("44 WORD ,,,abc, DUP 1 + SWAP C@", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(pop_string(&mut stack, &mut memory), "abc");
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_source_id() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+    let path = temp_path("source-id.fth");
+    std::fs::write(&path, "SOURCE-ID\n").unwrap();
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("SOURCE-ID", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        load_file(&path, &mut ctx, config, options, &mut HashSet::new()).unwrap();
+    }
+    // A file is identified by a positive number, user input by 0
+    assert!(stack.pop(&mut memory).unwrap() > 0);
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert!(stack.is_empty());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_refill_reads_next_line_of_file() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+    let path = temp_path("refill.fth");
+    std::fs::write(&path, "NEXT-LINE\n  raw  line\n7\n").unwrap();
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // Take the next line as data and skip over it
+        execute_line(": NEXT-LINE REFILL DROP SOURCE DUP >IN ! ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        load_file(&path, &mut ctx, config, options, &mut HashSet::new()).unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(7));
+    assert_eq!(pop_string(&mut stack, &mut memory), "  raw  line");
+    assert!(stack.is_empty());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_save_and_restore_input() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("VARIABLE PASSES", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        // RESTORE-INPUT goes back to just after SAVE-INPUT, so the words after it run twice
+        execute_line(": AGAIN? PASSES @ 2 < IF RESTORE-INPUT DROP THEN ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("0 PASSES ! SAVE-INPUT PASSES @ 1 + PASSES ! AGAIN? PASSES @", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(2));
+    assert!(stack.is_empty());
+}
@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+use std::io::Cursor;
+use quarter::{execute_line, Dictionary, LoopStack, Memory, ReturnStack, Stack, RuntimeContext, CompilerConfig, ExecutionOptions};
+use quarter::{Input, Interpreter, Output};
+
+// The string ( addr len ) on top of the stack
+fn pop_string(stack: &mut Stack, memory: &mut Memory) -> String {
+    let len = stack.pop(memory).unwrap() as usize;
+    let addr = stack.pop(memory).unwrap() as usize;
+    (0..len).map(|i| memory.fetch_byte(addr + i).unwrap() as u8 as char).collect()
+}
+
+#[test]
+fn test_key_and_accept() {
+    let mut forth = Interpreter::new();
+    forth.set_input(Input::text("ab\r\nsecond line\nx"));
+
+    forth.eval("KEY KEY").unwrap();
+    assert_eq!(forth.stack(), vec![97, 98]);
+    forth.eval("2DROP CREATE BUF 80 ALLOT").unwrap();
+
+    // ACCEPT takes the rest of the line KEY is in, without its end
+    forth.eval("BUF 80 ACCEPT").unwrap();
+    assert_eq!(forth.pop::<i64>().unwrap(), 0);
+
+    // A line longer than the buffer is left for the next read
+    forth.eval("BUF 6 ACCEPT BUF SWAP").unwrap();
+    assert_eq!(forth.pop_string().unwrap(), "second");
+    forth.eval("BUF 80 ACCEPT BUF SWAP").unwrap();
+    assert_eq!(forth.pop_string().unwrap(), " line");
+
+    // The last line needn't end; after it KEY? is false and KEY returns 0
+    forth.eval("KEY? KEY KEY? KEY").unwrap();
+    assert_eq!(forth.stack(), vec![-1, 120, 0, 0]);
+}
+
+#[test]
+fn test_ekey_reads_characters() {
+    let mut forth = Interpreter::new();
+    forth.set_input(Input::text("é€A"));
+
+    forth.eval("EKEY EKEY EKEY? EKEY EKEY>CHAR").unwrap();
+    assert_eq!(forth.stack(), vec![0xE9, 0x20AC, -1, 65, -1]);
+}
+
+#[test]
+fn test_input_from_callback_and_reader() {
+    let mut forth = Interpreter::new();
+    let mut lines = vec!["3", "4"].into_iter();
+    forth.set_input(Input::callback(move || lines.next().map(String::from)));
+
+    forth.eval("CREATE BUF 80 ALLOT : NUMBER ( -- n ) BUF 80 ACCEPT BUF SWAP EVALUATE ;").unwrap();
+    forth.eval("NUMBER NUMBER * KEY?").unwrap();
+    assert_eq!(forth.stack(), vec![12, 0]);
+    forth.eval("DROP").unwrap();
+
+    forth.set_input(Input::Reader(Box::new(Cursor::new("5\n"))));
+    forth.eval("NUMBER").unwrap();
+    assert_eq!(forth.pop::<i64>().unwrap(), 5);
+}
+
+// A reader whose next line hasn't arrived yet: reading it would wait
+struct NothingYet;
+
+impl std::io::Read for NothingYet {
+    fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+        panic!("KEY? waited for input");
+    }
+}
+
+impl std::io::BufRead for NothingYet {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        panic!("KEY? waited for input");
+    }
+
+    fn consume(&mut self, _amt: usize) {}
+}
+
+#[test]
+fn test_key_question_never_waits() {
+    let mut forth = Interpreter::new();
+    forth.set_input(Input::Reader(Box::new(NothingYet)));
+    forth.eval("KEY? EKEY?").unwrap();
+    assert_eq!(forth.stack(), vec![0, 0]);
+    forth.eval("2DROP").unwrap();
+
+    // A callback is only asked for a line by the words that read one
+    forth.set_input(Input::callback(|| panic!("KEY? waited for input")));
+    forth.eval("KEY?").unwrap();
+    assert_eq!(forth.pop::<i64>().unwrap(), 0);
+
+    // The rest of a line already read is ready
+    let mut lines = vec!["ab"].into_iter();
+    forth.set_input(Input::callback(move || lines.next().map(String::from)));
+    forth.eval("KEY KEY?").unwrap();
+    assert_eq!(forth.stack(), vec![97, -1]);
+}
+
+#[test]
+fn test_readline_from_given_input() {
+    let mut forth = Interpreter::new();
+    forth.set_input(Input::text("typed\n"));
+    forth.set_output(Output::buffer());
+
+    // The prompt is written to the output rather than to a line editor
+    forth.eval("S\" > \" READLINE").unwrap();
+    assert_eq!(forth.pop::<i64>().unwrap(), -1);
+    assert_eq!(forth.pop_string().unwrap(), "typed");
+    forth.eval("S\" > \" READLINE").unwrap();
+    assert_eq!(forth.pop::<i64>().unwrap(), 0);
+    assert_eq!(forth.take_output(), "> > ");
+}
+
+#[test]
+fn test_refill_reads_input_after_user_input() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+    memory.session().set_input(Input::text("1 2 +\n  raw  line\n0 [IF] 7\n8 [THEN] 9\n"));
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": NEXT-LINE REFILL DROP SOURCE DUP >IN ! ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        // The line REFILL reads is interpreted after the text it was called from
+        execute_line("REFILL", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(3));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // Lines can be taken as data
+        execute_line("NEXT-LINE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(pop_string(&mut stack, &mut memory), "  raw  line");
+    assert!(stack.is_empty());
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // [IF] skips across lines. The rest of the line REFILL is called from is not interpreted
+        execute_line("REFILL NOT-REACHED", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(9));
+    assert_eq!(stack.pop(&mut memory), Some(-1));
+    assert!(stack.is_empty());
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // At the end of the input REFILL is false
+        execute_line("REFILL", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert!(stack.is_empty());
+}
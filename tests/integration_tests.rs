@@ -1,17 +1,8 @@
 use std::collections::HashSet;
-use quarter::{execute_line, load_file, Dictionary, LoopStack, ReturnStack, Stack, Memory, RuntimeContext, CompilerConfig, ExecutionOptions};
+use quarter::{execute_line, load_file, Dictionary, LoopStack, ReturnStack, Stack, Memory, RuntimeContext, CompilerConfig, ExecutionOptions, QuarterError};
 use std::fs;
 use std::io::Write;
 
-// Test fixture that automatically clears global state on drop
-struct TestGuard;
-
-impl Drop for TestGuard {
-    fn drop(&mut self) {
-        quarter::clear_test_state();
-    }
-}
-
 #[test]
 fn test_execute_line_simple_expression() {
     let mut stack = Stack::new();
@@ -32,7 +23,6 @@ fn test_execute_line_simple_expression() {
 
 #[test]
 fn test_execute_line_word_definition() {
-    let _guard = TestGuard;
     let mut stack = Stack::new();
     let mut loop_stack = LoopStack::new();
     let mut dict = Dictionary::new();
@@ -69,7 +59,7 @@ fn test_execute_line_if_then_error() {
     // IF/THEN outside definition should error
     let result = execute_line("1 IF 42 THEN", &mut ctx, config, options, &mut HashSet::new());
     assert!(result.is_err());
-    assert!(result.unwrap_err().contains("compile-only"));
+    assert!(result.unwrap_err().to_string().contains("compile-only"));
 }
 
 #[test]
@@ -138,7 +128,7 @@ fn test_execute_line_incomplete_definition() {
     // Missing semicolon
     let result = execute_line(": SQUARE DUP *", &mut ctx, config, options, &mut HashSet::new());
     assert!(result.is_err());
-    assert!(result.unwrap_err().contains("Missing ;"));
+    assert!(result.unwrap_err().to_string().contains("Missing ;"));
 }
 
 #[test]
@@ -203,7 +193,6 @@ fn test_load_file_with_comments() {
 
 #[test]
 fn test_load_file_with_definitions() {
-    let _guard = TestGuard;
     let mut stack = Stack::new();
     let mut loop_stack = LoopStack::new();
     let mut dict = Dictionary::new();
@@ -280,7 +269,7 @@ fn test_load_file_nonexistent() {
     // Try to load a file that doesn't exist
     let result = load_file("/tmp/nonexistent_file.qtr", &mut ctx, config, options, &mut HashSet::new());
     assert!(result.is_err());
-    assert!(result.unwrap_err().contains("Cannot read file"));
+    assert!(matches!(result, Err(QuarterError::Io(message)) if message.contains("Cannot read file")));
 }
 
 #[test]
@@ -315,7 +304,6 @@ fn test_load_file_with_paren_comments() {
 // INCLUDE tests
 #[test]
 fn test_include_simple() {
-    let _guard = TestGuard;
     let mut stack = Stack::new();
     let mut loop_stack = LoopStack::new();
     let mut dict = Dictionary::new();
@@ -353,7 +341,6 @@ fn test_include_simple() {
 
 #[test]
 fn test_include_nested() {
-    let _guard = TestGuard;
     let mut stack = Stack::new();
     let mut loop_stack = LoopStack::new();
     let mut dict = Dictionary::new();
@@ -414,7 +401,7 @@ fn test_include_nonexistent() {
 
     // Should error gracefully
     assert!(result.is_err());
-    assert!(result.unwrap_err().contains("Cannot read file"));
+    assert!(matches!(result, Err(QuarterError::Io(message)) if message.contains("Cannot read file")));
 }
 
 #[test]
@@ -432,5 +419,25 @@ fn test_include_missing_filename() {
     // INCLUDE without filename should error
     let result = execute_line("INCLUDE", &mut ctx, config, options, &mut HashSet::new());
     assert!(result.is_err());
-    assert!(result.unwrap_err().contains("requires a filename"));
+    assert!(result.unwrap_err().to_string().contains("requires a filename"));
+}
+
+#[test]
+fn test_evaluate_without_interpreter() {
+    let mut stack = Stack::new();
+    let mut loop_stack = LoopStack::new();
+    let mut dict = Dictionary::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    // EVALUATE runs on the dictionary and stacks of the line that called it
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": DOUBLE 2 * ;", &mut ctx, config, options, &mut HashSet::new()).unwrap();
+        execute_line("S\" 21 DOUBLE\" EVALUATE", &mut ctx, config, options, &mut HashSet::new()).unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(42));
 }
@@ -0,0 +1,160 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use quarter::{Interpreter, QuarterError};
+
+// A scratch file name unique to this test
+fn temp_path(name: &str) -> String {
+    std::env::temp_dir()
+        .join(format!("quarter-{}-{}", std::process::id(), name))
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[test]
+fn test_eval_and_call() {
+    let mut forth = Interpreter::new();
+
+    forth.eval(": SQUARE DUP * ;").unwrap();
+    forth.push(7i64).unwrap();
+    forth.call("square").unwrap();
+    assert_eq!(forth.pop::<i64>().unwrap(), 49);
+
+    // The standard library is loaded
+    forth.eval("1 2 NIP 3 4 2DROP").unwrap();
+    assert_eq!(forth.stack(), vec![2]);
+}
+
+#[test]
+fn test_typed_stack_access() {
+    let mut forth = Interpreter::new();
+
+    forth.push(true).unwrap();
+    forth.push('A').unwrap();
+    forth.push(12usize).unwrap();
+    forth.push(-3i32).unwrap();
+    forth.push(2.5f64).unwrap();
+    assert_eq!(forth.stack(), vec![-1, 65, 12, -3]);
+
+    assert_eq!(forth.pop::<f64>().unwrap(), 2.5);
+    assert_eq!(forth.pop::<i32>().unwrap(), -3);
+    assert_eq!(forth.pop::<usize>().unwrap(), 12);
+    assert_eq!(forth.pop::<char>().unwrap(), 'A');
+    assert!(forth.pop::<bool>().unwrap());
+    assert_eq!(forth.depth(), 0);
+
+    assert!(matches!(forth.pop::<i64>(), Err(QuarterError::StackUnderflow(_))));
+    assert!(matches!(forth.pop::<f64>(), Err(QuarterError::FloatStackUnderflow(_))));
+    forth.push(-1i64).unwrap();
+    assert!(forth.pop::<usize>().is_err());
+}
+
+#[test]
+fn test_pop_string() {
+    let mut forth = Interpreter::new();
+
+    forth.eval("S\" hello,  world\"").unwrap();
+    assert_eq!(forth.pop_string().unwrap(), "hello,  world");
+}
+
+#[test]
+fn test_errors_are_returned() {
+    let mut forth = Interpreter::new();
+
+    assert!(matches!(forth.eval("NO-SUCH-WORD").unwrap_err().kind(), QuarterError::UndefinedWord(_)));
+    assert!(matches!(forth.call("NO-SUCH-WORD"), Err(QuarterError::UndefinedWord(_))));
+    assert_eq!(forth.eval("1 0 /").unwrap_err().kind(), &QuarterError::DivisionByZero);
+
+    // The interpreter is still usable
+    forth.eval("1 2 +").unwrap();
+    assert_eq!(forth.pop::<i64>().unwrap(), 3);
+}
+
+#[test]
+fn test_define_native() {
+    let mut forth = Interpreter::new();
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let seen = Rc::clone(&log);
+    forth.define_native("RECORD", move |ctx| {
+        let n: i64 = ctx.pop()?;
+        seen.borrow_mut().push(n);
+        ctx.push(n * 2)
+    });
+    forth.define_native("FAIL", |_ctx| Err(QuarterError::Throw(-99)));
+
+    // Native words can be compiled into colon definitions
+    forth.eval(": TWICE RECORD RECORD ;").unwrap();
+    forth.eval("3 TWICE").unwrap();
+    assert_eq!(forth.pop::<i64>().unwrap(), 12);
+    assert_eq!(*log.borrow(), vec![3, 6]);
+
+    // Their errors can be caught like any other
+    forth.eval("' FAIL CATCH").unwrap();
+    assert_eq!(forth.pop::<i64>().unwrap(), -99);
+    assert!(matches!(forth.eval("RECORD").unwrap_err().kind(), QuarterError::StackUnderflow(_)));
+}
+
+#[test]
+fn test_load_file() {
+    let mut forth = Interpreter::new();
+    let path = temp_path("interpreter-load.fth");
+    std::fs::write(&path, ": GREET S\" hi\" ;\n5 CONSTANT FIVE\n").unwrap();
+
+    forth.load(&path).unwrap();
+    forth.eval("GREET FIVE").unwrap();
+    assert_eq!(forth.pop::<i64>().unwrap(), 5);
+    assert_eq!(forth.pop_string().unwrap(), "hi");
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_interpreters_are_independent() {
+    let mut first = Interpreter::new();
+    let mut second = Interpreter::new();
+
+    first.eval(": ANSWER 42 ;").unwrap();
+    assert!(second.call("ANSWER").is_err());
+
+    // EVALUATE runs in the interpreter that called it
+    first.eval("S\" ANSWER\" EVALUATE").unwrap();
+    second.eval("S\" : ANSWER 7 ; ANSWER\" EVALUATE").unwrap();
+    assert_eq!(first.pop::<i64>().unwrap(), 42);
+    assert_eq!(second.pop::<i64>().unwrap(), 7);
+}
+
+#[test]
+fn test_interpreter_called_from_another() {
+    let mut outer = Interpreter::new();
+    let inner = Rc::new(RefCell::new(Interpreter::new()));
+    inner.borrow_mut().eval(": ANSWER 7 ;").unwrap();
+
+    let nested = Rc::clone(&inner);
+    outer.define_native("ASK-INNER", move |ctx| {
+        let mut inner = nested.borrow_mut();
+        inner.eval("S\" ANSWER\" EVALUATE")?;
+        ctx.push(inner.pop::<i64>()?)
+    });
+    outer.eval(": ANSWER 42 ;").unwrap();
+
+    // The outer EVALUATE carries on in the outer interpreter once the inner one returns
+    outer.eval("S\" ASK-INNER ANSWER\" EVALUATE").unwrap();
+    assert_eq!(outer.stack(), vec![7, 42]);
+}
+
+#[test]
+fn test_interpreters_on_separate_threads() {
+    let threads: Vec<_> = (1..=4)
+        .map(|n| {
+            std::thread::spawn(move || {
+                let mut forth = Interpreter::new();
+                forth.eval(&format!(": ID {} ;", n)).unwrap();
+                forth.eval(": SUM 0 100 0 DO S\" ID\" EVALUATE + LOOP ; SUM").unwrap();
+                forth.pop::<i64>().unwrap()
+            })
+        })
+        .collect();
+
+    for (n, thread) in (1..=4).zip(threads) {
+        assert_eq!(thread.join().unwrap(), n * 100);
+    }
+}
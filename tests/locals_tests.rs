@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+use quarter::{execute_line, Dictionary, LoopStack, Memory, ReturnStack, Stack, RuntimeContext, CompilerConfig, ExecutionOptions};
+
+#[test]
+fn test_locals_take_arguments_in_order() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": DIFF {: a b -- c :} a b - ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("10 3 DIFF", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(7));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_uninitialized_locals_and_to() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // Sum 1..n using a local accumulator
+        execute_line(": SUM-TO {: n | acc -- sum :} n 1+ 1 DO I +TO acc LOOP acc ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": ZERO {: | x :} x ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("10 SUM-TO ZERO", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(55));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_locals_are_per_call() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // Recursion: each call sees its own n, including after the recursive call returns
+        execute_line(": FACT {: n :} n 2 < IF 1 ELSE n 1- RECURSE n * THEN ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        // A word with locals calling another one
+        execute_line(": INNER {: x :} x x * ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": OUTER {: x :} x 1+ INNER x + ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("5 FACT 3 OUTER", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(19));
+    assert_eq!(stack.pop(&mut memory), Some(120));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_locals_frame_dropped_on_exit() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": CLAMP {: x :} x 0< IF 0 EXIT THEN x ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": USE-CLAMP {: y :} y CLAMP y ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("-4 USE-CLAMP", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(-4));
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_locals_shadow_words() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": SHADOW {: dup :} dup dup + ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("21 SHADOW", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(42));
+    assert!(stack.is_empty());
+}
@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+use quarter::{execute_line, Dictionary, LoopStack, Memory, ReturnStack, Stack, RuntimeContext, CompilerConfig, ExecutionOptions};
+
+#[test]
+fn test_marker_restores_dictionary_and_here() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    let here = memory.here();
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": SQUARE DUP * ; 7 VALUE SIZE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("MARKER EXPERIMENT", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": SQUARE ( n -- n ) DROP 0 ; IMMEDIATE 1 VALUE SIZE CREATE SCRATCH 100 ALLOT", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("EXPERIMENT", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("3 SQUARE 2 TO SIZE SIZE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(2));
+    assert_eq!(stack.pop(&mut memory), Some(9));
+    assert!(stack.is_empty());
+    assert!(!dict.has_word("SCRATCH"));
+    assert!(!dict.has_word("EXPERIMENT"));
+    assert!(!dict.is_immediate("SQUARE"));
+    assert_eq!(dict.get_last_defined_word().map(|s| s.as_str()), Some("SIZE"));
+    // Only the VALUE cell defined before the marker is still allotted
+    assert_eq!(memory.here(), here + 8);
+}
+
+#[test]
+fn test_marker_restores_search_order() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("MARKER CLEAN WORDLIST DUP SET-CURRENT FORTH-WORDLIST SWAP 2 SET-ORDER", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": PRIVATE-WORD 1 ; CLEAN GET-CURRENT GET-ORDER", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert!(stack.is_empty());
+    assert!(!dict.has_word("PRIVATE-WORD"));
+}
+
+#[test]
+fn test_forget_removes_later_words() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    let here = memory.here();
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": GREET 1 ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("VARIABLE COUNTER : GREET 2 ; : FAREWELL 3 ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("FORGET COUNTER GREET", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert!(stack.is_empty());
+    assert!(!dict.has_word("COUNTER"));
+    assert!(!dict.has_word("FAREWELL"));
+    assert_eq!(memory.here(), here);
+}
+
+#[test]
+fn test_forget_rejects_built_in_and_unknown_words() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+    let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+
+    assert!(execute_line("FORGET DUP", &mut ctx, config, options, &mut HashSet::new()).is_err());
+    assert!(execute_line("FORGET NO-SUCH-WORD", &mut ctx, config, options, &mut HashSet::new()).is_err());
+    assert!(ctx.dict.has_word("DUP"));
+}
@@ -0,0 +1,102 @@
+use std::collections::HashSet;
+use std::cell::Cell;
+use std::rc::Rc;
+use quarter::{execute_line, Dictionary, Interpreter, LoopStack, Memory, QuarterError, ReturnStack, Stack, RuntimeContext, CompilerConfig, ExecutionOptions};
+
+#[test]
+fn test_native_word_keeps_state() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+    let calls = Rc::new(Cell::new(0));
+
+    let counted = Rc::clone(&calls);
+    let mut next_id = 100;
+    dict.add_native("next-id", move |ctx| {
+        counted.set(counted.get() + 1);
+        next_id += 1;
+        ctx.push(next_id)
+    });
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": TWO-IDS NEXT-ID NEXT-ID ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("TWO-IDS ' NEXT-ID EXECUTE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(103));
+    assert_eq!(stack.pop(&mut memory), Some(102));
+    assert_eq!(stack.pop(&mut memory), Some(101));
+    assert!(stack.is_empty());
+    assert_eq!(calls.get(), 3);
+}
+
+#[test]
+fn test_native_word_errors_unwind() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    dict.add_native("CHECKED-SQRT", |ctx| {
+        let n: i64 = ctx.pop()?;
+        if n < 0 {
+            return Err(QuarterError::Throw(-24));
+        }
+        ctx.push((n as f64).sqrt() as i64)
+    });
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": ROOT CHECKED-SQRT 1 + ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        let error = execute_line("CHECKED-SQRT", &mut ctx, config, options, &mut HashSet::new()).unwrap_err();
+        assert!(matches!(error.kind(), QuarterError::StackUnderflow(_)));
+        execute_line("16 ' ROOT CATCH -9 ' ROOT CATCH", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    // CATCH restores the stack below the xt, so -9 is left under the code
+    assert_eq!(stack.pop(&mut memory), Some(-24));
+    assert_eq!(stack.pop(&mut memory), Some(-9));
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(5));
+    assert!(stack.is_empty());
+}
+
+// Call a word the way compiled code does, through the native-word trampoline
+fn call_through_trampoline(ctx: &mut quarter::NativeContext) -> Result<(), QuarterError> {
+    unsafe {
+        quarter::words::quarter_call_native(ctx.memory.as_mut_ptr(), ctx.stack.sp_mut_ptr(), ctx.return_stack.rp_mut_ptr());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_trampoline_runs_native_word_by_xt() {
+    let mut forth = Interpreter::new();
+
+    forth.define_native("DOUBLE", |ctx| {
+        let n: i64 = ctx.pop()?;
+        ctx.push(n * 2)
+    });
+    forth.define_native("FAIL", |_ctx| Err(QuarterError::Throw(-99)));
+    forth.define_native("TRAMPOLINE", call_through_trampoline);
+
+    forth.eval("21 ' DOUBLE TRAMPOLINE").unwrap();
+    assert_eq!(forth.pop::<i64>().unwrap(), 42);
+
+    // The native word's error is left pending, as compiled code expects a THROW
+    forth.eval("' FAIL ' TRAMPOLINE CATCH").unwrap();
+    assert_eq!(forth.pop::<i64>().unwrap(), -99);
+    assert_eq!(forth.depth(), 1);
+}
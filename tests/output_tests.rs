@@ -1,5 +1,16 @@
+use std::cell::RefCell;
 use std::collections::HashSet;
+use std::rc::Rc;
 use quarter::{execute_line, Dictionary, LoopStack, Memory, ReturnStack, Stack, RuntimeContext, CompilerConfig, ExecutionOptions};
+use quarter::{Interpreter, Output};
+
+// What has been printed to a buffer output since the last call
+fn take_printed(memory: &mut Memory) -> String {
+    match &mut memory.session().output {
+        Output::Buffer(buffer) => String::from_utf8(std::mem::take(buffer)).unwrap(),
+        _ => panic!("output is not a buffer"),
+    }
+}
 
 #[test]
 fn test_u_dot() {
@@ -8,6 +19,7 @@ fn test_u_dot() {
     let mut loop_stack = LoopStack::new();
     let mut return_stack = ReturnStack::new();
     let mut memory = Memory::new();
+    memory.session().output = Output::buffer();
 
     let config = CompilerConfig::new(false, false, false);
     let options = ExecutionOptions::new(false, false);
@@ -26,6 +38,7 @@ fn test_u_dot() {
         .unwrap();
     }
     assert!(stack.is_empty());
+    assert_eq!(take_printed(&mut memory), "42 ");
 
     {
         let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
@@ -41,6 +54,7 @@ fn test_u_dot() {
         .unwrap();
     }
     assert!(stack.is_empty());
+    assert_eq!(take_printed(&mut memory), "18446744073709551615 ");
 }
 
 #[test]
@@ -50,6 +64,7 @@ fn test_dot_r() {
     let mut loop_stack = LoopStack::new();
     let mut return_stack = ReturnStack::new();
     let mut memory = Memory::new();
+    memory.session().output = Output::buffer();
 
     let config = CompilerConfig::new(false, false, false);
     let options = ExecutionOptions::new(false, false);
@@ -68,6 +83,7 @@ fn test_dot_r() {
         .unwrap();
     }
     assert!(stack.is_empty());
+    assert_eq!(take_printed(&mut memory), "        42 ");
 
     {
         let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
@@ -83,6 +99,7 @@ fn test_dot_r() {
         .unwrap();
     }
     assert!(stack.is_empty());
+    assert_eq!(take_printed(&mut memory), "    -123 ");
 
     {
         let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
@@ -98,6 +115,7 @@ fn test_dot_r() {
         .unwrap();
     }
     assert!(stack.is_empty());
+    assert_eq!(take_printed(&mut memory), "12345 ");
 }
 
 #[test]
@@ -107,6 +125,7 @@ fn test_u_dot_r() {
     let mut loop_stack = LoopStack::new();
     let mut return_stack = ReturnStack::new();
     let mut memory = Memory::new();
+    memory.session().output = Output::buffer();
 
     let config = CompilerConfig::new(false, false, false);
     let options = ExecutionOptions::new(false, false);
@@ -125,6 +144,7 @@ fn test_u_dot_r() {
         .unwrap();
     }
     assert!(stack.is_empty());
+    assert_eq!(take_printed(&mut memory), "        42 ");
 
     {
         let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
@@ -140,4 +160,109 @@ fn test_u_dot_r() {
         .unwrap();
     }
     assert!(stack.is_empty());
+    assert_eq!(take_printed(&mut memory), "18446744073709551615 ");
+}
+
+// An interpreter writing to an in-memory buffer
+fn buffered() -> Interpreter {
+    let mut forth = Interpreter::new();
+    forth.set_output(Output::buffer());
+    forth
+}
+
+#[test]
+fn test_output_words_write_to_buffer() {
+    let mut forth = buffered();
+
+    forth.eval("42 . -1 U. 7 4 .R CR 65 EMIT SPACE S\" hi\" TYPE").unwrap();
+    assert_eq!(forth.take_output(), "42 18446744073709551615    7 \nA hi");
+
+    forth.eval(": GREET .\" hello\" ; GREET 1 2 D. 1.5E0 F.").unwrap();
+    assert_eq!(forth.take_output(), "hello36893488147419103233 1.5 ");
+
+    // Taking the output empties the buffer
+    assert_eq!(forth.take_output(), "");
+}
+
+#[test]
+fn test_output_to_callback_and_file() {
+    let mut forth = Interpreter::new();
+    let seen = Rc::new(RefCell::new(Vec::new()));
+
+    let sink = Rc::clone(&seen);
+    forth.set_output(Output::callback(move |bytes| sink.borrow_mut().extend_from_slice(bytes)));
+    forth.eval("1 . 2 .").unwrap();
+    assert_eq!(*seen.borrow(), b"1 2 ");
+
+    let path = std::env::temp_dir().join(format!("quarter-{}-output.txt", std::process::id()));
+    forth.set_output(Output::file(&path).unwrap());
+    forth.eval(".\" to a file\" CR").unwrap();
+    // Putting stdout back drops the file, closing it
+    forth.set_output(Output::Stdout);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "to a file\n");
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_capture_to_string() {
+    let mut forth = buffered();
+
+    forth.eval(": SHOW ( n -- ) .\" <\" 0 .R .\" >\" ;").unwrap();
+    forth.eval(">STRING 42 SHOW STRING>").unwrap();
+    assert_eq!(forth.pop_string().unwrap(), "<42 >");
+    assert_eq!(forth.take_output(), "");
+
+    // Captures nest, and the string is ALLOCATEd
+    forth.eval(">STRING 1 . >STRING 2 . STRING> TYPE 3 . STRING> 2DUP TYPE DROP FREE").unwrap();
+    assert_eq!(forth.take_output(), "1 2 3 ");
+    assert_eq!(forth.pop::<i64>().unwrap(), 0);
+}
+
+#[test]
+fn test_capture_unwound_by_errors() {
+    let mut forth = buffered();
+
+    // CATCH closes the captures opened inside it
+    forth.eval(": PARTIAL >STRING 5 . 1 0 / STRING> ; ' PARTIAL CATCH . 9 .").unwrap();
+    assert_eq!(forth.take_output(), "-10 9 ");
+
+    // So does an error returned to the embedding program
+    assert!(forth.eval(">STRING 1 0 /").is_err());
+    forth.eval("3 .").unwrap();
+    assert_eq!(forth.take_output(), "3 ");
+
+    // STRING> with no capture open is a control structure mismatch
+    forth.eval("' STRING> CATCH").unwrap();
+    assert_eq!(forth.pop::<i64>().unwrap(), -22);
+}
+
+#[test]
+fn test_string_word_errors_are_thrown_not_printed() {
+    let mut forth = buffered();
+
+    // Errors from string words reach CATCH instead of the output
+    forth.eval("S\" abc\" DROP -1 ' TYPE CATCH .").unwrap();
+    assert_eq!(forth.take_output(), "-24 ");
+    forth.eval("S\" abc\" DROP -1 2DUP ' COMPARE CATCH .").unwrap();
+    assert_eq!(forth.take_output(), "-24 ");
+
+    let mut forth = buffered();
+    forth.eval("' -TRAILING CATCH .").unwrap();
+    assert_eq!(forth.take_output(), "-4 ");
+    assert!(forth.eval("TYPE").is_err());
+    assert_eq!(forth.take_output(), "");
+}
+
+#[test]
+fn test_dot_s_is_captured() {
+    let mut forth = buffered();
+
+    // .S writes to the output like . does, leaving the stack alone
+    forth.eval(".S 1 2 -3 .S").unwrap();
+    assert_eq!(forth.take_output(), "<0> <3> 1 2 -3 ");
+    assert_eq!(forth.pop::<i64>().unwrap(), -3);
+
+    // So >STRING captures it
+    forth.eval(">STRING .S STRING> TYPE").unwrap();
+    assert_eq!(forth.take_output(), "<2> 1 2 ");
 }
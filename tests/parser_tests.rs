@@ -1,4 +1,4 @@
-use quarter::{LoopStack, parse_tokens, AstNode, Dictionary, ReturnStack, Stack, Memory};
+use quarter::{LoopStack, parse_tokens, AstNode, Dictionary, QuarterError, ReturnStack, Stack, Memory};
 
 #[test]
 fn test_parse_simple_number() {
@@ -111,7 +111,7 @@ fn test_parse_missing_then() {
     let tokens = vec!["1", "IF", "42"];
     let result = parse_tokens(&tokens, &dict, None);
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), "Missing THEN");
+    assert_eq!(result.unwrap_err(), QuarterError::Compile("Missing THEN".to_string()));
 }
 
 #[test]
@@ -120,7 +120,7 @@ fn test_parse_unexpected_then() {
     let tokens = vec!["42", "THEN"];
     let result = parse_tokens(&tokens, &dict, None);
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), "Unexpected THEN or ELSE");
+    assert_eq!(result.unwrap_err(), QuarterError::Compile("Unexpected THEN or ELSE".to_string()));
 }
 
 #[test]
@@ -129,7 +129,7 @@ fn test_parse_unexpected_else() {
     let tokens = vec!["42", "ELSE"];
     let result = parse_tokens(&tokens, &dict, None);
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), "Unexpected THEN or ELSE");
+    assert_eq!(result.unwrap_err(), QuarterError::Compile("Unexpected THEN or ELSE".to_string()));
 }
 
 #[test]
@@ -174,3 +174,79 @@ fn test_parse_empty_tokens() {
         assert_eq!(nodes.len(), 0);
     }
 }
+
+#[test]
+fn test_parse_case() {
+    let dict = Dictionary::new();
+    let tokens = vec!["CASE", "1", "OF", "10", "ENDOF", "2", "OF", "20", "ENDOF", "99", "SWAP", "ENDCASE"];
+    let ast = parse_tokens(&tokens, &dict, None).unwrap();
+
+    match ast {
+        AstNode::Case { clauses, default } => {
+            assert_eq!(clauses.len(), 2);
+            assert!(matches!(clauses[1].0[..], [AstNode::PushNumber(2)]));
+            assert!(matches!(clauses[1].1[..], [AstNode::PushNumber(20)]));
+            assert_eq!(default.len(), 2);
+        }
+        _ => panic!("Expected Case"),
+    }
+}
+
+#[test]
+fn test_parse_missing_endcase() {
+    let dict = Dictionary::new();
+    let tokens = vec!["CASE", "1", "OF", "10", "ENDOF"];
+    let result = parse_tokens(&tokens, &dict, None);
+    assert_eq!(result.unwrap_err(), QuarterError::Compile("Missing ENDCASE".to_string()));
+}
+
+#[test]
+fn test_parse_of_without_endof() {
+    let dict = Dictionary::new();
+    let tokens = vec!["CASE", "1", "OF", "10", "ENDCASE"];
+    let result = parse_tokens(&tokens, &dict, None);
+    assert_eq!(result.unwrap_err(), QuarterError::Compile("OF without ENDOF".to_string()));
+}
+
+#[test]
+fn test_parse_unexpected_endof() {
+    let dict = Dictionary::new();
+    let tokens = vec!["42", "ENDOF"];
+    let result = parse_tokens(&tokens, &dict, None);
+    assert_eq!(result.unwrap_err(), QuarterError::Compile("ENDOF without CASE".to_string()));
+}
+
+#[test]
+fn test_parse_locals() {
+    let dict = Dictionary::new();
+    let tokens = vec!["{:", "a", "b", "|", "c", "--", "d", ":}", "a", "TO", "c", "b", "c"];
+    let ast = parse_tokens(&tokens, &dict, Some("FOO")).unwrap();
+
+    match ast {
+        AstNode::Locals { count, args, body } => {
+            assert_eq!(count, 3);
+            assert_eq!(args, 2);
+            assert!(matches!(
+                body[..],
+                [AstNode::LocalFetch(0), AstNode::LocalStore(2), AstNode::LocalFetch(1), AstNode::LocalFetch(2)]
+            ));
+        }
+        _ => panic!("Expected Locals"),
+    }
+}
+
+#[test]
+fn test_parse_missing_locals_end() {
+    let dict = Dictionary::new();
+    let tokens = vec!["{:", "a", "b", "a"];
+    let result = parse_tokens(&tokens, &dict, Some("FOO"));
+    assert_eq!(result.unwrap_err(), QuarterError::Compile("Missing :}".to_string()));
+}
+
+#[test]
+fn test_parse_nested_locals() {
+    let dict = Dictionary::new();
+    let tokens = vec!["{:", "a", ":}", "{:", "b", ":}", "a", "b"];
+    let result = parse_tokens(&tokens, &dict, Some("FOO"));
+    assert!(result.is_err());
+}
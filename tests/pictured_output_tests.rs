@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+use quarter::{execute_line, Dictionary, LoopStack, Memory, ReturnStack, Stack, RuntimeContext, CompilerConfig, ExecutionOptions};
+
+// Pop ( c-addr u ) and read the string it describes
+fn pop_string(stack: &mut Stack, memory: &mut Memory) -> String {
+    let len = stack.pop(memory).unwrap() as usize;
+    let addr = stack.pop(memory).unwrap() as usize;
+    (0..len)
+        .map(|i| memory.fetch_byte(addr + i).unwrap() as u8 as char)
+        .collect()
+}
+
+#[test]
+fn test_number_sign_s_in_decimal_and_hex() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("<# 9876 0 #S #>", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(pop_string(&mut stack, &mut memory), "9876");
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("16 BASE ! <# 48879 0 #S #> 10 BASE !", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(pop_string(&mut stack, &mut memory), "BEEF");
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_zero_padding_with_number_sign() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line(": 4DIGITS ( u -- c-addr u ) 0 <# # # # # #> ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("42 4DIGITS", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(pop_string(&mut stack, &mut memory), "0042");
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_sign_hold_and_holds() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // Fixed-point currency: two decimals, a point, the sign and a prefix
+        execute_line(
+            ": MONEY ( n -- c-addr u ) DUP ABS 0 <# # # 46 HOLD #S ROT SIGN S\" EUR \" HOLDS #> ;",
+            &mut ctx, config, options, &mut HashSet::new(),
+        )
+        .unwrap();
+        execute_line("-123456 MONEY", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(pop_string(&mut stack, &mut memory), "EUR -1234.56");
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_full_width_double() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // 128 binary digits must fit in the hold buffer
+        execute_line("2 BASE ! <# -1 -1 #S #> 10 BASE !", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(pop_string(&mut stack, &mut memory), "1".repeat(128));
+    assert!(stack.is_empty());
+}
@@ -1,4 +1,4 @@
-use quarter::{Dictionary, LoopStack, Memory, ReturnStack, Stack};
+use quarter::{Dictionary, LoopStack, Memory, QuarterError, ReturnStack, Stack};
 
 // Test CMOVE primitive
 #[test]
@@ -86,9 +86,7 @@ fn test_cmove_large_block() {
 
 #[test]
 fn test_throw_with_nonzero() {
-    // THROW currently just prints a message and doesn't actually throw
-    // This test documents the current behavior
-    // See issue #80 for proper THROW implementation
+    // Without a CATCH, a non-zero THROW unwinds all the way out as an error
     let mut stack = Stack::new();
     let dict = Dictionary::new();
     let mut loop_stack = LoopStack::new();
@@ -98,12 +96,10 @@ fn test_throw_with_nonzero() {
     // Push non-zero error code
     stack.push(42, &mut memory);
 
-    // THROW pops the value, prints message, then pushes it back (for CATCH to see)
-    dict.execute_word("THROW", &mut stack, &mut loop_stack, &mut return_stack, &mut memory)
-        .unwrap();
+    let result = dict.execute_word("THROW", &mut stack, &mut loop_stack, &mut return_stack, &mut memory);
 
-    // Stack should have the error code back on it (current behavior)
-    assert_eq!(stack.pop(&mut memory), Some(42));
+    // The code is consumed and carried by the error
+    assert_eq!(result, Err(QuarterError::Throw(42)));
     assert!(stack.is_empty());
 }
 
@@ -0,0 +1,120 @@
+use std::collections::HashSet;
+use quarter::{load_file, Dictionary, LoopStack, Memory, ReturnStack, Stack, RuntimeContext, CompilerConfig, ExecutionOptions, QuarterError, Source};
+
+// A scratch file name unique to this test
+fn temp_path(name: &str) -> String {
+    std::env::temp_dir()
+        .join(format!("quarter-{}-{}", std::process::id(), name))
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[test]
+fn test_undefined_word_reports_file_line_and_column() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    let path = temp_path("undefined.fth");
+    std::fs::write(&path, ": GOOD 1 ; \\ fine\n\n( a comment ) : BAD\n    GOOD foo ;\n").unwrap();
+
+    let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+    let result = load_file(&path, &mut ctx, config, options, &mut HashSet::new());
+    std::fs::remove_file(&path).unwrap();
+
+    let error = result.unwrap_err();
+    assert_eq!(error.to_string(), format!("{}:4:10: undefined word FOO\n    GOOD foo ;\n         ^^^", path));
+    // The location does not hide what went wrong
+    assert_eq!(error.kind(), &QuarterError::UndefinedWord("FOO".to_string()));
+    assert_eq!(error.code(), -13);
+}
+
+#[test]
+fn test_runtime_error_points_at_the_failing_word() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    let path = temp_path("runtime.fth");
+    std::fs::write(&path, ": HALF 2 / ;\n1 2 + DROP 10 HALF\n0 0 / DROP\n").unwrap();
+
+    let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+    let result = load_file(&path, &mut ctx, config, options, &mut HashSet::new());
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(result.unwrap_err().to_string(), format!("{}:3:5: Division by zero\n0 0 / DROP\n    ^", path));
+}
+
+#[test]
+fn test_error_while_compiling_points_at_the_immediate_word() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    let path = temp_path("immediate.fth");
+    std::fs::write(&path, ": REFUSE -3 THROW ; IMMEDIATE\n: USER 1\n  2 REFUSE ;\n").unwrap();
+
+    let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+    let result = load_file(&path, &mut ctx, config, options, &mut HashSet::new());
+    std::fs::remove_file(&path).unwrap();
+
+    let error = result.unwrap_err();
+    assert_eq!(error.to_string(), format!("{}:3:5: THROW -3\n  2 REFUSE ;\n    ^^^^^^", path));
+    assert_eq!(error.code(), -3);
+}
+
+#[test]
+fn test_errors_in_included_files_name_the_include_chain() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    let inner = temp_path("inner.fth");
+    let outer = temp_path("outer.fth");
+    std::fs::write(&inner, "1 DROP\n: BROKEN MISSING ;\n").unwrap();
+    std::fs::write(&outer, format!("\\ loads the inner file\n  INCLUDE {}\n", inner)).unwrap();
+
+    let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+    let result = load_file(&outer, &mut ctx, config, options, &mut HashSet::new());
+    std::fs::remove_file(&inner).unwrap();
+    std::fs::remove_file(&outer).unwrap();
+
+    assert_eq!(result.unwrap_err().to_string(), format!(
+        "{}:2:10: undefined word MISSING\n: BROKEN MISSING ;\n         ^^^^^^^\nincluded from {}:2:3",
+        inner, outer
+    ));
+}
+
+#[test]
+fn test_source_tokens_keep_their_spans() {
+    let source = Source::new("test.fth", "1 .( hi  there ) ( skipped )\n\tDUP \\ gone\n");
+    let tokens = source.tokens();
+    // The text of a string is one token, spacing and all
+    assert_eq!(tokens, vec!["1", ".(", "hi  there )", "DUP"]);
+
+    let span = source.span(tokens[3]).unwrap();
+    assert_eq!((span.line, span.column), (2, 2));
+    assert_eq!(span.to_string(), "test.fth:2:2");
+    // Only slices of the source text have a place in it
+    assert_eq!(source.span("DUP"), None);
+}
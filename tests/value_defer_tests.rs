@@ -0,0 +1,171 @@
+use std::collections::HashSet;
+use quarter::{execute_line, Dictionary, LoopStack, Memory, ReturnStack, Stack, RuntimeContext, CompilerConfig, ExecutionOptions};
+
+#[test]
+fn test_value_to_and_plus_to() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("10 VALUE COUNTER", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("COUNTER 25 TO COUNTER COUNTER 5 +TO COUNTER COUNTER", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(30));
+    assert_eq!(stack.pop(&mut memory), Some(25));
+    assert_eq!(stack.pop(&mut memory), Some(10));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_value_in_compiled_word() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("0 VALUE TOTAL", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": ADD-TOTAL ( n -- ) +TO TOTAL ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": RESET-TOTAL 0 TO TOTAL ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        // The new contents are read each time TOTAL runs, not inlined when USE-TOTAL is compiled
+        execute_line(": USE-TOTAL TOTAL ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("3 ADD-TOTAL 4 ADD-TOTAL USE-TOTAL RESET-TOTAL USE-TOTAL", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(0));
+    assert_eq!(stack.pop(&mut memory), Some(7));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_to_requires_value() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+    let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+
+    execute_line("5 CONSTANT FIVE", &mut ctx, config, options, &mut HashSet::new())
+        .unwrap();
+    let result = execute_line("6 TO FIVE", &mut ctx, config, options, &mut HashSet::new());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_defer_is_rebinds_callers() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("DEFER OP", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": APPLY ( a b -- c ) OP ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("' + IS OP 3 4 APPLY", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("' * IS OP 3 4 APPLY", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(12));
+    assert_eq!(stack.pop(&mut memory), Some(7));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_is_and_action_of_in_definition() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("DEFER OP", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": USE-MINUS ['] - IS OP ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": CURRENT-OP ACTION-OF OP ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("USE-MINUS 10 3 CURRENT-OP EXECUTE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(7));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_defer_fetch_and_store() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("DEFER OP", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("' NEGATE ' OP DEFER! 5 OP", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("6 ' OP DEFER@ EXECUTE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(-6));
+    assert_eq!(stack.pop(&mut memory), Some(-5));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_uninitialized_defer_errors() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+    let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+
+    execute_line("DEFER OP", &mut ctx, config, options, &mut HashSet::new())
+        .unwrap();
+    assert!(execute_line("OP", &mut ctx, config, options, &mut HashSet::new()).is_err());
+    // DEFER@ and DEFER! only accept deferred words
+    assert!(execute_line("' DUP DEFER@", &mut ctx, config, options, &mut HashSet::new()).is_err());
+}
@@ -446,3 +446,219 @@ fn test_comma_and_fetch() {
         .unwrap();
     assert_eq!(stack.pop(&mut memory), Some(30));
 }
+
+#[test]
+fn test_create_does_constant() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+
+        // Defining word whose children fetch their stored value
+        execute_line(": MYCONST CREATE , DOES> @ ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("42 MYCONST ANSWER 7 MYCONST SEVEN", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("ANSWER SEVEN", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(7));
+    assert_eq!(stack.pop(&mut memory), Some(42));
+}
+
+#[test]
+fn test_create_does_data_field_address() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    let data_addr = memory.here();
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+
+        // Child pushes its data-field address, then runs the DOES> body
+        execute_line(": ARRAY CREATE 8 * ALLOT DOES> SWAP 8 * + ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("4 ARRAY NUMS", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("0 NUMS 3 NUMS", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(data_addr + 24));
+    assert_eq!(stack.pop(&mut memory), Some(data_addr));
+    assert_eq!(memory.here(), data_addr + 32);
+}
+
+#[test]
+fn test_does_changes_the_word_created_last() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+
+        // DOES> needn't share a definition with CREATE
+        execute_line(": FETCHES DOES> @ ; CREATE X 5 , FETCHES X", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+
+        // With no word made by CREATE since the last definition, there is nothing to change
+        let result = execute_line(": BAD 1 DOES> @ ; BAD", &mut ctx, config, options, &mut HashSet::new());
+        assert!(result.is_err());
+    }
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert_eq!(stack.pop(&mut memory), Some(5));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_create_runs_where_it_is_reached() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+
+        // CREATE inside IF runs only when the branch is taken
+        execute_line(": MAYBE ( flag \"name\" -- ) IF CREATE 1 , ELSE 2 THEN ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("-1 MAYBE YES 0 MAYBE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("YES @", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+
+        // Each CREATE in a loop parses a name of its own
+        execute_line(": CELLS-NAMED ( n \"name\"... -- ) 0 DO CREATE I , LOOP ; 2 CELLS-NAMED C0 C1 C1 @", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert_eq!(stack.pop(&mut memory), Some(2));
+    assert!(stack.is_empty());
+    assert!(dict.has_word("C0"));
+}
+
+#[test]
+fn test_defining_word_built_on_another() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+
+        // COUNTER makes its child with CELL-VAR, then gives it a behaviour of its own
+        execute_line(": CELL-VAR ( x \"name\" -- ) CREATE , DOES> ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": COUNTER ( \"name\" -- ) 0 CELL-VAR DOES> DUP @ 1 + DUP ROT ! ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("7 CELL-VAR SEVEN COUNTER TICKS TICKS TICKS SEVEN @", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+
+        // Redefining a defining word leaves the words it made running the old DOES> code
+        execute_line(": COUNTER ( \"name\" -- ) 0 CELL-VAR DOES> DUP @ 10 + DUP ROT ! ; COUNTER TENS TENS TICKS", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(3));
+    assert_eq!(stack.pop(&mut memory), Some(10));
+    assert_eq!(stack.pop(&mut memory), Some(7));
+    assert_eq!(stack.pop(&mut memory), Some(2));
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_defining_word_called_from_definition_and_execute() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+
+        // The defining word parses each child's name from the input where it's called
+        execute_line(": MYCONST CREATE , DOES> @ ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": TWO-CONSTS 1 MYCONST 2 MYCONST ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("TWO-CONSTS ONE TWO-ALSO 3 ' MYCONST EXECUTE THREE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": BUFFER ['] CREATE EXECUTE 4 , ; BUFFER BUF", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("ONE TWO-ALSO THREE BUF @", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+
+        let result = execute_line(": NAMELESS MYCONST ; 5 NAMELESS", &mut ctx, config, options, &mut HashSet::new());
+        assert!(result.is_err());
+    }
+    assert_eq!(stack.pop(&mut memory), Some(5));
+    assert_eq!(stack.pop(&mut memory), Some(4));
+    assert_eq!(stack.pop(&mut memory), Some(3));
+    assert_eq!(stack.pop(&mut memory), Some(2));
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_create_as_a_name_or_string_is_not_a_defining_word() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+
+        // Neither definition creates anything when it runs
+        execute_line(": MY-CREATE POSTPONE CREATE ; IMMEDIATE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": SAYS S\" CREATE X\" SWAP DROP ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("SAYS", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+
+        // MY-CREATE compiles CREATE into the word that uses it
+        execute_line(": CELL-VAR MY-CREATE 6 , ; CELL-VAR SIX SIX @", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(6));
+    assert_eq!(stack.pop(&mut memory), Some(8));
+    assert!(stack.is_empty());
+}
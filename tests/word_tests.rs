@@ -1,5 +1,5 @@
 use std::collections::HashSet;
-use quarter::{Dictionary, LoopStack, Memory, ReturnStack, Stack, parse_tokens, execute_line, RuntimeContext, CompilerConfig, ExecutionOptions};
+use quarter::{Dictionary, LoopStack, Memory, ReturnStack, Stack, parse_tokens, execute_line, QuarterError, RuntimeContext, CompilerConfig, ExecutionOptions};
 
 #[test]
 fn test_dot_word() {
@@ -1029,7 +1029,7 @@ fn test_recurse_outside_definition() {
     assert!(result.is_err());
     assert_eq!(
         result.unwrap_err(),
-        "RECURSE can only be used inside a word definition"
+        QuarterError::Compile("RECURSE can only be used inside a word definition".to_string())
     );
 }
 
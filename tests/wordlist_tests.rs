@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+use quarter::{execute_line, load_file, Dictionary, LoopStack, Memory, ReturnStack, Stack, RuntimeContext, CompilerConfig, ExecutionOptions};
+
+#[test]
+fn test_vocabulary_shadows_forth_word() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // ALSO, DEFINITIONS, PREVIOUS and VOCABULARY are defined in core.fth
+        load_file("stdlib/core.fth", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("VOCABULARY TRIG ALSO TRIG DEFINITIONS", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": NEGATE ( n -- n ) 100 + ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("PREVIOUS DEFINITIONS 5 NEGATE ALSO TRIG 5 NEGATE PREVIOUS", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(105));
+    assert_eq!(stack.pop(&mut memory), Some(-5));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_definitions_keep_their_bindings() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        execute_line("WORDLIST CONSTANT PRIVATE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("FORTH-WORDLIST PRIVATE 2 SET-ORDER PRIVATE SET-CURRENT", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": HELPER 7 ; : API HELPER 1+ ;", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("FORTH-WORDLIST SET-CURRENT -1 SET-ORDER", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        // Words in PRIVATE can't be found by name, but still call each other
+        execute_line("S\" API\" PRIVATE SEARCH-WORDLIST DROP EXECUTE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line(": HELPER 100 ; S\" API\" PRIVATE SEARCH-WORDLIST DROP EXECUTE", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(8));
+    assert_eq!(stack.pop(&mut memory), Some(8));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_private_words_are_not_found() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+    let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+
+    execute_line("WORDLIST SET-CURRENT : HIDDEN 1 ; FORTH-WORDLIST SET-CURRENT", &mut ctx, config, options, &mut HashSet::new())
+        .unwrap();
+    assert!(execute_line(": USE-HIDDEN HIDDEN ;", &mut ctx, config, options, &mut HashSet::new()).is_err());
+    assert!(execute_line("' HIDDEN", &mut ctx, config, options, &mut HashSet::new()).is_err());
+}
+
+#[test]
+fn test_get_and_set_order() {
+    let mut stack = Stack::new();
+    let mut dict = Dictionary::new();
+    let mut loop_stack = LoopStack::new();
+    let mut return_stack = ReturnStack::new();
+    let mut memory = Memory::new();
+
+    let config = CompilerConfig::new(false, false, false);
+    let options = ExecutionOptions::new(false, false);
+
+    {
+        let mut ctx = RuntimeContext::new(&mut stack, &mut dict, &mut loop_stack, &mut return_stack, &mut memory);
+        // wid1 (searched first) is on top
+        execute_line("FORTH-WORDLIST WORDLIST 2 SET-ORDER GET-ORDER", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+        execute_line("-1 SET-ORDER GET-ORDER", &mut ctx, config, options, &mut HashSet::new())
+            .unwrap();
+    }
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert_eq!(stack.pop(&mut memory), Some(2));
+    assert_eq!(stack.pop(&mut memory), Some(3));
+    assert_eq!(stack.pop(&mut memory), Some(1));
+    assert!(stack.is_empty());
+}